@@ -0,0 +1,31 @@
+-- Create dry_run_log table for submissions simulated in dry-run mode
+CREATE TABLE IF NOT EXISTS dry_run_log (
+    id SERIAL PRIMARY KEY,
+    -- What would have been submitted
+    context_type VARCHAR(20) NOT NULL, -- 'datafeed' or 'scheduled_task'
+    name VARCHAR(255) NOT NULL,
+    network_name VARCHAR(255) NOT NULL,
+    contract_address VARCHAR(42) NOT NULL,
+    round_id BIGINT, -- Datafeed submissions only
+    feed_value DOUBLE PRECISION, -- Datafeed submissions only
+    scaled_value NUMERIC(78, 0), -- Datafeed submissions only
+
+    -- Gas estimate
+    gas_limit BIGINT NOT NULL,
+    gas_price_gwei DOUBLE PRECISION NOT NULL,
+    estimated_cost_wei NUMERIC(78, 0) NOT NULL,
+    estimated_cost_usd DOUBLE PRECISION,
+
+    -- Simulation result
+    status VARCHAR(20) NOT NULL, -- 'success' or 'reverted'
+    error_message TEXT,
+
+    -- Timestamps
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Index for comparing decisions per feed over time
+CREATE INDEX idx_dry_run_log_name_created
+ON dry_run_log(name, network_name, created_at DESC);
+
+COMMENT ON TABLE dry_run_log IS 'Stores transactions Omikuji built and simulated in dry-run mode without broadcasting them';
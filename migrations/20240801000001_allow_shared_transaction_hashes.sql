@@ -0,0 +1,6 @@
+-- Batched submissions share one transaction, which is logged once per feed
+-- with the feed's share of the gas
+ALTER TABLE transaction_log DROP CONSTRAINT IF EXISTS unique_tx_hash;
+
+ALTER TABLE transaction_log
+ADD CONSTRAINT unique_tx_hash_feed UNIQUE (tx_hash, feed_name);
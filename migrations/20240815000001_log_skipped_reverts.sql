@@ -0,0 +1,3 @@
+-- Transactions skipped because the pre-flight simulation reverted are logged
+-- with status 'reverted' and the decoded reason, but were never sent
+ALTER TABLE transaction_log ALTER COLUMN tx_hash DROP NOT NULL;
@@ -0,0 +1,35 @@
+-- Create pending_transactions table for transactions broadcast but not yet resolved
+-- Rows are written before the transaction is sent and deleted once its nonce is
+-- mined, so after a restart the remaining rows are still in flight
+CREATE TABLE IF NOT EXISTS pending_transactions (
+    id SERIAL PRIMARY KEY,
+    -- Transaction identification
+    tx_hash VARCHAR(66) NOT NULL,
+    network_name VARCHAR(255) NOT NULL,
+    sender VARCHAR(42) NOT NULL,
+    nonce BIGINT NOT NULL,
+    raw_tx BYTEA NOT NULL, -- Signed EIP-2718 envelope, rebroadcast if dropped
+
+    -- What the transaction was sent for
+    context_type VARCHAR(20) NOT NULL, -- 'datafeed' or 'scheduled_task'
+    names TEXT[] NOT NULL, -- Feeds sharing the transaction, or the task
+
+    -- Gas settings
+    tx_type VARCHAR(20) NOT NULL, -- 'legacy' or 'eip1559'
+    gas_limit BIGINT NOT NULL,
+    gas_price_wei NUMERIC(78, 0), -- Legacy transactions
+    max_fee_per_gas_wei NUMERIC(78, 0), -- EIP-1559 transactions
+    max_priority_fee_per_gas_wei NUMERIC(78, 0), -- EIP-1559 transactions
+    attempt INTEGER NOT NULL DEFAULT 1, -- Replacements of the nonce sent so far
+
+    -- Timestamps
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT unique_pending_tx_hash UNIQUE (tx_hash)
+);
+
+-- Index for resolving every replacement of a nonce at once
+CREATE INDEX idx_pending_transactions_nonce
+ON pending_transactions(network_name, sender, nonce);
+
+COMMENT ON TABLE pending_transactions IS 'Stores transactions Omikuji broadcast that are not mined yet, so they are tracked across restarts';
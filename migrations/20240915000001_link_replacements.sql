@@ -0,0 +1,10 @@
+-- Transactions replacing a stuck one with the same nonce are linked to the
+-- first transaction sent with that nonce
+ALTER TABLE transaction_log ADD COLUMN IF NOT EXISTS original_tx_hash VARCHAR(66);
+
+CREATE INDEX IF NOT EXISTS idx_transaction_log_original_tx_hash
+ON transaction_log(original_tx_hash);
+
+-- Zero-value self-transfers sent to cancel a stuck nonce
+ALTER TABLE pending_transactions
+ADD COLUMN IF NOT EXISTS cancellation BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,14 @@
+-- Transactions are final once buried at their network's confirmation depth
+-- confirmation_status is 'pending' while a mined transaction is not deep enough,
+-- 'confirmed' once it is and 'reorged' if its block was reorged away before that.
+-- It is NULL for transactions that were never mined
+ALTER TABLE transaction_log ADD COLUMN IF NOT EXISTS confirmation_status VARCHAR(20);
+
+-- Transactions mined so far were final at their first receipt
+UPDATE transaction_log
+SET confirmation_status = 'confirmed'
+WHERE status IN ('success', 'failed', 'cancellation') AND confirmation_status IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_transaction_log_confirmation_status
+ON transaction_log(confirmation_status)
+WHERE confirmation_status IN ('pending', 'reorged');
@@ -0,0 +1,5 @@
+-- L1 fee of transactions on rollups, in wei
+-- On OP Stack chains it is paid on top of gas_used * effective gas price and is
+-- included in total_cost_wei. On Arbitrum it is the L1 part of gas_used and is
+-- already in total_cost_wei. It is 0 on other networks
+ALTER TABLE transaction_log ADD COLUMN IF NOT EXISTS l1_fee_wei NUMERIC(78, 0) NOT NULL DEFAULT 0;
@@ -25,6 +25,10 @@ pub struct Cli {
     /// Private key environment variable for signing transactions
     #[arg(short, long, default_value = "OMIKUJI_PRIVATE_KEY", global = true)]
     pub private_key_env: String,
+
+    /// Build and simulate transactions without broadcasting them
+    #[arg(long, global = true)]
+    pub dry_run: bool,
 }
 
 #[derive(Subcommand, Debug)]
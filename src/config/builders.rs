@@ -10,6 +10,7 @@ use crate::gas_price::models::GasPriceFeedConfig;
 use crate::scheduled_tasks::models::ScheduledTask;
 use alloy::primitives::I256;
 use serde_json::Value;
+use std::collections::BTreeMap;
 
 /// Builder for creating OmikujiConfig objects with a fluent API
 #[derive(Debug, Clone)]
@@ -21,6 +22,9 @@ pub struct OmikujiConfigBuilder {
     metrics: MetricsConfig,
     gas_price_feeds: GasPriceFeedConfig,
     scheduled_tasks: Vec<ScheduledTask>,
+    fetch_cache: FetchCacheConfig,
+    fetch_retry: FetchRetryConfig,
+    rate_limits: BTreeMap<String, RateLimitConfig>,
 }
 
 impl OmikujiConfigBuilder {
@@ -34,6 +38,9 @@ impl OmikujiConfigBuilder {
             metrics: MetricsConfig::default(),
             gas_price_feeds: GasPriceFeedConfig::default(),
             scheduled_tasks: Vec::new(),
+            fetch_cache: FetchCacheConfig::default(),
+            fetch_retry: FetchRetryConfig::default(),
+            rate_limits: BTreeMap::new(),
         }
     }
 
@@ -97,6 +104,24 @@ impl OmikujiConfigBuilder {
         self
     }
 
+    /// Set the fetch cache configuration
+    pub fn with_fetch_cache(mut self, config: FetchCacheConfig) -> Self {
+        self.fetch_cache = config;
+        self
+    }
+
+    /// Set the retry settings for datafeed HTTP requests
+    pub fn with_fetch_retry(mut self, config: FetchRetryConfig) -> Self {
+        self.fetch_retry = config;
+        self
+    }
+
+    /// Add a rate limit for requests to a host
+    pub fn with_rate_limit(mut self, host: impl Into<String>, config: RateLimitConfig) -> Self {
+        self.rate_limits.insert(host.into(), config);
+        self
+    }
+
     /// Build the OmikujiConfig
     pub fn build(self) -> OmikujiConfig {
         OmikujiConfig {
@@ -107,6 +132,9 @@ impl OmikujiConfigBuilder {
             metrics: self.metrics,
             gas_price_feeds: self.gas_price_feeds,
             scheduled_tasks: self.scheduled_tasks,
+            fetch_cache: self.fetch_cache,
+            fetch_retry: self.fetch_retry,
+            rate_limits: self.rate_limits,
         }
     }
 }
@@ -121,11 +149,19 @@ impl Default for OmikujiConfigBuilder {
 #[derive(Debug, Clone)]
 pub struct NetworkBuilder {
     name: String,
+    chain_id: Option<u64>,
     rpc_url: String,
+    rpc_endpoints: Vec<RpcEndpoint>,
+    rpc_failover: RpcFailoverConfig,
+    read_quorum: ReadQuorumConfig,
+    check_feeds_on_new_heads: bool,
     transaction_type: String,
+    l2_type: String,
     gas_config: GasConfig,
     gas_token: String,
     gas_token_symbol: String,
+    batching: BatchingConfig,
+    confirmations: u64,
 }
 
 impl NetworkBuilder {
@@ -133,11 +169,19 @@ impl NetworkBuilder {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
+            chain_id: None,
             rpc_url: "http://localhost:8545".to_string(),
+            rpc_endpoints: Vec::new(),
+            rpc_failover: RpcFailoverConfig::default(),
+            read_quorum: ReadQuorumConfig::default(),
+            check_feeds_on_new_heads: false,
             transaction_type: "eip1559".to_string(),
+            l2_type: "none".to_string(),
             gas_config: GasConfig::default(),
             gas_token: "ethereum".to_string(),
             gas_token_symbol: "ETH".to_string(),
+            batching: BatchingConfig::default(),
+            confirmations: 1,
         }
     }
 
@@ -147,12 +191,51 @@ impl NetworkBuilder {
         self
     }
 
+    /// Set the chain ID the RPC endpoints must serve
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Add an RPC endpoint to fail over to
+    pub fn with_rpc_endpoint(mut self, url: impl Into<String>, priority: u32) -> Self {
+        self.rpc_endpoints.push(RpcEndpoint {
+            url: url.into(),
+            priority,
+        });
+        self
+    }
+
+    /// Set the health check and failover configuration of the RPC endpoints
+    pub fn with_rpc_failover(mut self, rpc_failover: RpcFailoverConfig) -> Self {
+        self.rpc_failover = rpc_failover;
+        self
+    }
+
+    /// Set the quorum read configuration
+    pub fn with_read_quorum(mut self, read_quorum: ReadQuorumConfig) -> Self {
+        self.read_quorum = read_quorum;
+        self
+    }
+
+    /// Check the network's datafeeds on every new block
+    pub fn with_check_feeds_on_new_heads(mut self, enabled: bool) -> Self {
+        self.check_feeds_on_new_heads = enabled;
+        self
+    }
+
     /// Set the transaction type (legacy, eip1559)
     pub fn with_transaction_type(mut self, tx_type: impl Into<String>) -> Self {
         self.transaction_type = tx_type.into();
         self
     }
 
+    /// Set the rollup type for L1 fees (op_stack, arbitrum, none)
+    pub fn with_l2_type(mut self, l2_type: impl Into<String>) -> Self {
+        self.l2_type = l2_type.into();
+        self
+    }
+
     /// Set the gas configuration
     pub fn with_gas_config(mut self, config: GasConfig) -> Self {
         self.gas_config = config;
@@ -166,15 +249,35 @@ impl NetworkBuilder {
         self
     }
 
+    /// Set the submission batching configuration
+    pub fn with_batching(mut self, batching: BatchingConfig) -> Self {
+        self.batching = batching;
+        self
+    }
+
+    /// Set the confirmation depth at which transactions are final
+    pub fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
     /// Build the Network configuration
     pub fn build(self) -> Network {
         Network {
             name: self.name,
+            chain_id: self.chain_id,
             rpc_url: self.rpc_url,
+            rpc_endpoints: self.rpc_endpoints,
+            rpc_failover: self.rpc_failover,
+            read_quorum: self.read_quorum,
+            check_feeds_on_new_heads: self.check_feeds_on_new_heads,
             transaction_type: self.transaction_type,
+            l2_type: self.l2_type,
             gas_config: self.gas_config,
             gas_token: self.gas_token,
             gas_token_symbol: self.gas_token_symbol,
+            batching: self.batching,
+            confirmations: self.confirmations,
         }
     }
 
@@ -219,12 +322,26 @@ pub struct DatafeedBuilder {
     check_frequency: u64,
     contract_address: String,
     contract_type: String,
+    setter_signature: Option<String>,
+    custom_abi: Option<CustomAbiConfig>,
+    custom_errors: Vec<String>,
     read_contract_config: bool,
     minimum_update_frequency: u64,
     deviation_threshold_pct: f64,
     feed_url: String,
     feed_json_path: String,
     feed_json_path_timestamp: Option<String>,
+    request: RequestConfig,
+    sources: Vec<FeedSource>,
+    aggregation: AggregationConfig,
+    min_sources: usize,
+    stream: Option<StreamConfig>,
+    onchain: Option<OnChainSource>,
+    transform: Option<String>,
+    outlier_guard: Option<OutlierGuardConfig>,
+    dry_run: bool,
+    gas_config: Option<GasConfig>,
+    deployments: Vec<DatafeedDeployment>,
     decimals: Option<u8>,
     min_value: Option<I256>,
     max_value: Option<I256>,
@@ -240,12 +357,26 @@ impl DatafeedBuilder {
             check_frequency: 60, // 1 minute
             contract_address: "0x0000000000000000000000000000000000000000".to_string(),
             contract_type: "fluxmon".to_string(),
+            setter_signature: None,
+            custom_abi: None,
+            custom_errors: Vec::new(),
             read_contract_config: true,
             minimum_update_frequency: 300, // 5 minutes
             deviation_threshold_pct: 0.5,  // 0.5%
             feed_url: "https://api.example.com/price".to_string(),
             feed_json_path: "$.price".to_string(),
             feed_json_path_timestamp: None,
+            request: RequestConfig::default(),
+            sources: Vec::new(),
+            aggregation: AggregationConfig::default(),
+            min_sources: 1,
+            stream: None,
+            onchain: None,
+            transform: None,
+            outlier_guard: None,
+            dry_run: false,
+            gas_config: None,
+            deployments: Vec::new(),
             decimals: None,
             min_value: None,
             max_value: None,
@@ -271,6 +402,24 @@ impl DatafeedBuilder {
         self
     }
 
+    /// Set the setter used to submit values to owner_settable contracts
+    pub fn with_setter_signature(mut self, signature: impl Into<String>) -> Self {
+        self.setter_signature = Some(signature.into());
+        self
+    }
+
+    /// Set the functions used to read from and submit to custom contracts
+    pub fn with_custom_abi(mut self, custom_abi: CustomAbiConfig) -> Self {
+        self.custom_abi = Some(custom_abi);
+        self
+    }
+
+    /// Set the custom error signatures used to decode reverts
+    pub fn with_custom_errors(mut self, signatures: Vec<String>) -> Self {
+        self.custom_errors = signatures;
+        self
+    }
+
     /// Set whether to read configuration from the contract
     pub fn with_read_contract_config(mut self, read_config: bool) -> Self {
         self.read_contract_config = read_config;
@@ -307,6 +456,79 @@ impl DatafeedBuilder {
         self
     }
 
+    /// Set the HTTP request settings for the feed URL
+    pub fn with_request(mut self, request: RequestConfig) -> Self {
+        self.request = request;
+        self
+    }
+
+    /// Add a data source for multi-source aggregation
+    pub fn with_source(mut self, source: FeedSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// Set the aggregation method and trim percentage
+    pub fn with_aggregation(mut self, method: impl Into<String>, trim_pct: f64) -> Self {
+        self.aggregation = AggregationConfig {
+            method: method.into(),
+            trim_pct,
+        };
+        self
+    }
+
+    /// Set the minimum number of sources required per round
+    pub fn with_min_sources(mut self, min_sources: usize) -> Self {
+        self.min_sources = min_sources;
+        self
+    }
+
+    /// Use a WebSocket stream as the data source instead of the feed URL
+    pub fn with_stream(mut self, stream: StreamConfig) -> Self {
+        self.feed_url.clear();
+        self.feed_json_path.clear();
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Read the value from a contract instead of the feed URL
+    pub fn with_onchain_source(mut self, onchain: OnChainSource) -> Self {
+        self.feed_url.clear();
+        self.feed_json_path.clear();
+        self.onchain = Some(onchain);
+        self
+    }
+
+    /// Set the transform expression applied to the extracted value
+    pub fn with_transform(mut self, expression: impl Into<String>) -> Self {
+        self.transform = Some(expression.into());
+        self
+    }
+
+    /// Set the outlier rejection and circuit breaker settings
+    pub fn with_outlier_guard(mut self, guard: OutlierGuardConfig) -> Self {
+        self.outlier_guard = Some(guard);
+        self
+    }
+
+    /// Simulate submissions instead of broadcasting them
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Override the network's gas settings
+    pub fn with_gas_config(mut self, gas_config: GasConfig) -> Self {
+        self.gas_config = Some(gas_config);
+        self
+    }
+
+    /// Add a network and contract the value is submitted to
+    pub fn with_deployment(mut self, deployment: DatafeedDeployment) -> Self {
+        self.deployments.push(deployment);
+        self
+    }
+
     /// Set the number of decimals
     pub fn with_decimals(mut self, decimals: u8) -> Self {
         self.decimals = Some(decimals);
@@ -339,12 +561,26 @@ impl DatafeedBuilder {
             check_frequency: self.check_frequency,
             contract_address: self.contract_address,
             contract_type: self.contract_type,
+            setter_signature: self.setter_signature,
+            custom_abi: self.custom_abi,
+            custom_errors: self.custom_errors,
             read_contract_config: self.read_contract_config,
             minimum_update_frequency: self.minimum_update_frequency,
             deviation_threshold_pct: self.deviation_threshold_pct,
             feed_url: self.feed_url,
             feed_json_path: self.feed_json_path,
             feed_json_path_timestamp: self.feed_json_path_timestamp,
+            request: self.request,
+            sources: self.sources,
+            aggregation: self.aggregation,
+            min_sources: self.min_sources,
+            stream: self.stream,
+            onchain: self.onchain,
+            transform: self.transform,
+            outlier_guard: self.outlier_guard,
+            dry_run: self.dry_run,
+            gas_config: self.gas_config,
+            deployments: self.deployments,
             decimals: self.decimals,
             min_value: self.min_value,
             max_value: self.max_value,
@@ -475,6 +711,7 @@ impl ScheduledTaskBuilder {
             contract_address: contract_address.into(),
             function: function.into(),
             parameters: Vec::new(),
+            custom_errors: Vec::new(),
         });
         self
     }
@@ -528,6 +765,7 @@ impl ScheduledTaskBuilder {
             check_condition: self.check_condition,
             target_function,
             gas_config: self.gas_config,
+            dry_run: false,
         };
 
         // Validate the task before returning
@@ -1,5 +1,6 @@
 use alloy::primitives::I256;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use validator::{Validate, ValidationError};
 
 use super::metrics_config::MetricsConfig;
@@ -38,6 +39,20 @@ pub struct OmikujiConfig {
     /// Scheduled tasks configuration
     #[serde(default)]
     pub scheduled_tasks: Vec<ScheduledTask>,
+
+    /// Shared fetch cache configuration
+    #[serde(default)]
+    pub fetch_cache: FetchCacheConfig,
+
+    /// Retry settings for datafeed HTTP requests
+    #[serde(default)]
+    #[validate]
+    pub fetch_retry: FetchRetryConfig,
+
+    /// Token-bucket rate limits for datafeed HTTP requests, keyed by host
+    #[serde(default)]
+    #[validate(custom = "validate_rate_limits")]
+    pub rate_limits: BTreeMap<String, RateLimitConfig>,
 }
 
 /// Configuration for database cleanup task
@@ -65,6 +80,120 @@ fn default_cleanup_schedule() -> String {
     "0 0 * * * *".to_string() // Every hour at minute 0
 }
 
+/// Configuration for the fetch cache shared by all datafeeds
+///
+/// Concurrent requests for the same URL and request settings are coalesced
+/// into one HTTP call, and successful responses are reused for `ttl_ms`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchCacheConfig {
+    /// Whether responses are shared between datafeeds (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// How long a successful response is reused (in milliseconds, default: 1000)
+    #[serde(default = "default_fetch_cache_ttl_ms")]
+    pub ttl_ms: u64,
+}
+
+impl Default for FetchCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_ms: default_fetch_cache_ttl_ms(),
+        }
+    }
+}
+
+fn default_fetch_cache_ttl_ms() -> u64 {
+    1000
+}
+
+/// Retry settings for datafeed HTTP requests
+///
+/// Network errors, 5xx and 429 responses are retried with exponential
+/// backoff and jitter. A `Retry-After` header takes precedence over the backoff.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct FetchRetryConfig {
+    /// Maximum number of retries after the first attempt, 0 disables retries (default: 3)
+    #[serde(default = "default_fetch_max_retries")]
+    pub max_retries: u32,
+
+    /// Delay before the first retry (in milliseconds, default: 1000)
+    #[serde(default = "default_fetch_initial_backoff_ms")]
+    #[validate(range(min = 1))]
+    pub initial_backoff_ms: u64,
+
+    /// Maximum delay between retries (in milliseconds, default: 30000).
+    /// Requests whose `Retry-After` exceeds this are not retried.
+    #[serde(default = "default_fetch_max_backoff_ms")]
+    #[validate(range(min = 1))]
+    pub max_backoff_ms: u64,
+
+    /// Timeout for a single attempt (in seconds, default: 30)
+    #[serde(default = "default_fetch_timeout_secs")]
+    #[validate(range(min = 1))]
+    pub timeout_secs: u64,
+}
+
+impl Default for FetchRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_fetch_max_retries(),
+            initial_backoff_ms: default_fetch_initial_backoff_ms(),
+            max_backoff_ms: default_fetch_max_backoff_ms(),
+            timeout_secs: default_fetch_timeout_secs(),
+        }
+    }
+}
+
+fn default_fetch_max_retries() -> u32 {
+    crate::constants::network::MAX_NETWORK_RETRIES
+}
+
+fn default_fetch_initial_backoff_ms() -> u64 {
+    crate::constants::network::NETWORK_RETRY_DELAY_MS
+}
+
+fn default_fetch_max_backoff_ms() -> u64 {
+    30000
+}
+
+fn default_fetch_timeout_secs() -> u64 {
+    crate::constants::network::HTTP_REQUEST_TIMEOUT_SECS
+}
+
+/// Token-bucket rate limit for requests to a single host
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Sustained request rate
+    pub requests_per_second: f64,
+
+    /// Number of requests that may be sent back to back (default: 1)
+    #[serde(default = "default_rate_limit_burst")]
+    pub burst: u32,
+}
+
+fn default_rate_limit_burst() -> u32 {
+    1
+}
+
+/// Validates that rate limits have a positive rate and burst
+fn validate_rate_limits(
+    rate_limits: &BTreeMap<String, RateLimitConfig>,
+) -> Result<(), ValidationError> {
+    for limit in rate_limits.values() {
+        if !limit.requests_per_second.is_finite()
+            || limit.requests_per_second <= 0.0
+            || limit.burst == 0
+        {
+            return Err(ValidationError::new(
+                "rate limits must have a positive requests_per_second and burst",
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Configuration for key storage
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct KeyStorageConfig {
@@ -221,20 +350,51 @@ fn validate_key_storage_type(storage_type: &str) -> Result<(), ValidationError>
 
 /// Configuration for a blockchain network
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_network_read_quorum"))]
 pub struct Network {
     /// Network name (e.g., "ethereum", "base")
     #[validate(length(min = 1))]
     pub name: String,
 
-    /// RPC URL for the network
+    /// Chain ID the RPC endpoints must serve, checked on startup
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+
+    /// RPC URL for the network, the preferred endpoint
     #[validate(url)]
     pub rpc_url: String,
 
+    /// Additional RPC endpoints to fail over to
+    #[serde(default)]
+    #[validate]
+    pub rpc_endpoints: Vec<RpcEndpoint>,
+
+    /// Health checks and failover across the RPC endpoints
+    #[serde(default)]
+    #[validate]
+    pub rpc_failover: RpcFailoverConfig,
+
+    /// Reads of the latest contract round checked against several endpoints
+    #[serde(default)]
+    #[validate]
+    pub read_quorum: ReadQuorumConfig,
+
+    /// Check the network's datafeeds on every new block of a WebSocket
+    /// endpoint, with `check_frequency` as the fallback interval
+    #[serde(default)]
+    pub check_feeds_on_new_heads: bool,
+
     /// Transaction type to use ("legacy" or "eip1559")
     #[serde(default = "default_transaction_type")]
     #[validate(custom = "validate_transaction_type")]
     pub transaction_type: String,
 
+    /// Rollup the network is, for the L1 fee of its transactions
+    /// ("op_stack", "arbitrum" or "none")
+    #[serde(default = "default_l2_type")]
+    #[validate(custom = "validate_l2_type")]
+    pub l2_type: String,
+
     /// Gas configuration for this network
     #[serde(default)]
     #[validate]
@@ -247,21 +407,185 @@ pub struct Network {
     /// Gas token symbol (e.g., "ETH", "BNB")
     #[serde(default = "default_gas_token_symbol")]
     pub gas_token_symbol: String,
+
+    /// Batching of datafeed submissions into Multicall3 transactions
+    #[serde(default)]
+    #[validate]
+    pub batching: BatchingConfig,
+
+    /// Blocks a transaction must be buried under before it is final
+    /// (1 treats the including block as final)
+    #[serde(default = "default_confirmations")]
+    #[validate(range(min = 1, max = 256))]
+    pub confirmations: u64,
 }
 
 impl Default for Network {
     fn default() -> Self {
         Self {
             name: "localhost".to_string(),
+            chain_id: None,
             rpc_url: "http://localhost:8545".to_string(),
+            rpc_endpoints: Vec::new(),
+            rpc_failover: RpcFailoverConfig::default(),
+            read_quorum: ReadQuorumConfig::default(),
+            check_feeds_on_new_heads: false,
             transaction_type: default_transaction_type(),
+            l2_type: default_l2_type(),
             gas_config: GasConfig::default(),
             gas_token: default_gas_token(),
             gas_token_symbol: default_gas_token_symbol(),
+            batching: BatchingConfig::default(),
+            confirmations: default_confirmations(),
         }
     }
 }
 
+impl Network {
+    /// Every RPC endpoint of the network, `rpc_url` first with priority 0
+    pub fn endpoints(&self) -> Vec<RpcEndpoint> {
+        let primary = RpcEndpoint {
+            url: self.rpc_url.clone(),
+            priority: 0,
+        };
+
+        std::iter::once(primary)
+            .chain(self.rpc_endpoints.iter().cloned())
+            .collect()
+    }
+}
+
+/// An additional RPC endpoint of a network
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct RpcEndpoint {
+    /// HTTP(S) URL of the endpoint
+    #[validate(url)]
+    pub url: String,
+
+    /// Preference among healthy endpoints, lower first (`rpc_url` has 0)
+    #[serde(default = "default_endpoint_priority")]
+    pub priority: u32,
+}
+
+fn default_endpoint_priority() -> u32 {
+    1
+}
+
+/// Health checks and failover across the RPC endpoints of a network
+///
+/// Endpoints trailing the highest block by more than `max_block_lag` or
+/// failing more than `max_error_rate` of their requests are unhealthy.
+/// Requests go to the healthy endpoint with the lowest priority and latency.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct RpcFailoverConfig {
+    /// Seconds between health probes of the endpoints
+    #[serde(default = "default_health_check_interval_secs")]
+    #[validate(range(min = 1, max = 3600))]
+    pub health_check_interval_secs: u64,
+
+    /// Blocks an endpoint may trail the highest head seen before it is unhealthy
+    #[serde(default = "default_max_block_lag")]
+    pub max_block_lag: u64,
+
+    /// Share of recent requests that may fail before an endpoint is unhealthy
+    #[serde(default = "default_max_error_rate")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub max_error_rate: f64,
+
+    /// Number of endpoints every signed transaction is broadcast to
+    #[serde(default = "default_broadcast_endpoints")]
+    #[validate(range(min = 1, max = 10))]
+    pub broadcast_endpoints: usize,
+}
+
+fn default_health_check_interval_secs() -> u64 {
+    15
+}
+
+fn default_max_block_lag() -> u64 {
+    5
+}
+
+fn default_max_error_rate() -> f64 {
+    0.5
+}
+
+fn default_broadcast_endpoints() -> usize {
+    2
+}
+
+impl Default for RpcFailoverConfig {
+    fn default() -> Self {
+        Self {
+            health_check_interval_secs: default_health_check_interval_secs(),
+            max_block_lag: default_max_block_lag(),
+            max_error_rate: default_max_error_rate(),
+            broadcast_endpoints: default_broadcast_endpoints(),
+        }
+    }
+}
+
+/// Quorum reads of the latest contract round across RPC endpoints
+///
+/// The latest answer, timestamp and round of a datafeed's contract are read
+/// from `endpoints` endpoints at the same recent block, and only accepted if
+/// at least `min_agreeing` of them return the same value.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ReadQuorumConfig {
+    /// Enable quorum reads on this network
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Number of endpoints each read is sent to
+    #[serde(default = "default_quorum_endpoints")]
+    #[validate(range(min = 2, max = 10))]
+    pub endpoints: usize,
+
+    /// Number of identical answers needed to accept a read
+    #[serde(default = "default_quorum_min_agreeing")]
+    #[validate(range(min = 1, max = 10))]
+    pub min_agreeing: usize,
+}
+
+fn default_quorum_endpoints() -> usize {
+    3
+}
+
+fn default_quorum_min_agreeing() -> usize {
+    2
+}
+
+impl Default for ReadQuorumConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoints: default_quorum_endpoints(),
+            min_agreeing: default_quorum_min_agreeing(),
+        }
+    }
+}
+
+/// Validates that a network has enough RPC endpoints for its read quorum
+fn validate_network_read_quorum(network: &Network) -> Result<(), ValidationError> {
+    let quorum = &network.read_quorum;
+    if !quorum.enabled {
+        return Ok(());
+    }
+
+    if quorum.min_agreeing > quorum.endpoints {
+        return Err(ValidationError::new(
+            "read_quorum min_agreeing exceeds read_quorum endpoints",
+        ));
+    }
+    if quorum.min_agreeing > network.endpoints().len() {
+        return Err(ValidationError::new(
+            "read_quorum min_agreeing exceeds the number of RPC endpoints",
+        ));
+    }
+
+    Ok(())
+}
+
 /// Gas configuration for a network
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct GasConfig {
@@ -309,12 +633,26 @@ pub struct FeeBumpingConfig {
     #[serde(default = "default_fee_increase_percent")]
     #[validate(range(min = 5.0, max = 100.0))]
     pub fee_increase_percent: f64,
+
+    /// Minimum fee increase for a replacement with the same nonce
+    /// Replacements are raised by the larger of this and `fee_increase_percent`
+    #[serde(default = "default_min_replacement_bump_percent")]
+    #[validate(range(min = 10.0, max = 100.0))]
+    pub min_replacement_bump_percent: f64,
+
+    /// Cancel the transaction with a zero-value self-transfer after the last retry
+    #[serde(default)]
+    pub cancel_after_max_retries: bool,
 }
 
 fn default_transaction_type() -> String {
     "eip1559".to_string()
 }
 
+fn default_l2_type() -> String {
+    "none".to_string()
+}
+
 fn default_gas_token() -> String {
     "ethereum".to_string()
 }
@@ -323,6 +661,10 @@ fn default_gas_token_symbol() -> String {
     "ETH".to_string()
 }
 
+fn default_confirmations() -> u64 {
+    1
+}
+
 fn default_gas_multiplier() -> f64 {
     crate::constants::gas::GAS_ESTIMATION_MULTIPLIER
 }
@@ -343,6 +685,10 @@ fn default_fee_increase_percent() -> f64 {
     (crate::constants::gas::FEE_BUMP_MULTIPLIER - 1.0) * 100.0
 }
 
+fn default_min_replacement_bump_percent() -> f64 {
+    crate::constants::gas::MIN_REPLACEMENT_BUMP_PERCENT
+}
+
 impl Default for GasConfig {
     fn default() -> Self {
         Self {
@@ -356,6 +702,56 @@ impl Default for GasConfig {
     }
 }
 
+/// Batching of datafeed submissions on a network
+///
+/// Submissions collected within the window are sent as one Multicall3
+/// `aggregate3` transaction. The oracle contracts see the multicall contract
+/// as the sender, so it must be allowed to submit to them.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct BatchingConfig {
+    /// Enable batching (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// How long to collect submissions before sending a batch, in milliseconds
+    #[serde(default = "default_batch_window_ms")]
+    #[validate(range(min = 10, max = 60000))]
+    pub window_ms: u64,
+
+    /// Maximum number of submissions in one batch
+    #[serde(default = "default_max_batch_size")]
+    #[validate(range(min = 1, max = 500))]
+    pub max_batch_size: usize,
+
+    /// Address of the Multicall3-compatible contract (default: the canonical Multicall3 deployment)
+    #[serde(default = "default_multicall_address")]
+    #[validate(custom = "validate_eth_address")]
+    pub multicall_address: String,
+}
+
+fn default_batch_window_ms() -> u64 {
+    2000
+}
+
+fn default_max_batch_size() -> usize {
+    20
+}
+
+fn default_multicall_address() -> String {
+    crate::contracts::multicall::MULTICALL3_ADDRESS.to_string()
+}
+
+impl Default for BatchingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_ms: default_batch_window_ms(),
+            max_batch_size: default_max_batch_size(),
+            multicall_address: default_multicall_address(),
+        }
+    }
+}
+
 impl Default for FeeBumpingConfig {
     fn default() -> Self {
         Self {
@@ -363,33 +759,49 @@ impl Default for FeeBumpingConfig {
             max_retries: default_max_retries(),
             initial_wait_seconds: default_initial_wait(),
             fee_increase_percent: default_fee_increase_percent(),
+            min_replacement_bump_percent: default_min_replacement_bump_percent(),
+            cancel_after_max_retries: false,
         }
     }
 }
 
 /// Configuration for a datafeed
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_datafeed_sources"))]
+#[validate(schema(function = "validate_datafeed_deployments"))]
 pub struct Datafeed {
     /// Datafeed name
     #[validate(length(min = 1))]
     pub name: String,
 
-    /// Network this datafeed operates on (must match a network name)
-    #[validate(length(min = 1))]
+    /// Network this datafeed operates on (must match a network name, may be omitted when
+    /// `deployments` is set)
+    #[serde(default)]
     pub networks: String,
 
     /// Frequency to check the datafeed (in seconds)
     #[validate(range(min = 1))]
     pub check_frequency: u64,
 
-    /// Smart contract address for the datafeed
-    #[validate(custom = "validate_eth_address")]
+    /// Smart contract address for the datafeed (may be omitted when `deployments` is set)
+    #[serde(default)]
     pub contract_address: String,
 
-    /// Contract type (e.g., "fluxmon")
-    #[validate(length(min = 1))]
+    /// Contract type ("fluxmon", "aggregator_v3", "owner_settable" or "custom")
+    #[validate(custom = "validate_contract_type")]
     pub contract_type: String,
 
+    /// Setter used to submit values to owner_settable contracts, e.g. "setAnswer(int256)"
+    pub setter_signature: Option<String>,
+
+    /// Functions used to read from and submit to custom contracts
+    #[validate]
+    pub custom_abi: Option<CustomAbiConfig>,
+
+    /// Custom error signatures used to decode reverts, e.g. "RoundNotOpen(uint32)" (optional)
+    #[serde(default)]
+    pub custom_errors: Vec<String>,
+
     /// Whether to read configuration from the contract
     pub read_contract_config: bool,
 
@@ -401,17 +813,67 @@ pub struct Datafeed {
     #[validate(range(min = 0.0, max = 100.0))]
     pub deviation_threshold_pct: f64,
 
-    /// URL to fetch the price feed data
-    #[validate(url)]
+    /// URL to fetch the price feed data (may be omitted when `sources` is set)
+    #[serde(default)]
     pub feed_url: String,
 
     /// JSON path to extract the price from the feed response
-    #[validate(length(min = 1))]
+    #[serde(default)]
     pub feed_json_path: String,
 
     /// JSON path to extract the timestamp from the feed response (optional)
     pub feed_json_path_timestamp: Option<String>,
 
+    /// HTTP request settings for the feed URL (method, headers, body, query parameters)
+    #[serde(default)]
+    #[validate]
+    pub request: RequestConfig,
+
+    /// Multiple data sources whose values are aggregated (optional, replaces feed_url)
+    #[serde(default)]
+    #[validate]
+    pub sources: Vec<FeedSource>,
+
+    /// Aggregation settings used when multiple sources are configured
+    #[serde(default)]
+    #[validate]
+    pub aggregation: AggregationConfig,
+
+    /// Minimum number of sources that must return a value for a round to proceed (default: 1)
+    #[serde(default = "default_min_sources")]
+    #[validate(range(min = 1))]
+    pub min_sources: usize,
+
+    /// WebSocket streaming source (optional, replaces feed_url and sources)
+    #[validate]
+    pub stream: Option<StreamConfig>,
+
+    /// On-chain source read with an eth_call (optional, replaces feed_url)
+    #[validate]
+    pub onchain: Option<OnChainSource>,
+
+    /// Arithmetic expression applied to the extracted value before submission (optional)
+    /// e.g. "1 / value", "value / 100" or "value * eth_usd" to reference another feed
+    pub transform: Option<String>,
+
+    /// Outlier rejection and circuit breaker settings (optional)
+    #[validate]
+    pub outlier_guard: Option<OutlierGuardConfig>,
+
+    /// Build and simulate submissions without broadcasting them (default: false)
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// Gas settings replacing the network's gas_config for this datafeed (optional)
+    #[validate]
+    pub gas_config: Option<GasConfig>,
+
+    /// Networks and contracts the value is submitted to (optional, replaces networks
+    /// and contract_address); the value is fetched once for all deployments
+    #[serde(default)]
+    #[validate]
+    pub deployments: Vec<DatafeedDeployment>,
+
     /// Number of decimals to use (optional, used when read_contract_config is false)
     pub decimals: Option<u8>,
 
@@ -431,6 +893,563 @@ fn default_data_retention_days() -> u32 {
     7
 }
 
+fn default_min_sources() -> usize {
+    1
+}
+
+impl Datafeed {
+    /// Returns the data sources to poll for this datafeed
+    ///
+    /// If `sources` is empty the legacy `feed_url`/`feed_json_path` pair is
+    /// returned as a single source. Sources without their own `request`
+    /// inherit the datafeed's request settings. Streaming datafeeds have no
+    /// polled sources.
+    pub fn effective_sources(&self) -> Vec<FeedSource> {
+        if self.stream.is_some() {
+            return vec![];
+        }
+
+        if !self.sources.is_empty() {
+            return self
+                .sources
+                .iter()
+                .cloned()
+                .map(|mut source| {
+                    source.request.get_or_insert_with(|| self.request.clone());
+                    source
+                })
+                .collect();
+        }
+
+        vec![FeedSource {
+            url: self.feed_url.clone(),
+            json_path: self.feed_json_path.clone(),
+            json_path_timestamp: self.feed_json_path_timestamp.clone(),
+            weight: default_source_weight(),
+            request: Some(self.request.clone()),
+            onchain: self.onchain.clone(),
+        }]
+    }
+
+    /// Returns one single-network datafeed per deployment
+    ///
+    /// Each deployment inherits the datafeed settings it doesn't override. A
+    /// datafeed without `deployments` is returned unchanged.
+    pub fn expand_deployments(&self) -> Vec<Datafeed> {
+        if self.deployments.is_empty() {
+            return vec![self.clone()];
+        }
+
+        self.deployments
+            .iter()
+            .map(|deployment| Datafeed {
+                networks: deployment.network.clone(),
+                contract_address: deployment.contract_address.clone(),
+                contract_type: deployment
+                    .contract_type
+                    .clone()
+                    .unwrap_or_else(|| self.contract_type.clone()),
+                read_contract_config: deployment
+                    .read_contract_config
+                    .unwrap_or(self.read_contract_config),
+                minimum_update_frequency: deployment
+                    .minimum_update_frequency
+                    .unwrap_or(self.minimum_update_frequency),
+                deviation_threshold_pct: deployment
+                    .deviation_threshold_pct
+                    .unwrap_or(self.deviation_threshold_pct),
+                decimals: deployment.decimals.or(self.decimals),
+                min_value: deployment.min_value.or(self.min_value),
+                max_value: deployment.max_value.or(self.max_value),
+                gas_config: deployment
+                    .gas_config
+                    .clone()
+                    .or_else(|| self.gas_config.clone()),
+                dry_run: deployment.dry_run.unwrap_or(self.dry_run),
+                deployments: Vec::new(),
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    /// Returns the networks the datafeed submits to, comma separated
+    ///
+    /// Used to label work done once for all deployments, such as fetching the value.
+    pub fn network_label(&self) -> String {
+        if self.deployments.is_empty() {
+            return self.networks.clone();
+        }
+
+        self.deployments
+            .iter()
+            .map(|deployment| deployment.network.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// A network and contract a datafeed submits to
+///
+/// Unset fields inherit the datafeed's settings.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct DatafeedDeployment {
+    /// Network name (must match a network name)
+    #[validate(length(min = 1))]
+    pub network: String,
+
+    /// Smart contract address on this network
+    #[validate(custom = "validate_eth_address")]
+    pub contract_address: String,
+
+    /// Contract type override
+    #[validate(custom = "validate_contract_type")]
+    pub contract_type: Option<String>,
+
+    /// Whether to read configuration from this contract
+    pub read_contract_config: Option<bool>,
+
+    /// Minimum time between updates override (in seconds)
+    #[validate(range(min = 1))]
+    pub minimum_update_frequency: Option<u64>,
+
+    /// Deviation threshold override (in percent)
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub deviation_threshold_pct: Option<f64>,
+
+    /// Number of decimals override
+    pub decimals: Option<u8>,
+
+    /// Minimum valid value override
+    pub min_value: Option<I256>,
+
+    /// Maximum valid value override
+    pub max_value: Option<I256>,
+
+    /// Gas settings replacing the network's gas_config for this deployment
+    #[validate]
+    pub gas_config: Option<GasConfig>,
+
+    /// Dry-run override
+    pub dry_run: Option<bool>,
+}
+
+/// A single data source of a multi-source datafeed
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[validate(schema(function = "validate_feed_source"))]
+pub struct FeedSource {
+    /// URL to fetch the data from (may be omitted when `onchain` is set)
+    #[serde(default)]
+    pub url: String,
+
+    /// JSON path to extract the value from the response
+    #[serde(default)]
+    pub json_path: String,
+
+    /// JSON path to extract the timestamp from the response (optional)
+    pub json_path_timestamp: Option<String>,
+
+    /// Relative weight used by the "weighted_mean" aggregation (default: 1.0)
+    #[serde(default = "default_source_weight")]
+    #[validate(range(min = 0.0))]
+    pub weight: f64,
+
+    /// HTTP request settings for this source (optional, defaults to the datafeed's `request`)
+    #[validate]
+    pub request: Option<RequestConfig>,
+
+    /// On-chain source read with an eth_call (optional, replaces url)
+    #[validate]
+    pub onchain: Option<OnChainSource>,
+}
+
+impl FeedSource {
+    /// Returns a short description of the source for logs
+    pub fn describe(&self) -> String {
+        match &self.onchain {
+            Some(onchain) => format!(
+                "{}@{} on {}",
+                onchain.function, onchain.address, onchain.network
+            ),
+            None => self.url.clone(),
+        }
+    }
+}
+
+fn default_source_weight() -> f64 {
+    1.0
+}
+
+/// Validates that a source has either a URL with a JSON path or an on-chain call
+fn validate_feed_source(source: &FeedSource) -> Result<(), ValidationError> {
+    if source.onchain.is_some() {
+        if !source.url.is_empty() {
+            return Err(ValidationError::new(
+                "url and onchain cannot be used together",
+            ));
+        }
+        return Ok(());
+    }
+
+    if url::Url::parse(&source.url).is_err() {
+        return Err(ValidationError::new("invalid_source_url"));
+    }
+    if source.json_path.is_empty() {
+        return Err(ValidationError::new("missing_source_json_path"));
+    }
+
+    Ok(())
+}
+
+/// On-chain data source that reads a value from a contract with an `eth_call`
+///
+/// Examples: a Chainlink aggregator's `latestRoundData()`, a Uniswap V3
+/// pool's `slot0()` or an ERC-4626 vault's `convertToAssets(uint256)`.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct OnChainSource {
+    /// Network to read from (must match a network name, may differ from the datafeed's network)
+    #[validate(length(min = 1))]
+    pub network: String,
+
+    /// Address of the contract to call
+    #[validate(custom = "validate_eth_address")]
+    pub address: String,
+
+    /// Function signature, e.g. "latestRoundData()" or "convertToAssets(uint256)"
+    #[validate(length(min = 1))]
+    pub function: String,
+
+    /// Function arguments, in the order of the signature's parameters
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Return types of the function, e.g. ["uint80", "int256", "uint256", "uint256", "uint80"]
+    #[validate(length(min = 1))]
+    pub returns: Vec<String>,
+
+    /// Index of the return value holding the value (default: 0)
+    #[serde(default)]
+    pub value_index: usize,
+
+    /// Index of the return value holding the Unix timestamp (optional, defaults to the current time)
+    pub timestamp_index: Option<usize>,
+
+    /// Number of decimals the raw value is scaled down by (default: 0)
+    #[serde(default)]
+    pub decimals: u8,
+}
+
+/// Functions of a `custom` oracle contract
+///
+/// Lets a datafeed submit to contracts with their own setter, e.g.
+/// `updatePrice(bytes32,int256,uint64)`, without code changes.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CustomAbiConfig {
+    /// Function submitting a value, e.g. "updatePrice(bytes32,int256,uint64)"
+    #[validate(length(min = 1))]
+    pub submit_function: String,
+
+    /// Submit arguments, in the order of the signature's parameters; `{value}`
+    /// (the scaled value), `{timestamp}` (current Unix time) and `{round}` (latest
+    /// round + 1) are replaced, anything else is passed as a constant
+    #[serde(default)]
+    pub submit_args: Vec<String>,
+
+    /// Function reading the latest value, already scaled by the datafeed decimals
+    #[validate]
+    pub latest_value: CustomReadFunction,
+
+    /// Function reading the Unix time of the latest value (optional if
+    /// `latest_value` returns it at `timestamp_index`)
+    #[validate]
+    pub latest_timestamp: Option<CustomReadFunction>,
+
+    /// Function reading the latest round, required when `submit_args` uses `{round}`
+    #[validate]
+    pub latest_round: Option<CustomReadFunction>,
+
+    /// Function reading the decimals, required when `read_contract_config` is true
+    #[validate]
+    pub decimals: Option<CustomReadFunction>,
+}
+
+/// A view function of a custom contract and which return value to use
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CustomReadFunction {
+    /// Function signature, e.g. "getPrice(bytes32)"
+    #[validate(length(min = 1))]
+    pub function: String,
+
+    /// Function arguments, in the order of the signature's parameters
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Return types of the function, e.g. ["int256", "uint64"]
+    #[validate(length(min = 1))]
+    pub returns: Vec<String>,
+
+    /// Index of the return value to use (default: 0)
+    #[serde(default)]
+    pub value_index: usize,
+
+    /// Index of the return value holding the Unix timestamp (optional, `latest_value` only)
+    pub timestamp_index: Option<usize>,
+}
+
+/// HTTP request settings for a data source
+///
+/// Header values, query parameter values and the body may contain `${ENV_VAR}`
+/// or `${secret:NAME}` placeholders, which are resolved at request time so
+/// credentials don't need to be stored in the YAML file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Validate)]
+pub struct RequestConfig {
+    /// HTTP method: "GET" or "POST" (default: "GET")
+    #[serde(default = "default_http_method")]
+    #[validate(custom = "validate_http_method")]
+    pub method: String,
+
+    /// Additional request headers (e.g. API keys or bearer tokens)
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// Query parameters appended to the URL
+    #[serde(default)]
+    pub query_params: BTreeMap<String, String>,
+
+    /// Request body template (sent as JSON unless a Content-Type header is set)
+    pub body: Option<String>,
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        Self {
+            method: default_http_method(),
+            headers: BTreeMap::new(),
+            query_params: BTreeMap::new(),
+            body: None,
+        }
+    }
+}
+
+fn default_http_method() -> String {
+    "GET".to_string()
+}
+
+/// Validates that the HTTP method is supported for data sources
+fn validate_http_method(method: &str) -> Result<(), ValidationError> {
+    match method.to_uppercase().as_str() {
+        "GET" | "POST" => Ok(()),
+        _ => Err(ValidationError::new("http method must be 'GET' or 'POST'")),
+    }
+}
+
+/// Configuration for combining values from multiple sources
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct AggregationConfig {
+    /// Aggregation method: "median", "weighted_mean" or "trimmed_mean" (default: "median")
+    #[serde(default = "default_aggregation_method")]
+    #[validate(custom = "validate_aggregation_method")]
+    pub method: String,
+
+    /// Percentage of values discarded at each end for "trimmed_mean" (default: 20)
+    #[serde(default = "default_trim_pct")]
+    #[validate(range(min = 0.0, max = 49.0))]
+    pub trim_pct: f64,
+}
+
+impl Default for AggregationConfig {
+    fn default() -> Self {
+        Self {
+            method: default_aggregation_method(),
+            trim_pct: default_trim_pct(),
+        }
+    }
+}
+
+fn default_aggregation_method() -> String {
+    "median".to_string()
+}
+
+fn default_trim_pct() -> f64 {
+    20.0
+}
+
+/// Validates that the aggregation method is supported
+fn validate_aggregation_method(method: &str) -> Result<(), ValidationError> {
+    match method {
+        "median" | "weighted_mean" | "trimmed_mean" => Ok(()),
+        _ => Err(ValidationError::new(
+            "aggregation method must be 'median', 'weighted_mean', or 'trimmed_mean'",
+        )),
+    }
+}
+
+/// WebSocket streaming source for a datafeed
+///
+/// The subscribe message may contain `${ENV_VAR}` or `${secret:NAME}`
+/// placeholders, which are resolved on every (re)connect.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct StreamConfig {
+    /// WebSocket URL (ws:// or wss://)
+    #[validate(custom = "validate_ws_url")]
+    pub url: String,
+
+    /// Message sent after connecting to subscribe to updates (optional)
+    pub subscribe_message: Option<String>,
+
+    /// JSON path to extract the value from each message; messages without
+    /// a match (acks, heartbeats) are ignored
+    #[validate(length(min = 1))]
+    pub json_path: String,
+
+    /// JSON path to extract the timestamp from each message (optional)
+    pub json_path_timestamp: Option<String>,
+
+    /// Initial delay before reconnecting after a disconnect (in milliseconds, default: 1000)
+    #[serde(default = "default_stream_reconnect_initial_ms")]
+    #[validate(range(min = 1))]
+    pub reconnect_initial_ms: u64,
+
+    /// Maximum delay between reconnect attempts (in milliseconds, default: 60000)
+    #[serde(default = "default_stream_reconnect_max_ms")]
+    #[validate(range(min = 1))]
+    pub reconnect_max_ms: u64,
+
+    /// Reconnect if no message is received for this long (in seconds, default: 120)
+    #[serde(default = "default_stream_idle_timeout_secs")]
+    #[validate(range(min = 1))]
+    pub idle_timeout_secs: u64,
+}
+
+fn default_stream_reconnect_initial_ms() -> u64 {
+    1000
+}
+
+fn default_stream_reconnect_max_ms() -> u64 {
+    60000
+}
+
+fn default_stream_idle_timeout_secs() -> u64 {
+    120
+}
+
+/// Validates that a URL uses the ws:// or wss:// scheme
+fn validate_ws_url(ws_url: &str) -> Result<(), ValidationError> {
+    match url::Url::parse(ws_url) {
+        Ok(parsed) if matches!(parsed.scheme(), "ws" | "wss") => Ok(()),
+        _ => Err(ValidationError::new(
+            "stream url must be a ws:// or wss:// URL",
+        )),
+    }
+}
+
+/// Sanity checks applied to a datafeed value before it is submitted
+///
+/// A value that fails any check is not submitted and opens the circuit
+/// breaker, which keeps the feed paused until `recovery_readings`
+/// consecutive values pass all checks. As many consecutive values that fail
+/// nothing but `max_onchain_jump_pct` and agree with each other are accepted
+/// as a genuine move.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct OutlierGuardConfig {
+    /// Maximum change from the current on-chain answer (in percent, optional)
+    #[validate(range(min = 0.0))]
+    pub max_onchain_jump_pct: Option<f64>,
+
+    /// Maximum deviation from the median of recent values (in percent, optional)
+    #[validate(range(min = 0.0))]
+    pub max_median_deviation_pct: Option<f64>,
+
+    /// Number of recent values the median is computed over (default: 10)
+    #[serde(default = "default_median_window")]
+    #[validate(range(min = 1))]
+    pub median_window: usize,
+
+    /// Maximum age of the source timestamp (in seconds, optional)
+    pub max_staleness_secs: Option<u64>,
+
+    /// Consecutive sane values required to close the circuit breaker (default: 3)
+    #[serde(default = "default_recovery_readings")]
+    #[validate(range(min = 1))]
+    pub recovery_readings: u32,
+}
+
+fn default_median_window() -> usize {
+    10
+}
+
+fn default_recovery_readings() -> u32 {
+    3
+}
+
+/// Validates that a datafeed has exactly one of a feed_url, a list of sources,
+/// a stream or an on-chain source
+fn validate_datafeed_sources(datafeed: &Datafeed) -> Result<(), ValidationError> {
+    let source_kinds = [
+        !datafeed.feed_url.is_empty(),
+        !datafeed.sources.is_empty(),
+        datafeed.stream.is_some(),
+        datafeed.onchain.is_some(),
+    ];
+    if source_kinds.iter().filter(|set| **set).count() > 1 {
+        return Err(ValidationError::new(
+            "only one of feed_url, sources, stream or onchain can be used",
+        ));
+    }
+
+    if let Some(stream) = &datafeed.stream {
+        if stream.reconnect_initial_ms > stream.reconnect_max_ms {
+            return Err(ValidationError::new(
+                "stream reconnect_initial_ms exceeds reconnect_max_ms",
+            ));
+        }
+        return Ok(());
+    }
+
+    if datafeed.sources.is_empty() && datafeed.onchain.is_none() {
+        if url::Url::parse(&datafeed.feed_url).is_err() {
+            return Err(ValidationError::new("invalid_feed_url"));
+        }
+        if datafeed.feed_json_path.is_empty() {
+            return Err(ValidationError::new("missing_feed_json_path"));
+        }
+    }
+
+    if datafeed.min_sources > datafeed.effective_sources().len() {
+        return Err(ValidationError::new(
+            "min_sources exceeds the number of configured sources",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Validates that a datafeed has either a network and contract address or a list of deployments
+fn validate_datafeed_deployments(datafeed: &Datafeed) -> Result<(), ValidationError> {
+    if datafeed.deployments.is_empty() {
+        if datafeed.networks.is_empty() {
+            return Err(ValidationError::new("missing_networks"));
+        }
+        return validate_eth_address(&datafeed.contract_address);
+    }
+
+    if !datafeed.networks.is_empty() || !datafeed.contract_address.is_empty() {
+        return Err(ValidationError::new(
+            "networks and contract_address cannot be combined with deployments",
+        ));
+    }
+
+    let mut networks = std::collections::HashSet::new();
+    for deployment in &datafeed.deployments {
+        if !networks.insert(deployment.network.as_str()) {
+            return Err(ValidationError::new(
+                "a datafeed can only have one deployment per network",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 /// Validates that a string is a valid Ethereum address
 fn validate_eth_address(address: &str) -> Result<(), ValidationError> {
     // Simple validation: check if it's a hex string starting with 0x and of correct length
@@ -444,6 +1463,14 @@ fn validate_eth_address(address: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Validates that a contract type is registered in `contracts::oracle`
+fn validate_contract_type(contract_type: &str) -> Result<(), ValidationError> {
+    if crate::contracts::oracle::contract_type(contract_type).is_none() {
+        return Err(ValidationError::new("unsupported_contract_type"));
+    }
+    Ok(())
+}
+
 /// Validates that transaction type is either "legacy" or "eip1559"
 pub fn validate_transaction_type(tx_type: &str) -> Result<(), ValidationError> {
     match tx_type.to_lowercase().as_str() {
@@ -451,3 +1478,11 @@ pub fn validate_transaction_type(tx_type: &str) -> Result<(), ValidationError> {
         _ => Err(ValidationError::new("invalid_transaction_type")),
     }
 }
+
+/// Validates that the L2 type is "op_stack", "arbitrum" or "none"
+pub fn validate_l2_type(l2_type: &str) -> Result<(), ValidationError> {
+    match l2_type {
+        "op_stack" | "arbitrum" | "none" => Ok(()),
+        _ => Err(ValidationError::new("invalid_l2_type")),
+    }
+}
@@ -5,7 +5,12 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 use validator::Validate;
 
-use super::models::OmikujiConfig;
+use super::models::{Datafeed, OmikujiConfig};
+use crate::contracts::oracle;
+use crate::datafeed::json_extractor::JsonExtractor;
+use crate::datafeed::onchain::OnChainReader;
+use crate::datafeed::transform::Transform;
+use std::collections::HashMap;
 
 /// Errors that can occur during configuration parsing
 #[derive(Debug, Error)]
@@ -47,16 +52,87 @@ pub fn load_config<P: AsRef<Path>>(config_path: P) -> Result<OmikujiConfig, Conf
     // Validate the configuration
     config.validate().map_err(ConfigError::ValidationError)?;
 
-    // Check if networks referenced by datafeeds exist
-    for datafeed in &config.datafeeds {
-        if !config.networks.iter().any(|n| n.name == datafeed.networks) {
+    // Check if networks referenced by datafeeds exist and their contracts are configured
+    for datafeed in config
+        .datafeeds
+        .iter()
+        .flat_map(Datafeed::expand_deployments)
+    {
+        let Some(network) = config.networks.iter().find(|n| n.name == datafeed.networks) else {
             return Err(ConfigError::Other(format!(
                 "Datafeed '{}' references network '{}' which is not defined",
                 datafeed.name, datafeed.networks
             )));
+        };
+
+        // Batches are sent with the network's gas settings
+        if network.batching.enabled && datafeed.gas_config.is_some() && !datafeed.dry_run {
+            return Err(ConfigError::Other(format!(
+                "Datafeed '{}' sets gas_config on network '{}', which batches submissions \
+                 with the network's gas_config",
+                datafeed.name, datafeed.networks
+            )));
         }
+
+        // Check the settings the contract type needs
+        oracle::validate(&datafeed).map_err(|e| {
+            ConfigError::Other(format!(
+                "Datafeed '{}' has an invalid contract configuration: {}",
+                datafeed.name, e
+            ))
+        })?;
     }
 
+    // Check that JSON paths are valid dot-notation paths or JSONPath expressions
+    for datafeed in &config.datafeeds {
+        let mut paths = Vec::new();
+        for source in datafeed.effective_sources() {
+            if source.onchain.is_none() {
+                paths.push(source.json_path);
+                paths.extend(source.json_path_timestamp);
+            }
+        }
+        if let Some(stream) = &datafeed.stream {
+            paths.push(stream.json_path.clone());
+            paths.extend(stream.json_path_timestamp.clone());
+        }
+
+        for path in &paths {
+            JsonExtractor::validate_path(path).map_err(|e| {
+                ConfigError::Other(format!(
+                    "Datafeed '{}' has an invalid JSON path: {}",
+                    datafeed.name, e
+                ))
+            })?;
+        }
+    }
+
+    // Check that on-chain sources read from defined networks and can be encoded and decoded
+    for datafeed in &config.datafeeds {
+        for source in datafeed.effective_sources() {
+            let Some(onchain) = &source.onchain else {
+                continue;
+            };
+
+            if !config.networks.iter().any(|n| n.name == onchain.network) {
+                return Err(ConfigError::Other(format!(
+                    "Datafeed '{}' has an on-chain source on network '{}' which is not defined",
+                    datafeed.name, onchain.network
+                )));
+            }
+
+            OnChainReader::validate(onchain).map_err(|e| {
+                ConfigError::Other(format!(
+                    "Datafeed '{}' has an invalid on-chain source: {}",
+                    datafeed.name, e
+                ))
+            })?;
+        }
+    }
+
+    // Check that transform expressions parse and only reference other defined feeds
+    validate_transforms(&config)?;
+
     // Check if networks referenced by scheduled tasks exist
     for task in &config.scheduled_tasks {
         if !config.networks.iter().any(|n| n.name == task.network) {
@@ -78,6 +154,57 @@ pub fn load_config<P: AsRef<Path>>(config_path: P) -> Result<OmikujiConfig, Conf
     Ok(config)
 }
 
+/// Validates datafeed transform expressions and rejects circular feed references
+fn validate_transforms(config: &OmikujiConfig) -> Result<(), ConfigError> {
+    let mut references: HashMap<&str, Vec<String>> = HashMap::new();
+
+    for datafeed in &config.datafeeds {
+        let Some(expression) = &datafeed.transform else {
+            continue;
+        };
+
+        let transform = Transform::parse(expression).map_err(|e| {
+            ConfigError::Other(format!(
+                "Datafeed '{}' has an invalid transform: {}",
+                datafeed.name, e
+            ))
+        })?;
+
+        for name in transform.feed_references() {
+            if name == datafeed.name || !config.datafeeds.iter().any(|d| d.name == name) {
+                return Err(ConfigError::Other(format!(
+                    "Datafeed '{}' transform references feed '{}' which is not another defined datafeed",
+                    datafeed.name, name
+                )));
+            }
+        }
+
+        references.insert(&datafeed.name, transform.feed_references());
+    }
+
+    // Walk the reference graph from each feed; reaching the start again means a cycle
+    for start in references.keys() {
+        let mut stack: Vec<&str> = vec![start];
+        let mut visited: Vec<&str> = Vec::new();
+
+        while let Some(current) = stack.pop() {
+            for next in references.get(current).into_iter().flatten() {
+                if next == start {
+                    return Err(ConfigError::Other(format!(
+                        "Datafeed '{start}' transform has a circular reference through feed '{current}'"
+                    )));
+                }
+                if !visited.contains(&next.as_str()) {
+                    visited.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,15 +382,88 @@ mod tests {
 
         let temp_file = create_temp_file(config_yaml);
         let result = load_config(temp_file.path());
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
 
-        // The parser might accept this but validation should catch it
-        // If it doesn't error, check that the value was parsed
-        if result.is_ok() {
-            let config = result.unwrap();
-            assert_eq!(config.datafeeds[0].contract_type, "unsupported_type");
-        } else {
-            assert!(matches!(result, Err(ConfigError::ValidationError(_))));
-        }
+    #[test]
+    fn test_aggregator_v3_and_owner_settable_contract_types() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: chainlink_eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419
+            contract_type: aggregator_v3
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: owner_settable
+            setter_signature: updateAnswer(int256,uint256)
+            read_contract_config: false
+            decimals: 8
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert_eq!(config.datafeeds[0].contract_type, "aggregator_v3");
+        assert_eq!(
+            config.datafeeds[1].setter_signature.as_deref(),
+            Some("updateAnswer(int256,uint256)")
+        );
+    }
+
+    #[test]
+    fn test_owner_settable_requires_valid_setter() {
+        let base = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: owner_settable
+            read_contract_config: false
+            decimals: 8
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+        "#;
+
+        let temp_file = create_temp_file(base);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::Other(_))
+        ));
+
+        let invalid_setter = format!(
+            "{base}
+            setter_signature: setAnswer(string)
+"
+        );
+        let temp_file = create_temp_file(&invalid_setter);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::Other(_))
+        ));
     }
 
     #[test]
@@ -592,4 +665,1214 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result, Err(ConfigError::ValidationError(_))));
     }
+
+    #[test]
+    fn test_multi_source_datafeed() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            sources:
+              - url: https://api.exchange-a.com/ticker/ETHUSD
+                json_path: price
+              - url: https://api.exchange-b.com/ticker/ETHUSD
+                json_path: data.last
+                weight: 2.0
+              - url: https://api.exchange-c.com/ticker/ETHUSD
+                json_path: result.price
+            aggregation:
+              method: weighted_mean
+            min_sources: 2
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        let datafeed = &config.datafeeds[0];
+        assert_eq!(datafeed.sources.len(), 3);
+        assert_eq!(datafeed.sources[0].weight, 1.0);
+        assert_eq!(datafeed.sources[1].weight, 2.0);
+        assert_eq!(datafeed.aggregation.method, "weighted_mean");
+        assert_eq!(datafeed.aggregation.trim_pct, 20.0);
+        assert_eq!(datafeed.min_sources, 2);
+        assert_eq!(datafeed.effective_sources().len(), 3);
+    }
+
+    #[test]
+    fn test_single_source_defaults() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://min-api.cryptocompare.com/data/pricemultifull?fsyms=ETH&tsyms=USD
+            feed_json_path: RAW.ETH.USD.PRICE
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        let datafeed = &config.datafeeds[0];
+        assert!(datafeed.sources.is_empty());
+        assert_eq!(datafeed.aggregation.method, "median");
+        assert_eq!(datafeed.min_sources, 1);
+
+        let sources = datafeed.effective_sources();
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].url, datafeed.feed_url);
+        assert_eq!(sources[0].json_path, "RAW.ETH.USD.PRICE");
+    }
+
+    #[test]
+    fn test_missing_feed_url_and_sources() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_min_sources_exceeds_sources() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            sources:
+              - url: https://api.exchange-a.com/ticker/ETHUSD
+                json_path: price
+              - url: https://api.exchange-b.com/ticker/ETHUSD
+                json_path: price
+            min_sources: 3
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_invalid_aggregation_method() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            sources:
+              - url: https://api.exchange-a.com/ticker/ETHUSD
+                json_path: price
+            aggregation:
+              method: mode
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_invalid_json_path_expression() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/tickers
+            feed_json_path: "$.tickers[?(@.symbol=='ETHUSD'].last"
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        match result {
+            Err(ConfigError::Other(msg)) => {
+                assert!(msg.contains("Datafeed 'eth_usd' has an invalid JSON path"));
+            }
+            _ => panic!("Expected Other error for invalid JSON path"),
+        }
+    }
+
+    #[test]
+    fn test_transform_referencing_other_feed() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/price
+            feed_json_path: price
+          - name: btc_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/price
+            feed_json_path: price
+            transform: "value * eth_usd"
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert_eq!(config.datafeeds[0].transform, None);
+        assert_eq!(
+            config.datafeeds[1].transform.as_deref(),
+            Some("value * eth_usd")
+        );
+    }
+
+    #[test]
+    fn test_transform_unknown_feed_reference() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: btc_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/price
+            feed_json_path: price
+            transform: "value * eth_usd"
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        match result {
+            Err(ConfigError::Other(msg)) => {
+                assert!(msg.contains("references feed 'eth_usd'"));
+            }
+            _ => panic!("Expected Other error for unknown feed reference"),
+        }
+    }
+
+    #[test]
+    fn test_transform_circular_reference() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/price
+            feed_json_path: price
+            transform: "value * btc_eth"
+          - name: btc_eth
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/price
+            feed_json_path: price
+            transform: "value / eth_usd"
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        match result {
+            Err(ConfigError::Other(msg)) => assert!(msg.contains("circular reference")),
+            _ => panic!("Expected Other error for circular transform reference"),
+        }
+    }
+
+    #[test]
+    fn test_transform_invalid_expression() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/price
+            feed_json_path: price
+            transform: "value * (2"
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        match result {
+            Err(ConfigError::Other(msg)) => assert!(msg.contains("invalid transform")),
+            _ => panic!("Expected Other error for invalid transform"),
+        }
+    }
+
+    #[test]
+    fn test_streaming_datafeed() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            stream:
+              url: wss://stream.example.com/ws
+              subscribe_message: '{"op": "subscribe", "channel": "ticker.ETHUSD"}'
+              json_path: $.data.last_price
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        let datafeed = &config.datafeeds[0];
+        let stream = datafeed.stream.as_ref().unwrap();
+        assert_eq!(stream.url, "wss://stream.example.com/ws");
+        assert_eq!(stream.json_path, "$.data.last_price");
+        assert_eq!(stream.reconnect_initial_ms, 1000);
+        assert_eq!(stream.reconnect_max_ms, 60000);
+        assert_eq!(stream.idle_timeout_secs, 120);
+        assert!(datafeed.effective_sources().is_empty());
+    }
+
+    #[test]
+    fn test_stream_with_feed_url() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/price
+            feed_json_path: price
+            stream:
+              url: wss://stream.example.com/ws
+              json_path: price
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_stream_requires_websocket_url() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            stream:
+              url: https://stream.example.com/ws
+              json_path: price
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_onchain_datafeed() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+          - name: base
+            rpc_url: https://mainnet.base.org
+
+        datafeeds:
+          - name: eth_usd
+            networks: base
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            onchain:
+              network: ethereum
+              address: 0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419
+              function: latestRoundData()
+              returns: [uint80, int256, uint256, uint256, uint80]
+              value_index: 1
+              timestamp_index: 3
+              decimals: 8
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        let sources = config.datafeeds[0].effective_sources();
+        assert_eq!(sources.len(), 1);
+
+        let onchain = sources[0].onchain.as_ref().unwrap();
+        assert_eq!(onchain.network, "ethereum");
+        assert_eq!(onchain.function, "latestRoundData()");
+        assert!(onchain.args.is_empty());
+        assert_eq!(onchain.value_index, 1);
+        assert_eq!(onchain.timestamp_index, Some(3));
+        assert_eq!(onchain.decimals, 8);
+    }
+
+    #[test]
+    fn test_onchain_source_mixed_with_http_sources() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            sources:
+              - url: https://api.exchange-a.com/ticker/ETHUSD
+                json_path: price
+              - onchain:
+                  network: ethereum
+                  address: 0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419
+                  function: latestAnswer()
+                  returns: [int256]
+                  decimals: 8
+            min_sources: 2
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        let sources = config.datafeeds[0].effective_sources();
+        assert!(sources[0].onchain.is_none());
+        assert!(sources[1].onchain.is_some());
+    }
+
+    #[test]
+    fn test_onchain_source_undefined_network() {
+        let config_yaml = r#"
+        networks:
+          - name: base
+            rpc_url: https://mainnet.base.org
+
+        datafeeds:
+          - name: eth_usd
+            networks: base
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            onchain:
+              network: ethereum
+              address: 0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419
+              function: latestAnswer()
+              returns: [int256]
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        match result {
+            Err(ConfigError::Other(msg)) => {
+                assert!(msg.contains("'ethereum' which is not defined"))
+            }
+            _ => panic!("Expected Other error for undefined on-chain network"),
+        }
+    }
+
+    #[test]
+    fn test_onchain_source_invalid_value_index() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            onchain:
+              network: ethereum
+              address: 0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419
+              function: latestAnswer()
+              returns: [int256]
+              value_index: 1
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+
+        match result {
+            Err(ConfigError::Other(msg)) => assert!(msg.contains("invalid on-chain source")),
+            _ => panic!("Expected Other error for invalid on-chain source"),
+        }
+    }
+
+    #[test]
+    fn test_fetch_cache_config() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds: []
+
+        fetch_cache:
+          ttl_ms: 5000
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert!(config.fetch_cache.enabled);
+        assert_eq!(config.fetch_cache.ttl_ms, 5000);
+    }
+
+    #[test]
+    fn test_fetch_retry_and_rate_limits_config() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds: []
+
+        fetch_retry:
+          max_retries: 5
+          max_backoff_ms: 10000
+
+        rate_limits:
+          api.coingecko.com:
+            requests_per_second: 0.5
+            burst: 5
+          api.binance.com:
+            requests_per_second: 10
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert_eq!(config.fetch_retry.max_retries, 5);
+        assert_eq!(config.fetch_retry.initial_backoff_ms, 1000);
+        assert_eq!(config.fetch_retry.max_backoff_ms, 10000);
+
+        let coingecko = &config.rate_limits["api.coingecko.com"];
+        assert_eq!(coingecko.requests_per_second, 0.5);
+        assert_eq!(coingecko.burst, 5);
+        assert_eq!(config.rate_limits["api.binance.com"].burst, 1);
+    }
+
+    #[test]
+    fn test_invalid_rate_limit() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds: []
+
+        rate_limits:
+          api.coingecko.com:
+            requests_per_second: 0
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let result = load_config(temp_file.path());
+        assert!(matches!(result, Err(ConfigError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_outlier_guard_config() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+            outlier_guard:
+              max_onchain_jump_pct: 20
+              max_median_deviation_pct: 5
+              max_staleness_secs: 300
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        let guard = config.datafeeds[0].outlier_guard.as_ref().unwrap();
+        assert_eq!(guard.max_onchain_jump_pct, Some(20.0));
+        assert_eq!(guard.max_median_deviation_pct, Some(5.0));
+        assert_eq!(guard.max_staleness_secs, Some(300));
+        assert_eq!(guard.median_window, 10);
+        assert_eq!(guard.recovery_readings, 3);
+    }
+
+    #[test]
+    fn test_datafeed_dry_run() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+            dry_run: true
+          - name: btc_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: true
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/btc
+            feed_json_path: price
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert!(config.datafeeds[0].dry_run);
+        assert!(!config.datafeeds[1].dry_run);
+    }
+
+    #[test]
+    fn test_datafeed_deployments() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+          - name: base
+            rpc_url: https://mainnet.base.org
+
+        datafeeds:
+          - name: eth_usd
+            check_frequency: 60
+            contract_type: fluxmon
+            read_contract_config: false
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            decimals: 8
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+            deployments:
+              - network: ethereum
+                contract_address: 0x1234567890123456789012345678901234567890
+              - network: base
+                contract_address: 0x0987654321098765432109876543210987654321
+                deviation_threshold_pct: 0.1
+                decimals: 18
+                gas_config:
+                  gas_limit: 200000
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        let datafeed = &config.datafeeds[0];
+        assert_eq!(datafeed.network_label(), "ethereum,base");
+
+        let deployments = datafeed.expand_deployments();
+        assert_eq!(deployments.len(), 2);
+
+        assert_eq!(deployments[0].networks, "ethereum");
+        assert_eq!(
+            deployments[0].contract_address,
+            "0x1234567890123456789012345678901234567890"
+        );
+        assert_eq!(deployments[0].deviation_threshold_pct, 0.5);
+        assert_eq!(deployments[0].decimals, Some(8));
+        assert!(deployments[0].gas_config.is_none());
+
+        assert_eq!(deployments[1].networks, "base");
+        assert_eq!(deployments[1].deviation_threshold_pct, 0.1);
+        assert_eq!(deployments[1].decimals, Some(18));
+        assert_eq!(deployments[1].minimum_update_frequency, 3600);
+        assert_eq!(
+            deployments[1].gas_config.as_ref().unwrap().gas_limit,
+            Some(200000)
+        );
+        assert!(deployments.iter().all(|d| d.deployments.is_empty()));
+    }
+
+    #[test]
+    fn test_datafeed_deployments_invalid() {
+        let base = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            check_frequency: 60
+            contract_type: fluxmon
+            read_contract_config: false
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+        "#;
+
+        // No network at all
+        let temp_file = create_temp_file(base);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::ValidationError(_))
+        ));
+
+        // Both a top-level network and deployments
+        let mixed = format!(
+            "{base}
+            networks: ethereum
+            contract_address: 0x1234567890123456789012345678901234567890
+            deployments:
+              - network: ethereum
+                contract_address: 0x1234567890123456789012345678901234567890
+"
+        );
+        let temp_file = create_temp_file(&mixed);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::ValidationError(_))
+        ));
+
+        // Two deployments on the same network
+        let duplicate = format!(
+            "{base}
+            deployments:
+              - network: ethereum
+                contract_address: 0x1234567890123456789012345678901234567890
+              - network: ethereum
+                contract_address: 0x0987654321098765432109876543210987654321
+"
+        );
+        let temp_file = create_temp_file(&duplicate);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::ValidationError(_))
+        ));
+
+        // Deployment on an undefined network
+        let undefined = format!(
+            "{base}
+            deployments:
+              - network: base
+                contract_address: 0x1234567890123456789012345678901234567890
+"
+        );
+        let temp_file = create_temp_file(&undefined);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::Other(_))
+        ));
+    }
+
+    #[test]
+    fn test_custom_contract_type() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: custom
+            read_contract_config: false
+            decimals: 8
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+            custom_abi:
+              submit_function: updatePrice(bytes32,int256,uint64)
+              submit_args:
+                - "0x4554482f55534400000000000000000000000000000000000000000000000000"
+                - "{value}"
+                - "{timestamp}"
+              latest_value:
+                function: getPrice(bytes32)
+                args: ["0x4554482f55534400000000000000000000000000000000000000000000000000"]
+                returns: [int256, uint64]
+                timestamp_index: 1
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        let abi = config.datafeeds[0].custom_abi.as_ref().unwrap();
+        assert_eq!(abi.submit_args[1], "{value}");
+        assert_eq!(abi.latest_value.returns, vec!["int256", "uint64"]);
+        assert_eq!(abi.latest_value.value_index, 0);
+        assert_eq!(abi.latest_value.timestamp_index, Some(1));
+
+        // A custom contract without custom_abi is rejected
+        let without_abi = config_yaml
+            .split("            custom_abi:")
+            .next()
+            .unwrap()
+            .to_string();
+        let temp_file = create_temp_file(&without_abi);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::Other(_))
+        ));
+    }
+
+    #[test]
+    fn test_gas_config_override_on_batched_network() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+          - name: base
+            rpc_url: https://base.llamarpc.com
+            batching:
+              enabled: true
+
+        datafeeds:
+          - name: eth_usd
+            check_frequency: 60
+            contract_type: fluxmon
+            read_contract_config: false
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            decimals: 8
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+            deployments:
+              - network: ethereum
+                contract_address: 0x1234567890123456789012345678901234567890
+                gas_config:
+                  gas_limit: 200000
+              - network: base
+                contract_address: 0x0987654321098765432109876543210987654321
+        "#;
+
+        // Overrides on networks without batching are fine
+        let temp_file = create_temp_file(config_yaml);
+        assert!(load_config(temp_file.path()).is_ok());
+
+        let batched = config_yaml.replace(
+            "0x0987654321098765432109876543210987654321",
+            "0x0987654321098765432109876543210987654321\n                gas_config:\n                  gas_limit: 200000",
+        );
+        let temp_file = create_temp_file(&batched);
+        match load_config(temp_file.path()) {
+            Err(ConfigError::Other(msg)) => {
+                assert!(msg.contains("sets gas_config on network 'base'"))
+            }
+            other => panic!("Expected Other error for gas_config with batching, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_network_batching() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+          - name: base
+            rpc_url: https://base.llamarpc.com
+            batching:
+              enabled: true
+              window_ms: 500
+
+        datafeeds: []
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert!(!config.networks[0].batching.enabled);
+
+        let batching = &config.networks[1].batching;
+        assert!(batching.enabled);
+        assert_eq!(batching.window_ms, 500);
+        assert_eq!(batching.max_batch_size, 20);
+        assert_eq!(
+            batching.multicall_address,
+            crate::contracts::multicall::MULTICALL3_ADDRESS
+        );
+
+        // An invalid multicall address is rejected
+        let invalid = config_yaml.replace(
+            "window_ms: 500",
+            "window_ms: 500\n              multicall_address: 0x1234",
+        );
+        let temp_file = create_temp_file(&invalid);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_network_confirmations() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+          - name: polygon
+            rpc_url: https://polygon.llamarpc.com
+            confirmations: 32
+
+        datafeeds: []
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert_eq!(config.networks[0].confirmations, 1);
+        assert_eq!(config.networks[1].confirmations, 32);
+
+        // A depth of 0 would never wait for the including block
+        let invalid = config_yaml.replace("confirmations: 32", "confirmations: 0");
+        let temp_file = create_temp_file(&invalid);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_network_rpc_endpoints() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+            rpc_endpoints:
+              - url: https://rpc.ankr.com/eth
+              - url: https://cloudflare-eth.com
+                priority: 5
+            rpc_failover:
+              max_block_lag: 2
+
+        datafeeds: []
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+        let network = &config.networks[0];
+
+        let endpoints: Vec<(String, u32)> = network
+            .endpoints()
+            .into_iter()
+            .map(|e| (e.url, e.priority))
+            .collect();
+        assert_eq!(
+            endpoints,
+            vec![
+                ("https://eth.llamarpc.com".to_string(), 0),
+                ("https://rpc.ankr.com/eth".to_string(), 1),
+                ("https://cloudflare-eth.com".to_string(), 5),
+            ]
+        );
+        assert_eq!(network.rpc_failover.max_block_lag, 2);
+        assert_eq!(network.rpc_failover.broadcast_endpoints, 2);
+
+        let invalid = config_yaml.replace("https://cloudflare-eth.com", "cloudflare");
+        let temp_file = create_temp_file(&invalid);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_network_read_quorum() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+            rpc_endpoints:
+              - url: https://rpc.ankr.com/eth
+            read_quorum:
+              enabled: true
+              min_agreeing: 2
+
+        datafeeds: []
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+        let quorum = &config.networks[0].read_quorum;
+        assert!(quorum.enabled);
+        assert_eq!(quorum.endpoints, 3);
+        assert_eq!(quorum.min_agreeing, 2);
+
+        // Two endpoints can't give three agreeing answers
+        let invalid = config_yaml.replace("min_agreeing: 2", "min_agreeing: 3");
+        let temp_file = create_temp_file(&invalid);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_network_websocket_endpoints() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: wss://ethereum-rpc.publicnode.com
+            rpc_endpoints:
+              - url: https://eth.llamarpc.com
+            check_feeds_on_new_heads: true
+          - name: base
+            rpc_url: https://base.llamarpc.com
+
+        datafeeds: []
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert_eq!(
+            config.networks[0].rpc_url,
+            "wss://ethereum-rpc.publicnode.com"
+        );
+        assert!(config.networks[0].check_feeds_on_new_heads);
+        assert!(!config.networks[1].check_feeds_on_new_heads);
+    }
+
+    #[test]
+    fn test_network_chain_id() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            chain_id: 1
+            rpc_url: https://eth.llamarpc.com
+          - name: base
+            rpc_url: https://base.llamarpc.com
+
+        datafeeds: []
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert_eq!(config.networks[0].chain_id, Some(1));
+        assert_eq!(config.networks[1].chain_id, None);
+    }
+
+    #[test]
+    fn test_network_l2_type() {
+        let config_yaml = r#"
+        networks:
+          - name: base
+            rpc_url: https://base.llamarpc.com
+            l2_type: op_stack
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds: []
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+
+        assert_eq!(config.networks[0].l2_type, "op_stack");
+        assert_eq!(config.networks[1].l2_type, "none");
+
+        let invalid_yaml = r#"
+        networks:
+          - name: base
+            rpc_url: https://base.llamarpc.com
+            l2_type: zksync
+
+        datafeeds: []
+        "#;
+
+        let temp_file = create_temp_file(invalid_yaml);
+        assert!(load_config(temp_file.path()).is_err());
+    }
+
+    #[test]
+    fn test_datafeed_custom_errors() {
+        let config_yaml = r#"
+        networks:
+          - name: ethereum
+            rpc_url: https://eth.llamarpc.com
+
+        datafeeds:
+          - name: eth_usd
+            networks: ethereum
+            check_frequency: 60
+            contract_address: 0x1234567890123456789012345678901234567890
+            contract_type: fluxmon
+            read_contract_config: false
+            decimals: 8
+            minimum_update_frequency: 3600
+            deviation_threshold_pct: 0.5
+            feed_url: https://api.example.com/eth
+            feed_json_path: price
+            custom_errors:
+              - RoundNotOpen(uint32)
+        "#;
+
+        let temp_file = create_temp_file(config_yaml);
+        let config = load_config(temp_file.path()).unwrap();
+        assert_eq!(
+            config.datafeeds[0].custom_errors,
+            vec!["RoundNotOpen(uint32)"]
+        );
+
+        // An unparseable signature is rejected
+        let invalid = config_yaml.replace("RoundNotOpen(uint32)", "RoundNotOpen(uint32");
+        let temp_file = create_temp_file(&invalid);
+        assert!(matches!(
+            load_config(temp_file.path()),
+            Err(ConfigError::Other(_))
+        ));
+    }
 }
@@ -17,6 +17,13 @@ pub mod gas {
     /// Fee bump multiplier for each retry attempt
     pub const FEE_BUMP_MULTIPLIER: f64 = 1.1;
 
+    /// Minimum fee increase in percent for a same-nonce replacement to be accepted
+    /// Most nodes require 10%, some 12.5%
+    pub const MIN_REPLACEMENT_BUMP_PERCENT: f64 = 12.5;
+
+    /// Gas limit of a zero-value transfer, used to cancel a stuck transaction
+    pub const TRANSFER_GAS_LIMIT: u64 = 21_000;
+
     /// Default max gas price in gwei (for safety)
     pub const DEFAULT_MAX_GAS_PRICE_GWEI: u64 = 500;
 
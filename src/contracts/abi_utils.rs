@@ -4,7 +4,7 @@
 //! reducing code duplication and ensuring consistent handling across the codebase.
 
 use alloy::{
-    dyn_abi::{DynSolValue, FunctionExt, JsonAbiExt},
+    dyn_abi::{DynSolType, DynSolValue, FunctionExt, JsonAbiExt},
     json_abi::{Function, Param, StateMutability},
     primitives::{Address, Bytes, I256, U256},
 };
@@ -46,6 +46,16 @@ pub fn create_function_definition(
     signature: &str,
     return_type: Option<&str>,
     state_mutability: StateMutability,
+) -> Result<Function> {
+    let return_types: Vec<String> = return_type.map(str::to_string).into_iter().collect();
+    create_function_definition_with_outputs(signature, &return_types, state_mutability)
+}
+
+/// Create a Function definition from a signature and a list of return types
+pub fn create_function_definition_with_outputs(
+    signature: &str,
+    return_types: &[String],
+    state_mutability: StateMutability,
 ) -> Result<Function> {
     let (func_name, param_types) = parse_function_signature(signature)?;
 
@@ -63,17 +73,21 @@ pub fn create_function_definition(
         })
         .collect::<Result<Vec<_>>>()?;
 
-    // Handle return type
-    let outputs = if let Some(ret_type) = return_type {
-        vec![Param {
-            ty: ret_type.to_string(),
-            name: "result".to_string(),
+    // Handle return types
+    let outputs = return_types
+        .iter()
+        .enumerate()
+        .map(|(i, ret_type)| Param {
+            ty: ret_type.clone(),
+            name: if return_types.len() == 1 {
+                "result".to_string()
+            } else {
+                format!("result{i}")
+            },
             components: vec![],
             internal_type: None,
-        }]
-    } else {
-        Vec::new()
-    };
+        })
+        .collect();
 
     Ok(Function {
         name: func_name,
@@ -105,10 +119,6 @@ pub fn encode_parameter(param_type: &str, value: &str) -> Result<DynSolValue> {
             Ok(DynSolValue::Bool(val))
         }
         "string" => Ok(DynSolValue::String(value.to_string())),
-        "bytes32" => {
-            // For now, we'll skip bytes32 support as it requires specific type handling
-            Err(anyhow::anyhow!("bytes32 parameter type not yet supported"))
-        }
         "address[]" => {
             // Parse comma-separated addresses
             let addresses = value
@@ -121,10 +131,13 @@ pub fn encode_parameter(param_type: &str, value: &str) -> Result<DynSolValue> {
                 .collect::<Result<Vec<_>>>()?;
             Ok(DynSolValue::Array(addresses))
         }
-        _ => Err(anyhow::anyhow!(
-            "Unsupported parameter type: {}",
-            param_type
-        )),
+        // Any other Solidity type, e.g. "uint32", "bytes32" or "uint32[]" ("[1800,0]")
+        other => {
+            let ty = DynSolType::parse(other)
+                .with_context(|| format!("Unsupported parameter type: {other}"))?;
+            ty.coerce_str(value)
+                .with_context(|| format!("Failed to parse {other} value"))
+        }
     }
 }
 
@@ -168,6 +181,43 @@ pub fn decode_function_return(function: &Function, data: &Bytes) -> Result<Vec<D
         .context("Failed to decode function return value")
 }
 
+/// Convert a decoded numeric return value to f64, scaled down by `decimals`
+///
+/// Supports signed and unsigned integers of any size and booleans (0 or 1).
+pub fn dyn_value_to_f64(value: &DynSolValue, decimals: u8) -> Result<f64> {
+    let raw = match value {
+        DynSolValue::Uint(v, _) => v.to_string(),
+        DynSolValue::Int(v, _) => v.to_string(),
+        DynSolValue::Bool(b) => u8::from(*b).to_string(),
+        other => {
+            return Err(anyhow::anyhow!(
+                "Cannot convert {:?} to a number",
+                other.as_type()
+            ))
+        }
+    };
+
+    let raw = raw
+        .parse::<f64>()
+        .with_context(|| format!("Failed to convert {raw} to f64"))?;
+
+    Ok(raw / 10f64.powi(decimals as i32))
+}
+
+/// Convert a decoded integer return value to I256 without scaling
+pub fn dyn_value_to_i256(value: &DynSolValue) -> Result<I256> {
+    match value {
+        DynSolValue::Int(v, _) => Ok(*v),
+        DynSolValue::Uint(v, _) => {
+            I256::try_from(*v).with_context(|| format!("{v} does not fit in an int256"))
+        }
+        other => Err(anyhow::anyhow!(
+            "Cannot convert {:?} to an integer",
+            other.as_type()
+        )),
+    }
+}
+
 /// Generic contract call builder
 pub struct ContractCallBuilder {
     signature: String,
@@ -280,6 +330,74 @@ mod tests {
         assert!(matches!(val, DynSolValue::String(s) if s == "hello world"));
     }
 
+    #[test]
+    fn test_encode_other_solidity_types() {
+        let val = encode_parameter("uint32[]", "[1800, 0]").unwrap();
+        assert_eq!(
+            val,
+            DynSolValue::Array(vec![
+                DynSolValue::Uint(U256::from(1800), 32),
+                DynSolValue::Uint(U256::ZERO, 32),
+            ])
+        );
+
+        assert!(encode_parameter("notatype", "1").is_err());
+        assert!(encode_parameter("uint8", "256").is_err());
+    }
+
+    #[test]
+    fn test_decode_multiple_outputs() {
+        let function = create_function_definition_with_outputs(
+            "latestRoundData()",
+            &[
+                "uint80".to_string(),
+                "int256".to_string(),
+                "uint256".to_string(),
+                "uint256".to_string(),
+                "uint80".to_string(),
+            ],
+            StateMutability::View,
+        )
+        .unwrap();
+
+        let encoded = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(U256::from(1), 80),
+            DynSolValue::Int(I256::try_from(250_000_000_000i64).unwrap(), 256),
+            DynSolValue::Uint(U256::from(1_700_000_000u64), 256),
+            DynSolValue::Uint(U256::from(1_700_000_000u64), 256),
+            DynSolValue::Uint(U256::from(1), 80),
+        ])
+        .abi_encode_params();
+
+        let decoded = decode_function_return(&function, &encoded.into()).unwrap();
+        assert_eq!(decoded.len(), 5);
+        assert_eq!(dyn_value_to_f64(&decoded[1], 8).unwrap(), 2500.0);
+        assert_eq!(dyn_value_to_f64(&decoded[3], 0).unwrap(), 1_700_000_000.0);
+    }
+
+    #[test]
+    fn test_dyn_value_to_f64() {
+        let negative = DynSolValue::Int(I256::try_from(-150i64).unwrap(), 256);
+        assert_eq!(dyn_value_to_f64(&negative, 2).unwrap(), -1.5);
+        assert_eq!(dyn_value_to_f64(&DynSolValue::Bool(true), 0).unwrap(), 1.0);
+        assert!(dyn_value_to_f64(&DynSolValue::String("1".to_string()), 0).is_err());
+    }
+
+    #[test]
+    fn test_dyn_value_to_i256() {
+        let negative = I256::try_from(-150i64).unwrap();
+        assert_eq!(
+            dyn_value_to_i256(&DynSolValue::Int(negative, 256)).unwrap(),
+            negative
+        );
+        assert_eq!(
+            dyn_value_to_i256(&DynSolValue::Uint(U256::from(42), 64)).unwrap(),
+            I256::try_from(42).unwrap()
+        );
+        assert!(dyn_value_to_i256(&DynSolValue::Uint(U256::MAX, 256)).is_err());
+        assert!(dyn_value_to_i256(&DynSolValue::Bool(true)).is_err());
+    }
+
     #[test]
     fn test_contract_call_builder() {
         let call_data = ContractCallBuilder::new("transfer(address,uint256)")
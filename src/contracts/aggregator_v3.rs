@@ -0,0 +1,126 @@
+//! Read path for Chainlink AggregatorV3Interface feeds
+//!
+//! Used to monitor third-party feeds; values are never submitted to them.
+
+use super::oracle::{ContractConfig, OracleContract, RoundData, Submission};
+use alloy::{
+    network::Ethereum,
+    primitives::{Address, I256},
+    providers::Provider,
+    rpc::types::{BlockId, TransactionRequest},
+    sol,
+    sol_types::SolCall,
+    transports::Transport,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+
+sol! {
+    #[sol(rpc)]
+    interface IAggregatorV3 {
+        function decimals() external view returns (uint8);
+        function description() external view returns (string memory);
+        function latestRoundData() external view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound);
+    }
+}
+
+/// Wrapper for AggregatorV3Interface contract reads
+pub struct AggregatorV3Contract<T: Transport + Clone, P: Provider<T, Ethereum>> {
+    address: Address,
+    provider: P,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> AggregatorV3Contract<T, P> {
+    /// Create a new AggregatorV3 contract instance
+    pub fn new(address: Address, provider: P) -> Self {
+        Self {
+            address,
+            provider,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Get the latest round data
+    pub async fn latest_round_data(&self) -> Result<RoundData> {
+        let call = IAggregatorV3::latestRoundDataCall {};
+        let tx = TransactionRequest::default()
+            .to(self.address)
+            .input(call.abi_encode().into());
+        let result = self.provider.call(&tx).block(BlockId::latest()).await?;
+
+        let decoded = IAggregatorV3::latestRoundDataCall::abi_decode_returns(&result, true)?;
+        Ok(RoundData {
+            round_id: decoded.roundId.to(),
+            answer: decoded.answer,
+            updated_at: decoded.updatedAt.saturating_to(),
+        })
+    }
+
+    /// Get decimals
+    pub async fn decimals(&self) -> Result<u8> {
+        let call = IAggregatorV3::decimalsCall {};
+        let tx = TransactionRequest::default()
+            .to(self.address)
+            .input(call.abi_encode().into());
+        let result = self.provider.call(&tx).block(BlockId::latest()).await?;
+
+        let decoded = IAggregatorV3::decimalsCall::abi_decode_returns(&result, true)?;
+        Ok(decoded._0)
+    }
+}
+
+#[async_trait]
+impl<T, P> OracleContract for AggregatorV3Contract<T, P>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum> + Clone + 'static,
+{
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn latest_round_data(&self) -> Result<RoundData> {
+        AggregatorV3Contract::latest_round_data(self).await
+    }
+
+    async fn read_config(&self) -> Result<ContractConfig> {
+        Ok(ContractConfig {
+            decimals: self.decimals().await?,
+            min_value: None,
+            max_value: None,
+        })
+    }
+
+    async fn submission(&self, _answer: I256, _oracle: Option<Address>) -> Result<Submission> {
+        anyhow::bail!(
+            "aggregator_v3 contract {} is read-only, values cannot be submitted",
+            self.address
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{aliases::U80, U256};
+    use alloy::sol_types::SolValue;
+
+    #[test]
+    fn test_decode_latest_round_data() {
+        let encoded = (
+            U80::from(42),
+            I256::try_from(250_000_000_000i64).unwrap(),
+            U256::from(1_700_000_000u64),
+            U256::from(1_700_000_060u64),
+            U80::from(42),
+        )
+            .abi_encode_params();
+
+        let decoded =
+            IAggregatorV3::latestRoundDataCall::abi_decode_returns(&encoded, true).unwrap();
+        assert_eq!(decoded.roundId, U80::from(42));
+        assert_eq!(decoded.answer, I256::try_from(250_000_000_000i64).unwrap());
+        assert_eq!(decoded.updatedAt, U256::from(1_700_000_060u64));
+    }
+}
@@ -0,0 +1,365 @@
+//! Oracle contracts described entirely in the configuration
+//!
+//! The submit function, its argument template and the read functions come
+//! from the datafeed's `custom_abi`, so supporting a new contract is a YAML
+//! change. Calls are encoded and decoded with [`super::abi_utils`].
+
+use super::abi_utils::{
+    create_function_definition_with_outputs, decode_function_return, dyn_value_to_f64,
+    dyn_value_to_i256, encode_function_call,
+};
+use super::oracle::{ContractConfig, OracleContract, RoundData, Submission};
+use crate::config::models::{CustomAbiConfig, CustomReadFunction};
+use alloy::{
+    dyn_abi::{DynSolType, DynSolValue},
+    json_abi::StateMutability,
+    network::Ethereum,
+    primitives::{Address, Bytes, I256, U256},
+    providers::Provider,
+    rpc::types::{BlockId, TransactionRequest},
+    transports::Transport,
+};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+/// Placeholder replaced with the scaled value
+const VALUE: &str = "{value}";
+/// Placeholder replaced with the current Unix time
+const TIMESTAMP: &str = "{timestamp}";
+/// Placeholder replaced with the latest round + 1
+const ROUND: &str = "{round}";
+
+/// Checks that every call can be encoded and decoded, without calling the contract
+pub fn validate_custom_abi(abi: &CustomAbiConfig, read_contract_config: bool) -> Result<()> {
+    let sample_args = render_args(&abi.submit_args, I256::ONE, 0, Some(U256::ZERO));
+    encode_function_call(
+        &abi.submit_function,
+        &sample_args,
+        StateMutability::NonPayable,
+    )
+    .context("invalid submit_function or submit_args")?;
+
+    validate_read_function("latest_value", &abi.latest_value)?;
+    for (name, function) in [
+        ("latest_timestamp", &abi.latest_timestamp),
+        ("latest_round", &abi.latest_round),
+        ("decimals", &abi.decimals),
+    ] {
+        if let Some(function) = function {
+            validate_read_function(name, function)?;
+        }
+    }
+
+    if abi.latest_value.timestamp_index.is_none() && abi.latest_timestamp.is_none() {
+        anyhow::bail!("either latest_value.timestamp_index or latest_timestamp is required");
+    }
+    if uses_round(&abi.submit_args) && abi.latest_round.is_none() {
+        anyhow::bail!("submit_args use {ROUND}, which requires latest_round");
+    }
+    if read_contract_config && abi.decimals.is_none() {
+        anyhow::bail!("read_contract_config requires a decimals function");
+    }
+
+    Ok(())
+}
+
+fn validate_read_function(name: &str, function: &CustomReadFunction) -> Result<()> {
+    encode_function_call(&function.function, &function.args, StateMutability::View)
+        .with_context(|| format!("invalid {name} function or args"))?;
+
+    for return_type in &function.returns {
+        DynSolType::parse(return_type)
+            .with_context(|| format!("invalid {name} return type '{return_type}'"))?;
+    }
+
+    let indices = std::iter::once(("value_index", function.value_index))
+        .chain(function.timestamp_index.map(|i| ("timestamp_index", i)));
+    for (index_name, index) in indices {
+        if index >= function.returns.len() {
+            anyhow::bail!(
+                "{name} {index_name} {index} is out of range for {} return value(s)",
+                function.returns.len()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn uses_round(args: &[String]) -> bool {
+    args.iter().any(|arg| arg.contains(ROUND))
+}
+
+/// Replaces the placeholders in the submit argument template
+fn render_args(args: &[String], answer: I256, timestamp: u64, round: Option<U256>) -> Vec<String> {
+    args.iter()
+        .map(|arg| {
+            let arg = arg
+                .replace(VALUE, &answer.to_string())
+                .replace(TIMESTAMP, &timestamp.to_string());
+            match round {
+                Some(round) => arg.replace(ROUND, &round.to_string()),
+                None => arg,
+            }
+        })
+        .collect()
+}
+
+/// Wrapper for contracts configured with `custom_abi`
+pub struct CustomContract<T: Transport + Clone, P: Provider<T, Ethereum>> {
+    address: Address,
+    provider: P,
+    abi: CustomAbiConfig,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> CustomContract<T, P> {
+    /// Create a new custom contract instance
+    pub fn new(address: Address, provider: P, abi: CustomAbiConfig) -> Self {
+        Self {
+            address,
+            provider,
+            abi,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Calls a read function and returns all decoded return values
+    async fn call(&self, function: &CustomReadFunction) -> Result<Vec<DynSolValue>> {
+        let call_data =
+            encode_function_call(&function.function, &function.args, StateMutability::View)?;
+        let tx = TransactionRequest::default()
+            .to(self.address)
+            .input(call_data.into());
+        let result = self
+            .provider
+            .call(&tx)
+            .block(BlockId::latest())
+            .await
+            .with_context(|| format!("Failed to call {}", function.function))?;
+
+        decode_read_function(function, &result)
+    }
+
+    /// Reads the latest round, if the contract has a latest_round function
+    async fn latest_round(&self) -> Result<Option<U256>> {
+        let Some(function) = &self.abi.latest_round else {
+            return Ok(None);
+        };
+
+        let values = self.call(function).await?;
+        let round = dyn_value_to_i256(output(&values, function.value_index)?)?;
+        U256::try_from(round)
+            .context("Latest round is negative")
+            .map(Some)
+    }
+
+    /// Builds the submit calldata
+    pub fn submit_calldata(
+        &self,
+        answer: I256,
+        timestamp: u64,
+        round: Option<U256>,
+    ) -> Result<Bytes> {
+        let args = render_args(&self.abi.submit_args, answer, timestamp, round);
+        encode_function_call(
+            &self.abi.submit_function,
+            &args,
+            StateMutability::NonPayable,
+        )
+    }
+}
+
+/// Decodes a read function result
+fn decode_read_function(function: &CustomReadFunction, data: &Bytes) -> Result<Vec<DynSolValue>> {
+    let definition = create_function_definition_with_outputs(
+        &function.function,
+        &function.returns,
+        StateMutability::View,
+    )?;
+    decode_function_return(&definition, data)
+}
+
+/// Returns the return value at `index`
+fn output(values: &[DynSolValue], index: usize) -> Result<&DynSolValue> {
+    values
+        .get(index)
+        .with_context(|| format!("No return value at index {index}"))
+}
+
+#[async_trait]
+impl<T, P> OracleContract for CustomContract<T, P>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum> + Clone + 'static,
+{
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn latest_round_data(&self) -> Result<RoundData> {
+        let latest_value = &self.abi.latest_value;
+        let values = self.call(latest_value).await?;
+        let answer = dyn_value_to_i256(output(&values, latest_value.value_index)?)?;
+
+        let updated_at = match (&self.abi.latest_timestamp, latest_value.timestamp_index) {
+            (Some(function), _) => {
+                let values = self.call(function).await?;
+                dyn_value_to_f64(output(&values, function.value_index)?, 0)? as u64
+            }
+            (None, Some(index)) => dyn_value_to_f64(output(&values, index)?, 0)? as u64,
+            (None, None) => anyhow::bail!("custom_abi has no timestamp source"),
+        };
+
+        let round_id = self.latest_round().await?.unwrap_or(U256::ZERO);
+
+        Ok(RoundData {
+            round_id,
+            answer,
+            updated_at,
+        })
+    }
+
+    async fn read_config(&self) -> Result<ContractConfig> {
+        let function = self
+            .abi
+            .decimals
+            .as_ref()
+            .context("custom_abi has no decimals function")?;
+        let values = self.call(function).await?;
+        let decimals = dyn_value_to_f64(output(&values, function.value_index)?, 0)?;
+
+        Ok(ContractConfig {
+            decimals: decimals as u8,
+            min_value: None,
+            max_value: None,
+        })
+    }
+
+    async fn submission(&self, answer: I256, _oracle: Option<Address>) -> Result<Submission> {
+        let round_id = if uses_round(&self.abi.submit_args) {
+            let round = self.latest_round().await?.unwrap_or(U256::ZERO);
+            Some(round + U256::from(1))
+        } else {
+            None
+        };
+        let timestamp = crate::datafeed::contract_utils::current_timestamp()?;
+
+        Ok(Submission {
+            calldata: self.submit_calldata(answer, timestamp, round_id)?,
+            round_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::{ProviderBuilder, RootProvider};
+    use alloy::transports::http::{Client, Http};
+
+    const FEED_ID: &str = "0x4554482f55534400000000000000000000000000000000000000000000000000";
+
+    fn read_function(function: &str, returns: &[&str]) -> CustomReadFunction {
+        CustomReadFunction {
+            function: function.to_string(),
+            args: vec![FEED_ID.to_string()],
+            returns: returns.iter().map(|r| r.to_string()).collect(),
+            value_index: 0,
+            timestamp_index: None,
+        }
+    }
+
+    fn abi() -> CustomAbiConfig {
+        CustomAbiConfig {
+            submit_function: "updatePrice(bytes32,int256,uint64)".to_string(),
+            submit_args: vec![
+                FEED_ID.to_string(),
+                VALUE.to_string(),
+                TIMESTAMP.to_string(),
+            ],
+            latest_value: CustomReadFunction {
+                timestamp_index: Some(1),
+                ..read_function("getPrice(bytes32)", &["int256", "uint64"])
+            },
+            latest_timestamp: None,
+            latest_round: None,
+            decimals: None,
+        }
+    }
+
+    fn contract(abi: CustomAbiConfig) -> CustomContract<Http<Client>, RootProvider<Http<Client>>> {
+        let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse().unwrap());
+        CustomContract::new(Address::ZERO, provider, abi)
+    }
+
+    #[test]
+    fn test_validate_custom_abi() {
+        assert!(validate_custom_abi(&abi(), false).is_ok());
+
+        // Decimals can't be read from the contract
+        assert!(validate_custom_abi(&abi(), true).is_err());
+
+        // No timestamp source
+        let mut no_timestamp = abi();
+        no_timestamp.latest_value.timestamp_index = None;
+        assert!(validate_custom_abi(&no_timestamp, false).is_err());
+
+        // {round} without latest_round
+        let mut with_round = abi();
+        with_round.submit_args[2] = ROUND.to_string();
+        assert!(validate_custom_abi(&with_round, false).is_err());
+        with_round.latest_round = Some(read_function("latestRound(bytes32)", &["uint256"]));
+        assert!(validate_custom_abi(&with_round, false).is_ok());
+
+        // Argument count doesn't match the signature
+        let mut missing_arg = abi();
+        missing_arg.submit_args.pop();
+        assert!(validate_custom_abi(&missing_arg, false).is_err());
+
+        // Index out of range
+        let mut bad_index = abi();
+        bad_index.latest_value.timestamp_index = Some(2);
+        assert!(validate_custom_abi(&bad_index, false).is_err());
+    }
+
+    #[test]
+    fn test_submit_calldata() {
+        let answer = I256::try_from(250_000_000_000i64).unwrap();
+        let calldata = contract(abi())
+            .submit_calldata(answer, 1_700_000_000, None)
+            .unwrap();
+
+        let expected = encode_function_call(
+            "updatePrice(bytes32,int256,uint64)",
+            &[
+                FEED_ID.to_string(),
+                "250000000000".to_string(),
+                "1700000000".to_string(),
+            ],
+            StateMutability::NonPayable,
+        )
+        .unwrap();
+        assert_eq!(calldata, expected);
+    }
+
+    #[test]
+    fn test_decode_latest_value() {
+        let function = abi().latest_value;
+        let encoded = DynSolValue::Tuple(vec![
+            DynSolValue::Int(I256::try_from(-42i64).unwrap(), 256),
+            DynSolValue::Uint(U256::from(1_700_000_000u64), 64),
+        ])
+        .abi_encode_params();
+
+        let values = decode_read_function(&function, &encoded.into()).unwrap();
+        assert_eq!(
+            dyn_value_to_i256(output(&values, 0).unwrap()).unwrap(),
+            I256::try_from(-42i64).unwrap()
+        );
+        assert_eq!(
+            dyn_value_to_f64(output(&values, 1).unwrap(), 0).unwrap(),
+            1_700_000_000.0
+        );
+    }
+}
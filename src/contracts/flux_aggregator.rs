@@ -1,11 +1,12 @@
+use super::oracle::{ContractConfig, Ineligible, OracleContract, RoundData, Submission};
+use super::submitter::CallSubmitter;
 use crate::config::models::Network as NetworkConfig;
 use crate::database::TransactionLogRepository;
 use crate::gas::GasEstimate;
-use crate::metrics::gas_metrics::{GasMetrics, TransactionDetails};
 use crate::metrics::ContractMetrics;
 use alloy::{
-    network::{Ethereum, TransactionBuilder},
-    primitives::{Address, I256, U256},
+    network::Ethereum,
+    primitives::{Address, Bytes, I256, U256},
     providers::Provider,
     rpc::types::{BlockId, TransactionReceipt, TransactionRequest},
     sol,
@@ -13,10 +14,9 @@ use alloy::{
     transports::Transport,
 };
 use anyhow::Result;
+use async_trait::async_trait;
 use std::sync::Arc;
 use std::time::Instant;
-use tokio::time::Duration;
-use tracing::{error, info, warn};
 
 // Define the Solidity interface using alloy's sol! macro
 sol! {
@@ -36,6 +36,46 @@ sol! {
         function latestRoundData() external view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound);
         function submit(uint256 _roundId, int256 _submission) external;
         function oracleRoundState(address _oracle, uint32 _queriedRoundId) external view returns (bool _eligibleToSubmit, uint32 _roundId, int256 _latestSubmission, uint64 _startedAt, uint64 _timeout, uint128 _availableFunds, uint8 _oracleCount, uint128 _paymentAmount);
+        function getOracles() external view returns (address[] memory);
+        function withdrawablePayment(address _oracle) external view returns (uint256);
+    }
+}
+
+/// The round an oracle should submit to, as suggested by `oracleRoundState(oracle, 0)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct OracleRoundState {
+    pub eligible_to_submit: bool,
+    pub round_id: u32,
+    /// Start time of the round, 0 if submitting would start it
+    pub started_at: u64,
+    pub available_funds: u128,
+    pub oracle_count: u8,
+    pub payment_amount: u128,
+}
+
+impl OracleRoundState {
+    /// Why the oracle can't submit to the suggested round, None if it can
+    ///
+    /// The contract only reports that an oracle is ineligible, so the reason is
+    /// inferred: an open round was already submitted to, a new round is held
+    /// back by the restart delay.
+    pub fn ineligibility(&self, is_oracle: bool) -> Option<Ineligible> {
+        if !is_oracle {
+            return Some(Ineligible::NotOracle);
+        }
+        if self.available_funds < self.payment_amount {
+            return Some(Ineligible::InsufficientFunds {
+                available: self.available_funds,
+                payment: self.payment_amount,
+            });
+        }
+        if self.eligible_to_submit {
+            None
+        } else if self.started_at > 0 {
+            Some(Ineligible::RoundAlreadySubmitted(self.round_id))
+        } else {
+            Some(Ineligible::RestartDelay)
+        }
     }
 }
 
@@ -217,303 +257,174 @@ impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> FluxAggregatorContr
         Ok(decoded._0)
     }
 
-    /// Submit a new price to the FluxAggregator contract with gas estimation and retry logic
-    pub async fn submit_price_with_gas_estimation(
-        &self,
-        round_id: U256,
-        price: I256,
-        network_config: &NetworkConfig,
-        feed_name: &str,
-        tx_log_repo: Option<Arc<TransactionLogRepository>>,
-        from_address: Option<Address>,
-    ) -> Result<TransactionReceipt> {
-        let gas_config = &network_config.gas_config;
-        let fee_bumping = &gas_config.fee_bumping;
-
-        // Create the function call
-        let call = IFluxAggregator::submitCall {
-            _roundId: round_id,
-            _submission: price,
+    /// Get the round `oracle` should submit to and whether it is eligible
+    pub async fn oracle_round_state(&self, oracle: Address) -> Result<OracleRoundState> {
+        let call = IFluxAggregator::oracleRoundStateCall {
+            _oracle: oracle,
+            _queriedRoundId: 0,
         };
-
-        // Build base transaction request
-        let mut tx = TransactionRequest::default()
+        let tx = TransactionRequest::default()
             .to(self.address)
             .input(call.abi_encode().into());
+        let result = self.provider.call(&tx).block(BlockId::latest()).await?;
 
-        // Set from address if provided (needed for accurate gas estimation)
-        if let Some(from) = from_address {
-            tx = tx.from(from);
-        }
-
-        // Estimate gas
-        let gas_estimator = crate::gas::GasEstimator::<T, P>::new(
-            Arc::new(self.provider.clone()),
-            network_config.clone(),
-        );
-        let mut gas_estimate = gas_estimator.estimate_gas(&tx).await?;
-
-        let mut attempt = 0;
-        let max_attempts = if fee_bumping.enabled {
-            fee_bumping.max_retries + 1
-        } else {
-            1
-        };
-
-        loop {
-            attempt += 1;
-
-            // Apply gas settings
-            tx = tx.with_gas_limit(gas_estimate.gas_limit.to::<u64>());
-
-            // Apply fee settings based on transaction type
-            match network_config.transaction_type.to_lowercase().as_str() {
-                "legacy" => {
-                    if let Some(gas_price) = gas_estimate.gas_price {
-                        tx = tx.with_gas_price(gas_price.to::<u128>());
-                    }
-                }
-                "eip1559" => {
-                    if let Some(max_fee) = gas_estimate.max_fee_per_gas {
-                        tx = tx.with_max_fee_per_gas(max_fee.to::<u128>());
-                    }
-                    if let Some(priority_fee) = gas_estimate.max_priority_fee_per_gas {
-                        tx = tx.with_max_priority_fee_per_gas(priority_fee.to::<u128>());
-                    }
-                }
-                _ => {
-                    warn!("Unknown transaction type, defaulting to EIP-1559");
-                    if let Some(max_fee) = gas_estimate.max_fee_per_gas {
-                        tx = tx.with_max_fee_per_gas(max_fee.to::<u128>());
-                    }
-                    if let Some(priority_fee) = gas_estimate.max_priority_fee_per_gas {
-                        tx = tx.with_max_priority_fee_per_gas(priority_fee.to::<u128>());
-                    }
-                }
-            }
+        let decoded = IFluxAggregator::oracleRoundStateCall::abi_decode_returns(&result, true)?;
+        Ok(OracleRoundState {
+            eligible_to_submit: decoded._eligibleToSubmit,
+            round_id: decoded._roundId,
+            started_at: decoded._startedAt,
+            available_funds: decoded._availableFunds,
+            oracle_count: decoded._oracleCount,
+            payment_amount: decoded._paymentAmount,
+        })
+    }
 
-            info!("Sending transaction (attempt {})", attempt);
+    /// Get the addresses of the oracles allowed to submit
+    pub async fn get_oracles(&self) -> Result<Vec<Address>> {
+        let call = IFluxAggregator::getOraclesCall {};
+        let tx = TransactionRequest::default()
+            .to(self.address)
+            .input(call.abi_encode().into());
+        let result = self.provider.call(&tx).block(BlockId::latest()).await?;
 
-            // Record contract write attempt
-            let write_start = Instant::now();
+        let decoded = IFluxAggregator::getOraclesCall::abi_decode_returns(&result, true)?;
+        Ok(decoded._0)
+    }
 
-            // Send transaction
-            let pending_tx = match self.provider.send_transaction(tx.clone()).await {
-                Ok(tx) => tx,
-                Err(e) => {
-                    let write_duration = write_start.elapsed();
-                    error!("Failed to send transaction: {}", e);
+    /// Get the payment an oracle can withdraw
+    pub async fn withdrawable_payment(&self, oracle: Address) -> Result<U256> {
+        let call = IFluxAggregator::withdrawablePaymentCall { _oracle: oracle };
+        let tx = TransactionRequest::default()
+            .to(self.address)
+            .input(call.abi_encode().into());
+        let result = self.provider.call(&tx).block(BlockId::latest()).await?;
 
-                    // Record failed contract write
-                    ContractMetrics::record_contract_write(
-                        feed_name,
-                        &network_config.name,
-                        false,
-                        write_duration,
-                        None,
-                    );
+        let decoded = IFluxAggregator::withdrawablePaymentCall::abi_decode_returns(&result, true)?;
+        Ok(decoded._0)
+    }
 
-                    // Check for specific error types
-                    let error_str = e.to_string();
-                    if error_str.contains("revert") {
-                        ContractMetrics::record_transaction_revert(
-                            feed_name,
-                            &network_config.name,
-                            &error_str,
-                        );
-                    }
-
-                    if attempt >= max_attempts {
-                        ContractMetrics::record_transaction_retry(
-                            feed_name,
-                            &network_config.name,
-                            "max_attempts_reached",
-                            attempt as u32,
-                        );
-                        return Err(anyhow::anyhow!(
-                            "Failed to send transaction after {} attempts: {}",
-                            attempt as u32,
-                            e
-                        ));
-                    }
-
-                    ContractMetrics::record_transaction_retry(
-                        feed_name,
-                        &network_config.name,
-                        "send_error",
-                        attempt as u32,
-                    );
-                    continue;
-                }
-            };
+    /// Reads the oracle round state and works out why `oracle` can't submit, if it can't
+    async fn eligibility(&self, oracle: Address) -> Result<(OracleRoundState, Option<Ineligible>)> {
+        let state = self.oracle_round_state(oracle).await?;
 
-            let tx_hash = *pending_tx.tx_hash();
-            info!("Transaction sent: 0x{:x}", tx_hash);
+        // Oracle membership is only looked up to explain an ineligible oracle
+        let is_oracle = state.eligible_to_submit || self.get_oracles().await?.contains(&oracle);
+        let ineligible = state.ineligibility(is_oracle);
 
-            // Wait for confirmation with timeout
-            let wait_duration = Duration::from_secs(fee_bumping.initial_wait_seconds);
+        Ok((state, ineligible))
+    }
 
-            // Record transaction submission time
-            let submission_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
+    /// Build, estimate and simulate a price submission with `eth_call` without broadcasting it
+    /// Returns the gas estimate and the revert reason if the submission would fail
+    pub async fn simulate_submit(
+        &self,
+        round_id: U256,
+        price: I256,
+        network_config: &NetworkConfig,
+        from_address: Option<Address>,
+    ) -> Result<(GasEstimate, Option<String>)> {
+        CallSubmitter::<T, P>::new(self.address, self.provider.clone())
+            .simulate(
+                Self::submit_calldata(round_id, price),
+                network_config,
+                from_address,
+            )
+            .await
+    }
 
-            match tokio::time::timeout(
-                wait_duration,
-                pending_tx.with_required_confirmations(1).get_receipt(),
+    /// Submit a new price to the FluxAggregator contract with gas estimation and retry logic
+    pub async fn submit_price_with_gas_estimation(
+        &self,
+        round_id: U256,
+        price: I256,
+        network_config: &NetworkConfig,
+        feed_name: &str,
+        tx_log_repo: Option<Arc<TransactionLogRepository>>,
+        from_address: Option<Address>,
+    ) -> Result<TransactionReceipt> {
+        CallSubmitter::<T, P>::new(self.address, self.provider.clone())
+            .submit_with_gas_estimation(
+                Self::submit_calldata(round_id, price),
+                network_config,
+                feed_name,
+                tx_log_repo,
+                from_address,
             )
             .await
-            {
-                Ok(Ok(receipt)) => {
-                    let write_duration = write_start.elapsed();
-                    info!("Transaction confirmed: 0x{:x}", tx_hash);
-
-                    // Record successful contract write
-                    ContractMetrics::record_contract_write(
-                        feed_name,
-                        &network_config.name,
-                        true,
-                        write_duration,
-                        Some(&format!("0x{tx_hash:x}")),
-                    );
+    }
 
-                    // Record confirmation time
-                    let confirmation_time = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs();
-                    ContractMetrics::record_confirmation_time(
-                        feed_name,
-                        &network_config.name,
-                        submission_time,
-                        confirmation_time,
-                    );
+    /// Encodes `submit(roundId, submission)`
+    fn submit_calldata(round_id: U256, price: I256) -> Bytes {
+        IFluxAggregator::submitCall {
+            _roundId: round_id,
+            _submission: price,
+        }
+        .abi_encode()
+        .into()
+    }
+}
 
-                    // Record gas metrics
-                    GasMetrics::record_transaction(
-                        feed_name,
-                        &network_config.name,
-                        &receipt,
-                        gas_estimate.gas_limit,
-                        &network_config.transaction_type,
-                    );
+#[async_trait]
+impl<T, P> OracleContract for FluxAggregatorContract<T, P>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum> + Clone + 'static,
+{
+    fn address(&self) -> Address {
+        self.address
+    }
 
-                    // Log transaction if repository is available
-                    if let Some(repo) = &tx_log_repo {
-                        if let Err(e) = Self::log_transaction(
-                            repo,
-                            &tx_hash,
-                            &receipt,
-                            feed_name,
-                            &network_config.name,
-                            &gas_estimate,
-                            &network_config.transaction_type,
-                        )
-                        .await
-                        {
-                            error!("Failed to log transaction: {}", e);
-                        }
-                    }
-
-                    return Ok(receipt);
-                }
-                Ok(Err(e)) => {
-                    error!("Transaction failed: {}", e);
-
-                    // Record failed transaction
-                    GasMetrics::record_failed_transaction(
-                        feed_name,
-                        &network_config.name,
-                        gas_estimate.gas_limit,
-                        gas_estimate.gas_price.or(gas_estimate.max_fee_per_gas),
-                        &network_config.transaction_type,
-                        &e.to_string(),
-                    );
+    async fn latest_round_data(&self) -> Result<RoundData> {
+        let answer = self.latest_answer().await?;
+        let updated_at = self.latest_timestamp().await?;
+        let round_id = self.latest_round().await?;
 
-                    if attempt >= max_attempts {
-                        return Err(anyhow::anyhow!(
-                            "Transaction failed after {} attempts: {}",
-                            attempt as u32,
-                            e
-                        ));
-                    }
-                }
-                Err(_) => {
-                    warn!(
-                        "Transaction timed out after {} seconds: 0x{:x}",
-                        wait_duration.as_secs(),
-                        tx_hash
-                    );
-                    if attempt >= max_attempts {
-                        return Err(anyhow::anyhow!(
-                            "Transaction timed out after {} attempts",
-                            attempt
-                        ));
-                    }
-                }
-            }
+        Ok(RoundData {
+            round_id,
+            answer,
+            updated_at: updated_at.saturating_to(),
+        })
+    }
 
-            // Bump fees for retry
-            if fee_bumping.enabled && attempt < max_attempts {
-                gas_estimate = gas_estimator.bump_fees(&gas_estimate, attempt);
-                info!("Bumping fees for retry attempt {}", attempt + 1);
-            }
-        }
+    async fn read_config(&self) -> Result<ContractConfig> {
+        Ok(ContractConfig {
+            decimals: self.decimals().await?,
+            min_value: Some(self.min_submission_value().await?),
+            max_value: Some(self.max_submission_value().await?),
+        })
     }
 
-    /// Log transaction details to the database
-    async fn log_transaction(
-        repo: &Arc<TransactionLogRepository>,
-        tx_hash: &alloy::primitives::TxHash,
-        receipt: &TransactionReceipt,
-        feed_name: &str,
-        network_name: &str,
-        gas_estimate: &GasEstimate,
-        tx_type: &str,
-    ) -> Result<()> {
-        let gas_used = receipt.gas_used;
-        let gas_limit = gas_estimate.gas_limit;
-        let efficiency_percent = (gas_used as f64 / gas_limit.to::<u128>() as f64) * 100.0;
-
-        let gas_price_gwei = if let Some(price) = gas_estimate.gas_price {
-            alloy::primitives::utils::format_units(price, "gwei")?.parse::<f64>()?
-        } else if let Some(max_fee) = gas_estimate.max_fee_per_gas {
-            alloy::primitives::utils::format_units(max_fee, "gwei")?.parse::<f64>()?
-        } else {
-            0.0
+    async fn submission(&self, answer: I256, oracle: Option<Address>) -> Result<Submission> {
+        let round_id = match oracle {
+            Some(oracle) => match self.eligibility(oracle).await? {
+                (state, None) => U256::from(state.round_id),
+                (_, Some(ineligible)) => return Err(ineligible.into()),
+            },
+            // Without a wallet, e.g. in dry-run mode, assume a new round
+            None => self.latest_round().await? + U256::from(1),
         };
 
-        let total_cost_wei = U256::from(gas_used) * gas_estimate.gas_price.unwrap_or(U256::ZERO);
+        Ok(Submission {
+            calldata: Self::submit_calldata(round_id, answer),
+            round_id: Some(round_id),
+        })
+    }
 
-        let details = TransactionDetails {
-            tx_hash: format!("0x{tx_hash:x}"),
-            feed_name: feed_name.to_string(),
-            network: network_name.to_string(),
-            gas_limit: gas_limit.to::<u64>(),
-            gas_used: gas_used as u64,
-            gas_price_gwei,
-            total_cost_wei: total_cost_wei.to::<u128>(),
-            efficiency_percent,
-            tx_type: tx_type.to_string(),
-            status: if receipt.status() {
-                "success"
-            } else {
-                "failed"
-            }
-            .to_string(),
-            block_number: receipt.block_number.unwrap_or(0),
-            error_message: None,
-        };
+    async fn check_eligibility(&self, oracle: Address) -> Result<Option<Ineligible>> {
+        Ok(self.eligibility(oracle).await?.1)
+    }
 
-        repo.save_transaction(details).await?;
-        Ok(())
+    async fn withdrawable_payment(&self, oracle: Address) -> Result<Option<U256>> {
+        FluxAggregatorContract::withdrawable_payment(self, oracle)
+            .await
+            .map(Some)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::metrics::gas_metrics::TransactionDetails;
 
     #[test]
     fn test_transaction_details_creation() {
@@ -525,11 +436,13 @@ mod tests {
             gas_used: 150000,
             gas_price_gwei: 35.5,
             total_cost_wei: 5325000000000000u128,
+            l1_fee_wei: 0,
             efficiency_percent: 75.0,
             tx_type: "eip1559".to_string(),
             status: "success".to_string(),
             block_number: 15000000,
             error_message: None,
+            confirmation_status: Some("confirmed".to_string()),
         };
 
         assert_eq!(details.feed_name, "eth_usd");
@@ -671,4 +584,71 @@ mod tests {
         let low_efficiency = (50000f64 / 200000f64) * 100.0;
         assert_eq!(low_efficiency, 25.0);
     }
+
+    fn round_state(eligible_to_submit: bool, started_at: u64) -> OracleRoundState {
+        OracleRoundState {
+            eligible_to_submit,
+            round_id: 7,
+            started_at,
+            available_funds: 1_000,
+            oracle_count: 3,
+            payment_amount: 100,
+        }
+    }
+
+    #[test]
+    fn test_oracle_round_state_ineligibility() {
+        assert_eq!(round_state(true, 0).ineligibility(true), None);
+        assert_eq!(round_state(true, 1_700_000_000).ineligibility(true), None);
+
+        assert_eq!(
+            round_state(false, 0).ineligibility(false),
+            Some(Ineligible::NotOracle)
+        );
+        assert_eq!(
+            round_state(false, 1_700_000_000).ineligibility(true),
+            Some(Ineligible::RoundAlreadySubmitted(7))
+        );
+        assert_eq!(
+            round_state(false, 0).ineligibility(true),
+            Some(Ineligible::RestartDelay)
+        );
+
+        let unfunded = OracleRoundState {
+            available_funds: 50,
+            ..round_state(true, 0)
+        };
+        assert_eq!(
+            unfunded.ineligibility(true),
+            Some(Ineligible::InsufficientFunds {
+                available: 50,
+                payment: 100
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_oracle_round_state() {
+        use alloy::dyn_abi::DynSolValue;
+
+        let encoded = DynSolValue::Tuple(vec![
+            DynSolValue::Bool(true),
+            DynSolValue::Uint(U256::from(7), 32),
+            DynSolValue::Int(I256::try_from(250_000_000_000i64).unwrap(), 256),
+            DynSolValue::Uint(U256::from(1_700_000_000u64), 64),
+            DynSolValue::Uint(U256::from(600), 64),
+            DynSolValue::Uint(U256::from(1_000), 128),
+            DynSolValue::Uint(U256::from(3), 8),
+            DynSolValue::Uint(U256::from(100), 128),
+        ])
+        .abi_encode_params();
+
+        let decoded =
+            IFluxAggregator::oracleRoundStateCall::abi_decode_returns(&encoded, true).unwrap();
+        assert!(decoded._eligibleToSubmit);
+        assert_eq!(decoded._roundId, 7);
+        assert_eq!(decoded._startedAt, 1_700_000_000);
+        assert_eq!(decoded._availableFunds, 1_000);
+        assert_eq!(decoded._paymentAmount, 100);
+    }
 }
@@ -5,7 +5,7 @@
 
 use crate::config::models::Network as NetworkConfig;
 use crate::database::TransactionLogRepository;
-use crate::gas::GasEstimate;
+use crate::gas::{fetch_l1_fee, GasEstimate, L1Fee};
 use crate::metrics::{
     MetricsContext, RetryMetricsRecorder, TimedOperationRecorder, TransactionMetricsRecorder,
 };
@@ -276,8 +276,22 @@ impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> FluxAggregatorContr
             return Err(anyhow::anyhow!("Transaction failed: 0x{:x}", tx_hash));
         }
 
+        let l1_fee = fetch_l1_fee(
+            &network_config.name,
+            &network_config.l2_type,
+            &self.provider,
+            tx_hash,
+            receipt.effective_gas_price,
+        )
+        .await;
+
         // Record successful transaction with all metrics
-        tx_recorder.record_success(&receipt, gas_estimate.gas_limit, Some(submission_time));
+        tx_recorder.record_success(
+            &receipt,
+            gas_estimate.gas_limit,
+            l1_fee,
+            Some(submission_time),
+        );
 
         // Log transaction if repository is available
         if let Some(repo) = tx_log_repo {
@@ -288,6 +302,7 @@ impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> FluxAggregatorContr
                     &receipt,
                     gas_estimate,
                     &network_config.transaction_type,
+                    l1_fee,
                 )
                 .await
             {
@@ -324,6 +339,7 @@ impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> FluxAggregatorContr
         receipt: &TransactionReceipt,
         gas_estimate: &GasEstimate,
         tx_type: &str,
+        l1_fee: L1Fee,
     ) -> Result<()> {
         let gas_used = receipt.gas_used;
         let gas_limit = gas_estimate.gas_limit;
@@ -337,7 +353,8 @@ impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> FluxAggregatorContr
             0.0
         };
 
-        let total_cost_wei = U256::from(gas_used) * gas_estimate.gas_price.unwrap_or(U256::ZERO);
+        let total_cost_wei = U256::from(gas_used) * U256::from(receipt.effective_gas_price)
+            + U256::from(l1_fee.extra_wei());
 
         let details = crate::metrics::gas_metrics::TransactionDetails {
             tx_hash: format!("0x{tx_hash:x}"),
@@ -347,6 +364,7 @@ impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> FluxAggregatorContr
             gas_used: gas_used as u64,
             gas_price_gwei,
             total_cost_wei: total_cost_wei.to::<u128>(),
+            l1_fee_wei: l1_fee.wei,
             efficiency_percent,
             tx_type: tx_type.to_string(),
             status: if receipt.status() {
@@ -357,6 +375,7 @@ impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> FluxAggregatorContr
             .to_string(),
             block_number: receipt.block_number.unwrap_or(0),
             error_message: None,
+            confirmation_status: Some("confirmed".to_string()),
         };
 
         repo.save_transaction(details).await?;
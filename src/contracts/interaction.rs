@@ -5,12 +5,13 @@
 
 use crate::config::models::Network as NetworkConfig;
 use crate::database::TransactionLogRepository;
-use crate::gas::utils as gas_utils;
+use crate::gas::{fill_missing_fees, utils as gas_utils};
 use crate::metrics::ContractMetrics;
 use crate::utils::{TransactionContext, TransactionLogger};
 use alloy::{
-    network::{Network, ReceiptResponse, TransactionBuilder},
-    primitives::{Address, Bytes},
+    consensus::Transaction as _,
+    network::{Network, ReceiptResponse, TransactionBuilder, TransactionResponse},
+    primitives::{Address, Bytes, TxHash, U256},
     providers::Provider,
     rpc::types::BlockId,
     transports::Transport,
@@ -18,7 +19,7 @@ use alloy::{
 use anyhow::{Context, Result};
 use std::sync::Arc;
 use std::time::Instant;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 /// Common contract interaction builder
 pub struct ContractInteraction<T, N, P>
@@ -207,6 +208,9 @@ where
     }
 
     /// Send transaction with retry logic for stuck transactions
+    ///
+    /// Retries reuse the nonce of the first send, so they replace the stuck
+    /// transaction instead of queueing behind it.
     async fn send_with_retry(
         &self,
         mut tx: N::TransactionRequest,
@@ -217,14 +221,32 @@ where
         N::ReceiptResponse: std::fmt::Debug,
     {
         let fee_bumping_config = &self.network_config.gas_config.fee_bumping;
+        let max_attempts = if fee_bumping_config.enabled {
+            fee_bumping_config.max_retries as u32 + 1
+        } else {
+            1
+        };
         let mut attempt = 0;
+        // Transactions sent with the nonce, any of them may still be mined
+        let mut sent: Vec<TxHash> = Vec::new();
+
+        // Replacements raise the fees of the stuck transaction, so they must be known
+        if fee_bumping_config.enabled {
+            let legacy = self
+                .network_config
+                .transaction_type
+                .eq_ignore_ascii_case("legacy");
+            fill_missing_fees(&*self.provider, &mut tx, legacy)
+                .await
+                .context("Failed to estimate fees")?;
+        }
 
-        loop {
+        let receipt = loop {
             attempt += 1;
             debug!("Transaction attempt #{} for {}", attempt, context.name());
 
             // Apply fee bump if this is a retry
-            if attempt > 1 && fee_bumping_config.enabled {
+            if attempt > 1 {
                 self.apply_fee_bump(&mut tx, attempt);
             }
 
@@ -232,7 +254,11 @@ where
             match self.provider.send_transaction(tx.clone()).await {
                 Ok(pending_tx) => {
                     let tx_hash = *pending_tx.tx_hash();
+                    sent.push(tx_hash);
                     info!("Transaction submitted: 0x{:x}", tx_hash);
+                    if max_attempts > 1 && tx.nonce().is_none() {
+                        self.pin_nonce(&mut tx, tx_hash).await;
+                    }
 
                     // Wait for confirmation with timeout
                     match tokio::time::timeout(
@@ -241,34 +267,56 @@ where
                     )
                     .await
                     {
-                        Ok(Ok(receipt)) => {
-                            if receipt.status() {
-                                TransactionLogger::log_confirmation(tx_hash, receipt.gas_used());
-                                return Ok(receipt);
-                            } else {
-                                error!("Transaction failed: 0x{:x}", tx_hash);
-                                return Err(anyhow::anyhow!("Transaction reverted"));
-                            }
-                        }
+                        Ok(Ok(receipt)) => break receipt,
                         Ok(Err(e)) => {
                             error!("Failed to get receipt: {}", e);
-                            if attempt >= fee_bumping_config.max_retries as u32 {
+                            if let Some(receipt) = self.find_mined(&sent).await {
+                                break receipt;
+                            }
+                            if attempt >= max_attempts {
                                 return Err(e.into());
                             }
                             // Continue to retry
                         }
                         Err(_) => {
-                            if attempt >= fee_bumping_config.max_retries as u32 {
+                            // One of the transactions sent may have been mined just now
+                            if let Some(receipt) = self.find_mined(&sent).await {
+                                break receipt;
+                            }
+                            if attempt >= max_attempts {
+                                if fee_bumping_config.enabled
+                                    && fee_bumping_config.cancel_after_max_retries
+                                {
+                                    self.cancel(&tx, attempt + 1).await;
+                                }
                                 return Err(anyhow::anyhow!("Transaction confirmation timeout"));
                             }
-                            info!("Transaction confirmation timeout, retrying with higher fee");
+                            info!("Transaction confirmation timeout, replacing with higher fee");
                             // Continue to retry with fee bump
                         }
                     }
                 }
                 Err(e) => {
                     error!("Failed to send transaction: {}", e);
-                    if attempt >= fee_bumping_config.max_retries as u32 {
+
+                    // A replacement is rejected once the transaction it replaces
+                    // is mined, which must not be retried
+                    if let Some(receipt) = self.find_mined(&sent).await {
+                        break receipt;
+                    }
+                    if self.nonce_used(&tx, &sent).await {
+                        TransactionLogger::log_failure(
+                            context.context_type(),
+                            context.name(),
+                            &e.to_string(),
+                        );
+                        return Err(anyhow::anyhow!(
+                            "Nonce of the transaction was used while sending its replacement: {}",
+                            e
+                        ));
+                    }
+
+                    if attempt >= max_attempts {
                         TransactionLogger::log_failure(
                             context.context_type(),
                             context.name(),
@@ -279,53 +327,143 @@ where
                     // Continue to retry
                 }
             }
+        };
+
+        let tx_hash = receipt.transaction_hash();
+        if receipt.status() {
+            TransactionLogger::log_confirmation(tx_hash, receipt.gas_used());
+            Ok(receipt)
+        } else {
+            error!("Transaction failed: 0x{:x}", tx_hash);
+            Err(anyhow::anyhow!("Transaction reverted"))
         }
     }
 
-    /// Apply fee bump to a transaction for retry
-    fn apply_fee_bump(&self, tx: &mut N::TransactionRequest, attempt: u32)
+    /// Returns the receipt of a transaction already sent with the nonce, if one was mined
+    async fn find_mined(&self, sent: &[TxHash]) -> Option<N::ReceiptResponse> {
+        for tx_hash in sent {
+            match self.provider.get_transaction_receipt(*tx_hash).await {
+                Ok(Some(receipt)) => {
+                    info!("Transaction 0x{:x} was mined", tx_hash);
+                    return Some(receipt);
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to get the receipt of 0x{:x}: {}", tx_hash, e),
+            }
+        }
+
+        None
+    }
+
+    /// Returns true if the nonce was used after transactions were sent with it
+    ///
+    /// One of them may have been mined without its receipt being available yet.
+    async fn nonce_used(&self, tx: &N::TransactionRequest, sent: &[TxHash]) -> bool
     where
         N::TransactionRequest: TransactionBuilder<N>,
     {
-        let increase_percent = self
-            .network_config
-            .gas_config
-            .fee_bumping
-            .fee_increase_percent;
+        let (Some(from), Some(nonce)) = (tx.from(), tx.nonce()) else {
+            return false;
+        };
+        if sent.is_empty() {
+            return false;
+        }
 
-        match self.network_config.transaction_type.to_lowercase().as_str() {
-            "legacy" => {
-                // Bump gas price for legacy transactions
-                // Note: This is a simplified implementation. In production, you'd want to
-                // retrieve the current gas price from the transaction and multiply it.
-                if let Some(base_price) = self.network_config.gas_config.gas_price_gwei {
-                    let base_wei = gas_utils::gwei_to_wei(base_price);
-                    let bumped_wei =
-                        gas_utils::calculate_fee_bump(base_wei, attempt, increase_percent);
-                    tx.set_gas_price(bumped_wei.to::<u128>());
-                    TransactionLogger::log_fee_bump(attempt, base_wei, bumped_wei);
-                }
+        match self.provider.get_transaction_count(from).latest().await {
+            Ok(mined_nonce) => mined_nonce > nonce,
+            Err(e) => {
+                warn!("Failed to get the nonce of {}: {}", from, e);
+                false
             }
-            "eip1559" => {
-                // Bump both max fee and priority fee for EIP-1559
-                if let Some(base_max_fee) = self.network_config.gas_config.max_fee_per_gas_gwei {
-                    let base_wei = gas_utils::gwei_to_wei(base_max_fee);
-                    let bumped_wei =
-                        gas_utils::calculate_fee_bump(base_wei, attempt, increase_percent);
-                    tx.set_max_fee_per_gas(bumped_wei.to::<u128>());
-
-                    TransactionLogger::log_fee_bump(attempt, base_wei, bumped_wei);
-                }
-                if let Some(base_priority) =
-                    self.network_config.gas_config.max_priority_fee_per_gas_gwei
-                {
-                    let base_wei = gas_utils::gwei_to_wei(base_priority);
-                    let bumped_wei =
-                        gas_utils::calculate_fee_bump(base_wei, attempt, increase_percent);
-                    tx.set_max_priority_fee_per_gas(bumped_wei.to::<u128>());
-                }
+        }
+    }
+
+    /// Pins the sender and nonce the provider filled in for a sent transaction
+    async fn pin_nonce(&self, tx: &mut N::TransactionRequest, tx_hash: TxHash)
+    where
+        N::TransactionRequest: TransactionBuilder<N>,
+    {
+        match self.provider.get_transaction_by_hash(tx_hash).await {
+            Ok(Some(sent)) => {
+                tx.set_from(sent.from());
+                tx.set_nonce(sent.nonce());
             }
-            _ => {}
+            Ok(None) => warn!(
+                "Transaction 0x{:x} not found, retries will not replace it",
+                tx_hash
+            ),
+            Err(e) => warn!(
+                "Failed to get transaction 0x{:x}, retries will not replace it: {}",
+                tx_hash, e
+            ),
+        }
+    }
+
+    /// Replaces a stuck transaction with a zero-value self-transfer
+    async fn cancel(&self, stuck: &N::TransactionRequest, attempt: u32)
+    where
+        N::TransactionRequest: TransactionBuilder<N>,
+    {
+        let (Some(from), Some(nonce)) = (stuck.from(), stuck.nonce()) else {
+            warn!("Cannot cancel a stuck transaction with an unknown nonce");
+            return;
+        };
+
+        let mut tx = N::TransactionRequest::default()
+            .with_from(from)
+            .with_to(from)
+            .with_value(U256::ZERO)
+            .with_nonce(nonce)
+            .with_gas_limit(crate::constants::gas::TRANSFER_GAS_LIMIT);
+        if let Some(gas_price) = stuck.gas_price() {
+            tx.set_gas_price(gas_price);
+        }
+        if let Some(max_fee) = stuck.max_fee_per_gas() {
+            tx.set_max_fee_per_gas(max_fee);
+        }
+        if let Some(priority_fee) = stuck.max_priority_fee_per_gas() {
+            tx.set_max_priority_fee_per_gas(priority_fee);
+        }
+        self.apply_fee_bump(&mut tx, attempt);
+
+        match self.provider.send_transaction(tx).await {
+            Ok(pending_tx) => info!(
+                "Sent cancellation 0x{:x} for nonce {} of {}",
+                pending_tx.tx_hash(),
+                nonce,
+                from
+            ),
+            Err(e) => error!("Failed to cancel nonce {} of {}: {}", nonce, from, e),
+        }
+    }
+
+    /// Raise the fees of a transaction for its replacement
+    ///
+    /// Each fee goes up by the larger of `fee_increase_percent` and
+    /// `min_replacement_bump_percent`, so nodes accept the replacement.
+    fn apply_fee_bump(&self, tx: &mut N::TransactionRequest, attempt: u32)
+    where
+        N::TransactionRequest: TransactionBuilder<N>,
+    {
+        let fee_bumping = &self.network_config.gas_config.fee_bumping;
+        let bump_percent = fee_bumping
+            .fee_increase_percent
+            .max(fee_bumping.min_replacement_bump_percent);
+        let bump = |fee: u128| {
+            let previous = U256::from(fee);
+            (previous, gas_utils::replacement_fee(previous, bump_percent))
+        };
+
+        if let Some((previous, bumped)) = tx.gas_price().map(bump) {
+            tx.set_gas_price(bumped.to::<u128>());
+            TransactionLogger::log_fee_bump(attempt, previous, bumped);
+        }
+        if let Some((previous, bumped)) = tx.max_fee_per_gas().map(bump) {
+            tx.set_max_fee_per_gas(bumped.to::<u128>());
+            TransactionLogger::log_fee_bump(attempt, previous, bumped);
+        }
+        if let Some((_, bumped)) = tx.max_priority_fee_per_gas().map(bump) {
+            tx.set_max_priority_fee_per_gas(bumped.to::<u128>());
         }
     }
 }
@@ -355,6 +493,14 @@ where
             gas_config: Default::default(),
             gas_token: "ethereum".to_string(),
             gas_token_symbol: "ETH".to_string(),
+            batching: Default::default(),
+            confirmations: 1,
+            rpc_endpoints: Vec::new(),
+            rpc_failover: Default::default(),
+            read_quorum: Default::default(),
+            check_feeds_on_new_heads: false,
+            chain_id: None,
+            l2_type: "none".to_string(),
         };
 
         Self {
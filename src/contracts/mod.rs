@@ -1,17 +1,31 @@
 pub mod abi_utils;
+pub mod aggregator_v3;
+pub mod custom;
 pub mod flux_aggregator;
 pub mod flux_aggregator_v2;
 pub mod generic_caller;
 pub mod interaction;
+pub mod multicall;
+pub mod oracle;
+pub mod owner_settable;
+pub mod revert;
+pub mod submitter;
 
 pub use abi_utils::{
-    common_calls, encode_function_call, encode_parameter, encode_parameters,
-    parse_function_signature, ContractCallBuilder,
+    common_calls, create_function_definition_with_outputs, dyn_value_to_f64, dyn_value_to_i256,
+    encode_function_call, encode_parameter, encode_parameters, parse_function_signature,
+    ContractCallBuilder,
 };
+pub use aggregator_v3::AggregatorV3Contract;
+pub use custom::CustomContract;
 pub use flux_aggregator::FluxAggregatorContract;
 pub use flux_aggregator_v2::FluxAggregatorContractV2;
 pub use generic_caller::{create_contract_reader, MetricsAwareContractCaller};
 pub use interaction::{ContractInteraction, ContractReader};
+pub use oracle::{create_oracle_contract, Ineligible, OracleContract, RoundData, Submission};
+pub use owner_settable::OwnerSettableContract;
+pub use revert::{preflight, record_skipped_revert, RevertDecoder, WouldRevert};
+pub use submitter::CallSubmitter;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,146 @@
+//! Multicall3 `aggregate3` encoding for batched submissions
+//!
+//! Every call is sent with `allowFailure`, so one reverting submission doesn't
+//! revert the others in the batch.
+
+use alloy::{
+    primitives::{Address, Bytes},
+    sol,
+    sol_types::SolCall,
+};
+use anyhow::Result;
+
+sol! {
+    #[sol(rpc)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Result[] memory returnData);
+    }
+}
+
+/// Address of the canonical Multicall3 deployment, the same on most chains
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+/// A call in a batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchCall {
+    pub target: Address,
+    pub calldata: Bytes,
+}
+
+/// Result of a single call in a batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchCallResult {
+    pub success: bool,
+    /// Return data, or the revert data if the call failed
+    pub return_data: Bytes,
+}
+
+/// Encodes `aggregate3` with every call allowed to fail
+pub fn aggregate3_calldata(calls: &[BatchCall]) -> Bytes {
+    IMulticall3::aggregate3Call {
+        calls: calls
+            .iter()
+            .map(|call| IMulticall3::Call3 {
+                target: call.target,
+                allowFailure: true,
+                callData: call.calldata.clone(),
+            })
+            .collect(),
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Decodes the per-call results returned by `aggregate3`
+pub fn decode_aggregate3(data: &[u8]) -> Result<Vec<BatchCallResult>> {
+    let decoded = IMulticall3::aggregate3Call::abi_decode_returns(data, true)?;
+    Ok(decoded
+        .returnData
+        .into_iter()
+        .map(|result| BatchCallResult {
+            success: result.success,
+            return_data: result.returnData,
+        })
+        .collect())
+}
+
+/// Splits the gas used by a batch transaction evenly across its calls
+/// The remainder goes to the first calls, so the shares add up to `gas_used`
+pub fn split_gas(gas_used: u64, calls: usize) -> Vec<u64> {
+    if calls == 0 {
+        return Vec::new();
+    }
+
+    let share = gas_used / calls as u64;
+    let remainder = (gas_used % calls as u64) as usize;
+    (0..calls)
+        .map(|i| share + u64::from(i < remainder))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolValue;
+
+    #[test]
+    fn test_aggregate3_calldata() {
+        let calls = vec![
+            BatchCall {
+                target: Address::repeat_byte(1),
+                calldata: Bytes::from(vec![0xaa, 0xbb]),
+            },
+            BatchCall {
+                target: Address::repeat_byte(2),
+                calldata: Bytes::from(vec![0xcc]),
+            },
+        ];
+
+        let calldata = aggregate3_calldata(&calls);
+        let decoded = IMulticall3::aggregate3Call::abi_decode(&calldata, true).unwrap();
+        assert_eq!(decoded.calls.len(), 2);
+        assert!(decoded.calls.iter().all(|call| call.allowFailure));
+        assert_eq!(decoded.calls[1].target, Address::repeat_byte(2));
+        assert_eq!(decoded.calls[1].callData, Bytes::from(vec![0xcc]));
+    }
+
+    #[test]
+    fn test_decode_aggregate3() {
+        let encoded = vec![
+            IMulticall3::Result {
+                success: true,
+                returnData: Bytes::new(),
+            },
+            IMulticall3::Result {
+                success: false,
+                returnData: Bytes::from(vec![0x08, 0xc3, 0x79, 0xa0]),
+            },
+        ]
+        .abi_encode();
+
+        let results = decode_aggregate3(&encoded).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].success);
+        assert!(!results[1].success);
+        assert_eq!(results[1].return_data.len(), 4);
+    }
+
+    #[test]
+    fn test_split_gas() {
+        assert_eq!(split_gas(90_000, 3), vec![30_000, 30_000, 30_000]);
+        assert_eq!(split_gas(100_001, 3), vec![33_334, 33_334, 33_333]);
+        assert_eq!(split_gas(100_001, 3).iter().sum::<u64>(), 100_001);
+        assert!(split_gas(100_000, 0).is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+//! Contract types datafeeds can read from and submit to
+//!
+//! Every supported `contract_type` implements [`OracleContract`] and is
+//! registered in [`CONTRACT_TYPES`]. Sending the calldata an implementation
+//! builds is shared by all types, see [`super::submitter::CallSubmitter`].
+
+use super::aggregator_v3::AggregatorV3Contract;
+use super::custom::{validate_custom_abi, CustomContract};
+use super::flux_aggregator::FluxAggregatorContract;
+use super::owner_settable::{validate_setter_signature, OwnerSettableContract};
+use super::revert::RevertDecoder;
+use crate::config::models::Datafeed;
+use crate::metrics::SkipReason;
+use alloy::{
+    network::Ethereum,
+    primitives::{Address, Bytes, I256, U256},
+    providers::Provider,
+    transports::Transport,
+};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// FluxAggregator contracts, updated with `submit(roundId, answer)`
+pub const FLUXMON: &str = "fluxmon";
+/// Read-only AggregatorV3Interface feeds, e.g. third-party Chainlink feeds
+pub const AGGREGATOR_V3: &str = "aggregator_v3";
+/// Contracts updated by their owner through a configurable setter
+pub const OWNER_SETTABLE: &str = "owner_settable";
+/// Contracts whose submit and read functions are described by `custom_abi`
+pub const CUSTOM: &str = "custom";
+
+/// A registered contract type
+#[derive(Debug, Clone, Copy)]
+pub struct ContractType {
+    pub name: &'static str,
+    /// Whether values can be submitted to the contract
+    pub writable: bool,
+}
+
+/// Contract types supported by `contract_type`
+pub const CONTRACT_TYPES: &[ContractType] = &[
+    ContractType {
+        name: FLUXMON,
+        writable: true,
+    },
+    ContractType {
+        name: AGGREGATOR_V3,
+        writable: false,
+    },
+    ContractType {
+        name: OWNER_SETTABLE,
+        writable: true,
+    },
+    ContractType {
+        name: CUSTOM,
+        writable: true,
+    },
+];
+
+/// Looks up a registered contract type by name
+pub fn contract_type(name: &str) -> Option<&'static ContractType> {
+    CONTRACT_TYPES.iter().find(|t| t.name == name)
+}
+
+/// Returns true if values can be submitted to contracts of this type
+pub fn is_writable(name: &str) -> bool {
+    contract_type(name).is_some_and(|t| t.writable)
+}
+
+/// Configuration values read from an oracle contract
+#[derive(Debug, Clone)]
+pub struct ContractConfig {
+    pub decimals: u8,
+    /// Minimum submission value, if the contract enforces one
+    pub min_value: Option<I256>,
+    /// Maximum submission value, if the contract enforces one
+    pub max_value: Option<I256>,
+}
+
+/// The latest answer of an oracle contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundData {
+    pub round_id: U256,
+    pub answer: I256,
+    /// Unix time of the last update
+    pub updated_at: u64,
+}
+
+/// Calldata submitting a value, with the round it is submitted to if the contract has rounds
+#[derive(Debug, Clone)]
+pub struct Submission {
+    pub calldata: Bytes,
+    pub round_id: Option<U256>,
+}
+
+/// Why an oracle can't submit to a contract right now
+///
+/// Submitting anyway would revert, so the update is skipped instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum Ineligible {
+    #[error("the wallet is not an oracle of the contract")]
+    NotOracle,
+    #[error("already submitted to round {0}")]
+    RoundAlreadySubmitted(u32),
+    #[error("the restart delay since the last round started by this oracle has not passed")]
+    RestartDelay,
+    #[error("available funds {available} don't cover the oracle payment {payment}")]
+    InsufficientFunds { available: u128, payment: u128 },
+}
+
+impl Ineligible {
+    /// The skip reason recorded in the update decision metrics
+    pub fn skip_reason(&self) -> SkipReason {
+        match self {
+            Ineligible::NotOracle => SkipReason::NotOracle,
+            Ineligible::RoundAlreadySubmitted(_) => SkipReason::RoundAlreadySubmitted,
+            Ineligible::RestartDelay => SkipReason::RestartDelay,
+            Ineligible::InsufficientFunds { .. } => SkipReason::InsufficientFunds,
+        }
+    }
+}
+
+/// Reading from and submitting to an oracle contract
+#[async_trait]
+pub trait OracleContract: Send + Sync {
+    /// Address of the contract
+    fn address(&self) -> Address;
+
+    /// Reads the latest answer and when it was reported
+    async fn latest_round_data(&self) -> Result<RoundData>;
+
+    /// Reads the decimals and submission bounds
+    async fn read_config(&self) -> Result<ContractConfig>;
+
+    /// Builds the calldata submitting `answer`, which is already scaled by the decimals
+    ///
+    /// With the submitting `oracle` known, contracts with per-oracle rounds submit to
+    /// the round it is eligible for and fail with [`Ineligible`] if there is none.
+    async fn submission(&self, answer: I256, oracle: Option<Address>) -> Result<Submission>;
+
+    /// Checks whether `oracle` may submit now, returns None if it may
+    ///
+    /// Contracts without per-oracle rules always accept submissions.
+    async fn check_eligibility(&self, _oracle: Address) -> Result<Option<Ineligible>> {
+        Ok(None)
+    }
+
+    /// Payment `oracle` can withdraw, None if the contract doesn't pay oracles
+    async fn withdrawable_payment(&self, _oracle: Address) -> Result<Option<U256>> {
+        Ok(None)
+    }
+}
+
+/// Creates the contract implementation for the datafeed's `contract_type`
+pub fn create_oracle_contract<T, P>(
+    datafeed: &Datafeed,
+    address: Address,
+    provider: P,
+) -> Result<Box<dyn OracleContract>>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum> + Clone + 'static,
+{
+    let contract: Box<dyn OracleContract> = match datafeed.contract_type.as_str() {
+        FLUXMON => Box::new(FluxAggregatorContract::new(address, provider)),
+        AGGREGATOR_V3 => Box::new(AggregatorV3Contract::new(address, provider)),
+        OWNER_SETTABLE => {
+            let setter = datafeed
+                .setter_signature
+                .as_deref()
+                .context("owner_settable contracts require setter_signature")?;
+            Box::new(OwnerSettableContract::new(address, provider, setter)?)
+        }
+        CUSTOM => {
+            let abi = datafeed
+                .custom_abi
+                .clone()
+                .context("custom contracts require custom_abi")?;
+            Box::new(CustomContract::new(address, provider, abi))
+        }
+        other => anyhow::bail!("Unsupported contract type: {other}"),
+    };
+
+    Ok(contract)
+}
+
+/// Checks the contract settings of a single-network datafeed
+pub fn validate(datafeed: &Datafeed) -> Result<()> {
+    if contract_type(&datafeed.contract_type).is_none() {
+        anyhow::bail!("unsupported contract type '{}'", datafeed.contract_type);
+    }
+
+    if datafeed.contract_type == OWNER_SETTABLE {
+        let setter = datafeed
+            .setter_signature
+            .as_deref()
+            .context("owner_settable contracts require setter_signature")?;
+        validate_setter_signature(setter)?;
+    }
+
+    if datafeed.contract_type == CUSTOM {
+        let abi = datafeed
+            .custom_abi
+            .as_ref()
+            .context("custom contracts require custom_abi")?;
+        validate_custom_abi(abi, datafeed.read_contract_config)?;
+    }
+
+    RevertDecoder::new(&datafeed.custom_errors)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_contract_types() {
+        assert!(is_writable(FLUXMON));
+        assert!(is_writable(OWNER_SETTABLE));
+        assert!(is_writable(CUSTOM));
+        assert!(!is_writable(AGGREGATOR_V3));
+        assert!(!is_writable("unknown"));
+        assert!(contract_type("unknown").is_none());
+    }
+}
@@ -0,0 +1,152 @@
+//! Simple oracle contracts whose owner sets the answer directly
+//!
+//! The setter is configured with `setter_signature`. It takes the scaled value
+//! as its first argument and may take the current Unix time as a second one,
+//! e.g. `setAnswer(int256)` or `updateAnswer(int256,uint256)`. The answer is
+//! read back through the AggregatorV3Interface.
+
+use super::abi_utils::{encode_function_call, parse_function_signature};
+use super::aggregator_v3::AggregatorV3Contract;
+use super::oracle::{ContractConfig, OracleContract, RoundData, Submission};
+use alloy::{
+    json_abi::StateMutability,
+    network::Ethereum,
+    primitives::{Address, Bytes, I256},
+    providers::Provider,
+    transports::Transport,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Checks that a setter signature takes a value and optionally a timestamp
+/// Returns the number of parameters
+pub fn validate_setter_signature(signature: &str) -> Result<usize> {
+    let (name, param_types) = parse_function_signature(signature)?;
+    if name.is_empty() {
+        anyhow::bail!("setter signature '{signature}' has no function name");
+    }
+
+    match param_types.as_slice() {
+        [value] if is_integer(value) => Ok(1),
+        [value, timestamp] if is_integer(value) && timestamp.starts_with("uint") => Ok(2),
+        _ => anyhow::bail!(
+            "setter signature '{signature}' must take the value (int or uint) and optionally a uint timestamp"
+        ),
+    }
+}
+
+fn is_integer(param_type: &str) -> bool {
+    param_type.starts_with("int") || param_type.starts_with("uint")
+}
+
+/// Wrapper for owner-settable oracle contracts
+pub struct OwnerSettableContract<T: Transport + Clone, P: Provider<T, Ethereum>> {
+    reader: AggregatorV3Contract<T, P>,
+    address: Address,
+    setter_signature: String,
+    with_timestamp: bool,
+}
+
+impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> OwnerSettableContract<T, P> {
+    /// Create a new owner-settable contract instance
+    pub fn new(address: Address, provider: P, setter_signature: &str) -> Result<Self> {
+        let param_count = validate_setter_signature(setter_signature)?;
+
+        Ok(Self {
+            reader: AggregatorV3Contract::new(address, provider),
+            address,
+            setter_signature: setter_signature.to_string(),
+            with_timestamp: param_count == 2,
+        })
+    }
+
+    /// Encodes the setter call for a scaled value
+    pub fn setter_calldata(&self, answer: I256, timestamp: u64) -> Result<Bytes> {
+        let mut params = vec![answer.to_string()];
+        if self.with_timestamp {
+            params.push(timestamp.to_string());
+        }
+
+        encode_function_call(&self.setter_signature, &params, StateMutability::NonPayable)
+    }
+}
+
+#[async_trait]
+impl<T, P> OracleContract for OwnerSettableContract<T, P>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum> + Clone + 'static,
+{
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn latest_round_data(&self) -> Result<RoundData> {
+        self.reader.latest_round_data().await
+    }
+
+    async fn read_config(&self) -> Result<ContractConfig> {
+        Ok(ContractConfig {
+            decimals: self.reader.decimals().await?,
+            min_value: None,
+            max_value: None,
+        })
+    }
+
+    async fn submission(&self, answer: I256, _oracle: Option<Address>) -> Result<Submission> {
+        let timestamp = crate::datafeed::contract_utils::current_timestamp()?;
+
+        Ok(Submission {
+            calldata: self.setter_calldata(answer, timestamp)?,
+            round_id: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::{ProviderBuilder, RootProvider};
+    use alloy::transports::http::{Client, Http};
+
+    fn contract(
+        setter: &str,
+    ) -> Result<OwnerSettableContract<Http<Client>, RootProvider<Http<Client>>>> {
+        let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse().unwrap());
+        OwnerSettableContract::new(Address::ZERO, provider, setter)
+    }
+
+    #[test]
+    fn test_validate_setter_signature() {
+        assert_eq!(validate_setter_signature("setAnswer(int256)").unwrap(), 1);
+        assert_eq!(
+            validate_setter_signature("updateAnswer(int256,uint256)").unwrap(),
+            2
+        );
+        assert!(validate_setter_signature("setAnswer()").is_err());
+        assert!(validate_setter_signature("setAnswer(string)").is_err());
+        assert!(validate_setter_signature("setAnswer(int256,int256)").is_err());
+        assert!(validate_setter_signature("setAnswer(int256,uint256,uint256)").is_err());
+    }
+
+    #[test]
+    fn test_setter_calldata() {
+        let answer = I256::try_from(250_000_000_000i64).unwrap();
+
+        let calldata = contract("setAnswer(int256)")
+            .unwrap()
+            .setter_calldata(answer, 1_700_000_000)
+            .unwrap();
+        assert_eq!(calldata.len(), 4 + 32);
+
+        let calldata = contract("updateAnswer(int256,uint256)")
+            .unwrap()
+            .setter_calldata(answer, 1_700_000_000)
+            .unwrap();
+        assert_eq!(calldata.len(), 4 + 64);
+        assert_eq!(
+            alloy::primitives::U256::from_be_slice(&calldata[36..68]),
+            alloy::primitives::U256::from(1_700_000_000u64)
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Pre-flight simulation of transactions and revert reason decoding
+//!
+//! Transactions are simulated with `eth_call` at the pending block before they
+//! are broadcast. If the call would revert the send is skipped, so a failing
+//! submission doesn't cost gas, and the decoded reason is recorded instead.
+
+use crate::database::TransactionLogRepository;
+use crate::metrics::ContractMetrics;
+use alloy::{
+    dyn_abi::{DynSolValue, JsonAbiExt},
+    hex,
+    json_abi::Error as AbiError,
+    network::Network,
+    primitives::Bytes,
+    providers::Provider,
+    rpc::{json_rpc::ErrorPayload, types::BlockId},
+    sol_types::{Panic, Revert, SolError},
+    transports::Transport,
+};
+use anyhow::{Context, Result};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::error;
+
+/// A transaction that was not sent because it would revert
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("transaction would revert: {reason}")]
+pub struct WouldRevert {
+    pub reason: String,
+}
+
+/// Decodes revert data into a readable reason
+///
+/// `Error(string)` and `Panic(uint256)` are always decoded; custom errors only
+/// if their signatures are configured, e.g. `RoundNotOpen(uint32)`.
+#[derive(Debug, Clone, Default)]
+pub struct RevertDecoder {
+    errors: Vec<AbiError>,
+}
+
+impl RevertDecoder {
+    /// Creates a decoder for the given custom error signatures
+    pub fn new(signatures: &[String]) -> Result<Self> {
+        let errors = signatures
+            .iter()
+            .map(|signature| {
+                AbiError::parse(signature)
+                    .with_context(|| format!("invalid custom error signature '{signature}'"))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { errors })
+    }
+
+    /// Returns the revert reason encoded in `data`
+    pub fn decode(&self, data: &[u8]) -> String {
+        if data.is_empty() {
+            return "reverted without a reason".to_string();
+        }
+        if let Ok(revert) = Revert::abi_decode(data, true) {
+            return revert.to_string();
+        }
+        if let Ok(panic) = Panic::abi_decode(data, true) {
+            return panic.to_string();
+        }
+
+        if data.len() < 4 {
+            return format!("malformed revert data 0x{}", hex::encode(data));
+        }
+        let (selector, params) = data.split_at(4);
+        self.errors
+            .iter()
+            .filter(|error| error.selector().as_slice() == selector)
+            .find_map(|error| {
+                let values = error.abi_decode_input(params, true).ok()?;
+                let values: Vec<String> = values.iter().map(format_value).collect();
+                Some(format!("{}({})", error.name, values.join(", ")))
+            })
+            .unwrap_or_else(|| format!("custom error 0x{}", hex::encode(data)))
+    }
+}
+
+/// Formats a decoded custom error argument
+fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::Address(a) => a.to_checksum(None),
+        DynSolValue::String(s) => format!("{s:?}"),
+        DynSolValue::Bytes(b) => format!("0x{}", hex::encode(b)),
+        DynSolValue::FixedBytes(b, size) => format!("0x{}", hex::encode(&b[..*size])),
+        DynSolValue::Array(values)
+        | DynSolValue::FixedArray(values)
+        | DynSolValue::Tuple(values) => {
+            let values: Vec<String> = values.iter().map(format_value).collect();
+            format!("[{}]", values.join(", "))
+        }
+        other => format!("{other:?}"),
+    }
+}
+
+/// Simulates a transaction with `eth_call` at the pending block
+///
+/// Returns the decoded reason if the call would revert. Transport errors and
+/// error responses that aren't reverts, like rate limits or unsupported
+/// methods, are returned as errors, since they say nothing about the
+/// transaction.
+pub async fn preflight<T, N, P>(
+    provider: &P,
+    tx: &N::TransactionRequest,
+    decoder: &RevertDecoder,
+) -> Result<Option<WouldRevert>>
+where
+    T: Transport + Clone,
+    N: Network,
+    P: Provider<T, N>,
+{
+    let error = match provider.call(tx).block(BlockId::pending()).await {
+        Ok(_) => return Ok(None),
+        Err(error) => error,
+    };
+
+    match error
+        .as_error_resp()
+        .and_then(|payload| revert_reason(payload, decoder))
+    {
+        Some(reason) => Ok(Some(WouldRevert { reason })),
+        None => Err(anyhow::Error::new(error).context("Pre-flight simulation failed")),
+    }
+}
+
+/// Returns the revert reason of an error response, or `None` if it isn't a revert
+///
+/// A response is a revert if it carries revert data, has the code 3 nodes
+/// use for reverts, or says "execution reverted".
+fn revert_reason(payload: &ErrorPayload, decoder: &RevertDecoder) -> Option<String> {
+    let data = payload.as_revert_data().or_else(|| {
+        let data = payload.data.as_ref()?;
+        serde_json::from_str::<Bytes>(data.get()).ok()
+    });
+    if let Some(data) = data.filter(|data| !data.is_empty()) {
+        return Some(decoder.decode(&data));
+    }
+
+    let is_revert = payload.code == 3
+        || payload
+            .message
+            .to_lowercase()
+            .contains("execution reverted");
+    is_revert.then(|| payload.message.to_string())
+}
+
+/// Records a send skipped because the transaction would revert
+///
+/// The reason goes to the revert metrics and, with a repository, to the
+/// transaction log as a `reverted` row without a transaction hash.
+pub async fn record_skipped_revert(
+    name: &str,
+    network: &str,
+    tx_type: &str,
+    tx_log_repo: Option<&Arc<TransactionLogRepository>>,
+    reason: &str,
+) {
+    ContractMetrics::record_transaction_revert(name, network, reason);
+
+    if let Some(repo) = tx_log_repo {
+        if let Err(e) = repo
+            .save_skipped_revert(name, network, tx_type, reason)
+            .await
+        {
+            error!("Failed to log skipped transaction for {}: {}", name, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Address, U256};
+    use serde_json::value::RawValue;
+
+    fn payload(code: i64, message: &'static str, data: Option<&str>) -> ErrorPayload {
+        ErrorPayload {
+            code,
+            message: message.into(),
+            data: data.map(|data| RawValue::from_string(data.to_string()).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_decode_error_string() {
+        let data = Revert::from("No new round").abi_encode();
+        assert_eq!(
+            RevertDecoder::default().decode(&data),
+            "revert: No new round"
+        );
+    }
+
+    #[test]
+    fn test_decode_panic() {
+        let data = Panic::from(0x11).abi_encode();
+        let reason = RevertDecoder::default().decode(&data);
+        assert!(reason.starts_with("panic: "));
+        assert!(reason.ends_with("(0x11)"));
+    }
+
+    #[test]
+    fn test_decode_custom_error() {
+        let decoder = RevertDecoder::new(&[
+            "RoundNotOpen(uint32)".to_string(),
+            "NotOracle(address)".to_string(),
+        ])
+        .unwrap();
+
+        let error = AbiError::parse("NotOracle(address)").unwrap();
+        let oracle = Address::repeat_byte(0x11);
+        let data = error
+            .abi_encode_input(&[DynSolValue::Address(oracle)])
+            .unwrap();
+        assert_eq!(
+            decoder.decode(&data),
+            format!("NotOracle({})", oracle.to_checksum(None))
+        );
+
+        let error = AbiError::parse("RoundNotOpen(uint32)").unwrap();
+        let data = error
+            .abi_encode_input(&[DynSolValue::Uint(U256::from(7), 32)])
+            .unwrap();
+        assert_eq!(decoder.decode(&data), "RoundNotOpen(7)");
+
+        // Not configured
+        assert_eq!(
+            RevertDecoder::default().decode(&data),
+            format!("custom error 0x{}", hex::encode(&data))
+        );
+    }
+
+    #[test]
+    fn test_decode_empty_and_invalid() {
+        let decoder = RevertDecoder::default();
+        assert_eq!(decoder.decode(&[]), "reverted without a reason");
+        assert_eq!(
+            decoder.decode(&[0xab, 0xcd]),
+            "malformed revert data 0xabcd"
+        );
+    }
+
+    #[test]
+    fn test_invalid_custom_error_signature() {
+        assert!(RevertDecoder::new(&["NotAnError(".to_string()]).is_err());
+        assert!(RevertDecoder::new(&["error Stale(uint256 round)".to_string()]).is_ok());
+    }
+
+    #[test]
+    fn test_revert_reason_from_error_response() {
+        let decoder = RevertDecoder::default();
+        let data = format!(
+            "\"0x{}\"",
+            hex::encode(Revert::from("No new round").abi_encode())
+        );
+
+        let reverted = payload(3, "execution reverted: No new round", Some(&data));
+        assert_eq!(
+            revert_reason(&reverted, &decoder).as_deref(),
+            Some("revert: No new round")
+        );
+
+        let without_data = payload(-32000, "execution reverted", None);
+        assert_eq!(
+            revert_reason(&without_data, &decoder).as_deref(),
+            Some("execution reverted")
+        );
+    }
+
+    #[test]
+    fn test_non_revert_error_response_is_not_a_revert() {
+        let decoder = RevertDecoder::default();
+
+        let rate_limited = payload(-32005, "rate limit exceeded", None);
+        assert_eq!(revert_reason(&rate_limited, &decoder), None);
+
+        let unsupported = payload(-32601, "the method eth_call does not exist", Some("null"));
+        assert_eq!(revert_reason(&unsupported, &decoder), None);
+    }
+}
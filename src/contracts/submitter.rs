@@ -0,0 +1,790 @@
+//! Sending oracle submissions
+//!
+//! Every contract type builds its own calldata; pre-flight simulation,
+//! estimation, nonces, fee bumping, metrics and transaction logging are
+//! shared here.
+
+use super::multicall::split_gas;
+use super::revert::{preflight, record_skipped_revert, RevertDecoder};
+use crate::config::models::Network as NetworkConfig;
+use crate::database::TransactionLogRepository;
+use crate::gas::{fetch_l1_fee, GasEstimate, L1Fee};
+use crate::metrics::gas_metrics::{GasMetrics, TransactionDetails};
+use crate::metrics::ContractMetrics;
+use crate::network::{
+    cancellation, wait_for_confirmations, Confirmation, NonceManager, TrackedOwner,
+    TransactionTracker,
+};
+use alloy::{
+    network::{Ethereum, TransactionBuilder},
+    primitives::{Address, Bytes, B256, U256},
+    providers::{PendingTransactionBuilder, Provider},
+    rpc::types::{TransactionReceipt, TransactionRequest},
+    transports::Transport,
+};
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::time::Duration;
+use tracing::{error, info, warn};
+
+/// A feed's share of a transaction
+#[derive(Debug, Clone, Copy)]
+struct GasShare<'f> {
+    feed_name: &'f str,
+    gas_used: u64,
+    gas_limit: u64,
+    l1_fee: L1Fee,
+}
+
+/// Sends calls to a contract
+pub struct CallSubmitter<T: Transport + Clone, P: Provider<T, Ethereum>> {
+    address: Address,
+    provider: P,
+    /// Feeds sharing the transaction's gas, empty if it belongs to one feed
+    shared_by: Vec<String>,
+    revert_decoder: RevertDecoder,
+    nonce_manager: Option<Arc<NonceManager>>,
+    tracker: Option<Arc<TransactionTracker>>,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: Transport + Clone, P: Provider<T, Ethereum> + Clone> CallSubmitter<T, P> {
+    /// Create a submitter for the contract at `address`
+    pub fn new(address: Address, provider: P) -> Self {
+        Self {
+            address,
+            provider,
+            shared_by: Vec::new(),
+            revert_decoder: RevertDecoder::default(),
+            nonce_manager: None,
+            tracker: None,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Attributes the gas of the sent transaction to these feeds in equal shares
+    ///
+    /// Used for batches; the gas metrics and transaction log rows are then
+    /// recorded per feed instead of under the submitter's `feed_name`.
+    pub fn with_shared_by(mut self, feed_names: Vec<String>) -> Self {
+        self.shared_by = feed_names;
+        self
+    }
+
+    /// Decodes the reverts of the contract's custom errors
+    pub fn with_revert_decoder(mut self, revert_decoder: RevertDecoder) -> Self {
+        self.revert_decoder = revert_decoder;
+        self
+    }
+
+    /// Takes the nonces from the manager shared by all senders of the wallet
+    ///
+    /// Without one, the provider's filler asks the node for every transaction.
+    pub fn with_nonce_manager(mut self, nonce_manager: Arc<NonceManager>) -> Self {
+        self.nonce_manager = Some(nonce_manager);
+        self
+    }
+
+    /// Persists the transactions sent with a managed nonce until they resolve
+    ///
+    /// They are then signed locally and broadcast raw, and keep being watched
+    /// after the submitter gives up on them.
+    pub fn with_tracker(mut self, tracker: Arc<TransactionTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Build, estimate and simulate a call with `eth_call` without broadcasting it
+    /// Returns the gas estimate and the revert reason if the call would fail
+    pub async fn simulate(
+        &self,
+        calldata: Bytes,
+        network_config: &NetworkConfig,
+        from_address: Option<Address>,
+    ) -> Result<(GasEstimate, Option<String>)> {
+        let mut tx = TransactionRequest::default()
+            .to(self.address)
+            .input(calldata.into());
+
+        if let Some(from) = from_address {
+            tx = tx.from(from);
+        }
+
+        let gas_estimator = crate::gas::GasEstimator::<T, P>::new(
+            Arc::new(self.provider.clone()),
+            network_config.clone(),
+        );
+        let gas_estimate = gas_estimator.estimate_gas(&tx).await?;
+        tx = tx.with_gas_limit(gas_estimate.gas_limit.to::<u64>());
+
+        let simulation_error = preflight(&self.provider, &tx, &self.revert_decoder)
+            .await?
+            .map(|revert| revert.reason);
+
+        Ok((gas_estimate, simulation_error))
+    }
+
+    /// Send a call to the contract with gas estimation and retry logic
+    pub async fn submit_with_gas_estimation(
+        &self,
+        calldata: Bytes,
+        network_config: &NetworkConfig,
+        feed_name: &str,
+        tx_log_repo: Option<Arc<TransactionLogRepository>>,
+        from_address: Option<Address>,
+    ) -> Result<TransactionReceipt> {
+        let gas_config = &network_config.gas_config;
+        let fee_bumping = &gas_config.fee_bumping;
+
+        // Build base transaction request
+        let mut tx = TransactionRequest::default()
+            .to(self.address)
+            .input(calldata.into());
+
+        // Set from address if provided (needed for accurate gas estimation)
+        if let Some(from) = from_address {
+            tx = tx.from(from);
+        }
+
+        // Skip the send if the call would revert at the pending block
+        if let Some(revert) = preflight(&self.provider, &tx, &self.revert_decoder).await? {
+            warn!(
+                "Not sending transaction for {} on {}, {}",
+                feed_name, network_config.name, revert
+            );
+            for feed in self.feeds(feed_name) {
+                record_skipped_revert(
+                    feed,
+                    &network_config.name,
+                    &network_config.transaction_type,
+                    tx_log_repo.as_ref(),
+                    &revert.reason,
+                )
+                .await;
+            }
+            return Err(revert.into());
+        }
+
+        // Estimate gas
+        let gas_estimator = crate::gas::GasEstimator::<T, P>::new(
+            Arc::new(self.provider.clone()),
+            network_config.clone(),
+        );
+        let mut gas_estimate = gas_estimator.estimate_gas(&tx).await?;
+
+        let mut attempt = 0;
+        let mut nonce = None;
+        // Transactions sent with the current nonce, any of them may still be mined
+        let mut sent: Vec<B256> = Vec::new();
+        let mut write_start;
+        let mut submission_time;
+        let max_attempts = if fee_bumping.enabled {
+            fee_bumping.max_retries + 1
+        } else {
+            1
+        };
+
+        let receipt = loop {
+            attempt += 1;
+
+            // Apply gas settings
+            tx = tx.with_gas_limit(gas_estimate.gas_limit.to::<u64>());
+
+            // Apply fee settings based on transaction type
+            match network_config.transaction_type.to_lowercase().as_str() {
+                "legacy" => {
+                    if let Some(gas_price) = gas_estimate.gas_price {
+                        tx = tx.with_gas_price(gas_price.to::<u128>());
+                    }
+                }
+                "eip1559" => {
+                    if let Some(max_fee) = gas_estimate.max_fee_per_gas {
+                        tx = tx.with_max_fee_per_gas(max_fee.to::<u128>());
+                    }
+                    if let Some(priority_fee) = gas_estimate.max_priority_fee_per_gas {
+                        tx = tx.with_max_priority_fee_per_gas(priority_fee.to::<u128>());
+                    }
+                }
+                _ => {
+                    warn!("Unknown transaction type, defaulting to EIP-1559");
+                    if let Some(max_fee) = gas_estimate.max_fee_per_gas {
+                        tx = tx.with_max_fee_per_gas(max_fee.to::<u128>());
+                    }
+                    if let Some(priority_fee) = gas_estimate.max_priority_fee_per_gas {
+                        tx = tx.with_max_priority_fee_per_gas(priority_fee.to::<u128>());
+                    }
+                }
+            }
+
+            // A transaction that timed out keeps its nonce, so the retry replaces it
+            if nonce.is_none() {
+                nonce = self.next_nonce(&network_config.name, from_address).await?;
+                sent.clear();
+            }
+            if let Some(nonce) = nonce {
+                tx = tx.with_nonce(nonce);
+            }
+
+            info!("Sending transaction (attempt {})", attempt);
+
+            // Record contract write attempt
+            write_start = Instant::now();
+
+            // Record transaction submission time
+            submission_time = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+
+            // Send transaction
+            let pending_tx = match self
+                .send(tx.clone(), &network_config.name, feed_name, attempt)
+                .await
+            {
+                Ok(tx) => tx,
+                Err(e) => {
+                    let write_duration = write_start.elapsed();
+                    error!("Failed to send transaction: {}", e);
+
+                    // A replacement is rejected once the transaction it replaces
+                    // is mined, which must not be sent again with a new nonce
+                    if let Some(receipt) = self.find_mined(&sent).await {
+                        break receipt;
+                    }
+                    if self.nonce_used(from_address, nonce, &sent).await {
+                        self.release_nonce(&network_config.name, from_address, nonce.take())
+                            .await;
+                        return Err(anyhow::anyhow!(
+                            "Nonce of the transaction was used while sending its replacement: {}",
+                            e
+                        ));
+                    }
+
+                    self.release_nonce(&network_config.name, from_address, nonce.take())
+                        .await;
+
+                    // Record failed contract write
+                    ContractMetrics::record_contract_write(
+                        feed_name,
+                        &network_config.name,
+                        false,
+                        write_duration,
+                        None,
+                    );
+
+                    // Check for specific error types
+                    let error_str = e.to_string();
+                    if error_str.contains("revert") {
+                        ContractMetrics::record_transaction_revert(
+                            feed_name,
+                            &network_config.name,
+                            &error_str,
+                        );
+                    }
+
+                    if attempt >= max_attempts {
+                        ContractMetrics::record_transaction_retry(
+                            feed_name,
+                            &network_config.name,
+                            "max_attempts_reached",
+                            attempt as u32,
+                        );
+                        return Err(anyhow::anyhow!(
+                            "Failed to send transaction after {} attempts: {}",
+                            attempt as u32,
+                            e
+                        ));
+                    }
+
+                    ContractMetrics::record_transaction_retry(
+                        feed_name,
+                        &network_config.name,
+                        "send_error",
+                        attempt as u32,
+                    );
+                    continue;
+                }
+            };
+
+            let tx_hash = *pending_tx.tx_hash();
+            sent.push(tx_hash);
+            info!("Transaction sent: 0x{:x}", tx_hash);
+
+            // Wait for confirmation with timeout
+            let wait_duration = Duration::from_secs(fee_bumping.initial_wait_seconds);
+
+            match tokio::time::timeout(
+                wait_duration,
+                pending_tx.with_required_confirmations(1).get_receipt(),
+            )
+            .await
+            {
+                Ok(Ok(receipt)) => break receipt,
+                Ok(Err(e)) => {
+                    error!("Transaction failed: {}", e);
+                    if let Some(receipt) = self.find_mined(&sent).await {
+                        break receipt;
+                    }
+                    self.release_nonce(&network_config.name, from_address, nonce.take())
+                        .await;
+
+                    // Record failed transaction
+                    GasMetrics::record_failed_transaction(
+                        feed_name,
+                        &network_config.name,
+                        gas_estimate.gas_limit,
+                        gas_estimate.gas_price.or(gas_estimate.max_fee_per_gas),
+                        &network_config.transaction_type,
+                        &e.to_string(),
+                    );
+
+                    if attempt >= max_attempts {
+                        return Err(anyhow::anyhow!(
+                            "Transaction failed after {} attempts: {}",
+                            attempt as u32,
+                            e
+                        ));
+                    }
+                }
+                Err(_) => {
+                    warn!(
+                        "Transaction timed out after {} seconds: 0x{:x}",
+                        wait_duration.as_secs(),
+                        tx_hash
+                    );
+                    // One of the transactions sent may have been mined just now
+                    if let Some(receipt) = self.find_mined(&sent).await {
+                        break receipt;
+                    }
+                    if attempt >= max_attempts {
+                        if fee_bumping.enabled && fee_bumping.cancel_after_max_retries {
+                            self.cancel(
+                                &network_config.name,
+                                feed_name,
+                                from_address,
+                                nonce,
+                                &gas_estimator.bump_for_replacement(&gas_estimate),
+                                attempt + 1,
+                            )
+                            .await;
+                        }
+                        self.release_nonce(&network_config.name, from_address, nonce.take())
+                            .await;
+                        return Err(anyhow::anyhow!(
+                            "Transaction timed out after {} attempts",
+                            attempt
+                        ));
+                    }
+                }
+            }
+
+            // Bump fees for the replacement, by at least what nodes require
+            if fee_bumping.enabled && attempt < max_attempts {
+                gas_estimate = gas_estimator.bump_for_replacement(&gas_estimate);
+                info!("Bumping fees for retry attempt {}", attempt + 1);
+            }
+        };
+
+        // Final once buried at the network's confirmation depth
+        let receipt = match self
+            .bury(
+                receipt,
+                feed_name,
+                network_config,
+                &gas_estimate,
+                &tx_log_repo,
+            )
+            .await
+        {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                self.release_nonce(&network_config.name, from_address, nonce.take())
+                    .await;
+                return Err(e);
+            }
+        };
+
+        let tx_hash = receipt.transaction_hash;
+        let write_duration = write_start.elapsed();
+        info!("Transaction confirmed: 0x{:x}", tx_hash);
+        self.confirm_nonce(&network_config.name, from_address, nonce, &receipt)
+            .await;
+
+        // Record successful contract write
+        ContractMetrics::record_contract_write(
+            feed_name,
+            &network_config.name,
+            true,
+            write_duration,
+            Some(&format!("0x{tx_hash:x}")),
+        );
+
+        // Record confirmation time
+        let confirmation_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        ContractMetrics::record_confirmation_time(
+            feed_name,
+            &network_config.name,
+            submission_time,
+            confirmation_time,
+        );
+
+        // The L1 fee of rollups is part of the transaction's cost
+        let l1_fee = fetch_l1_fee(
+            &network_config.name,
+            &network_config.l2_type,
+            &self.provider,
+            receipt.transaction_hash,
+            receipt.effective_gas_price,
+        )
+        .await;
+
+        // Record gas metrics
+        if self.shared_by.is_empty() {
+            GasMetrics::record_transaction(
+                feed_name,
+                &network_config.name,
+                &receipt,
+                gas_estimate.gas_limit,
+                &network_config.transaction_type,
+                l1_fee,
+            );
+        } else {
+            GasMetrics::record_shared_transaction(
+                &self.shared_by,
+                &network_config.name,
+                &receipt,
+                gas_estimate.gas_limit,
+                &network_config.transaction_type,
+                l1_fee,
+            );
+        }
+
+        // Log transaction if repository is available
+        self.log_receipt(
+            tx_log_repo.as_ref(),
+            network_config,
+            &receipt,
+            &self.gas_shares(feed_name, &receipt, &gas_estimate, l1_fee),
+            &gas_estimate,
+            true,
+        )
+        .await;
+
+        Ok(receipt)
+    }
+
+    /// Returns the receipt of a transaction already sent with the nonce, if one was mined
+    async fn find_mined(&self, sent: &[B256]) -> Option<TransactionReceipt> {
+        for tx_hash in sent {
+            match self.provider.get_transaction_receipt(*tx_hash).await {
+                Ok(Some(receipt)) => {
+                    info!("Transaction 0x{:x} was mined", tx_hash);
+                    return Some(receipt);
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to get the receipt of 0x{:x}: {}", tx_hash, e),
+            }
+        }
+
+        None
+    }
+
+    /// Returns true if the sender's nonce was used after transactions were sent with it
+    ///
+    /// One of them may have been mined without its receipt being available yet.
+    async fn nonce_used(&self, from: Option<Address>, nonce: Option<u64>, sent: &[B256]) -> bool {
+        let (Some(from), Some(nonce)) = (from, nonce) else {
+            return false;
+        };
+        if sent.is_empty() {
+            return false;
+        }
+
+        match self.provider.get_transaction_count(from).latest().await {
+            Ok(mined_nonce) => mined_nonce > nonce,
+            Err(e) => {
+                warn!("Failed to get the nonce of {}: {}", from, e);
+                false
+            }
+        }
+    }
+
+    /// Sends the transaction, through the tracker if it has a managed nonce
+    async fn send(
+        &self,
+        tx: TransactionRequest,
+        network: &str,
+        feed_name: &str,
+        attempt: u8,
+    ) -> Result<PendingTransactionBuilder<T, Ethereum>> {
+        match &self.tracker {
+            Some(tracker) if tx.nonce.is_some() && tx.from.is_some() => {
+                let owner = TrackedOwner::datafeeds(
+                    self.feeds(feed_name)
+                        .into_iter()
+                        .map(String::from)
+                        .collect(),
+                );
+                tracker
+                    .broadcast(&self.provider, network, tx, &owner, attempt as u32)
+                    .await
+            }
+            _ => Ok(self.provider.send_transaction(tx).await?),
+        }
+    }
+
+    /// Replaces the stuck transaction of a nonce with a zero-value self-transfer
+    async fn cancel(
+        &self,
+        network: &str,
+        feed_name: &str,
+        from: Option<Address>,
+        nonce: Option<u64>,
+        fees: &GasEstimate,
+        attempt: u8,
+    ) {
+        let (Some(from), Some(nonce)) = (from, nonce) else {
+            warn!("Cannot cancel the stuck transaction of {feed_name} without a managed nonce");
+            return;
+        };
+
+        match self
+            .send(cancellation(from, nonce, fees), network, feed_name, attempt)
+            .await
+        {
+            Ok(sent) => info!(
+                "Sent cancellation 0x{:x} for nonce {} of {} on {}",
+                sent.tx_hash(),
+                nonce,
+                from,
+                network
+            ),
+            Err(e) => error!(
+                "Failed to cancel nonce {} of {} on {}: {}",
+                nonce, from, network, e
+            ),
+        }
+    }
+
+    /// Takes the sender's next nonce, None without a nonce manager or sender
+    async fn next_nonce(&self, network: &str, from: Option<Address>) -> Result<Option<u64>> {
+        match (&self.nonce_manager, from) {
+            (Some(manager), Some(from)) => manager
+                .next_nonce(network, from, &self.provider)
+                .await
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Marks the nonce of a mined transaction as used
+    async fn confirm_nonce(
+        &self,
+        network: &str,
+        from: Option<Address>,
+        nonce: Option<u64>,
+        receipt: &TransactionReceipt,
+    ) {
+        let (Some(from), Some(nonce)) = (from, nonce) else {
+            return;
+        };
+        if let Some(manager) = &self.nonce_manager {
+            manager.confirm(network, from, nonce).await;
+        }
+        if let Some(tracker) = &self.tracker {
+            tracker.resolve(network, from, nonce, receipt).await;
+        }
+    }
+
+    /// Returns the nonce of a failed transaction, so the next one is resynced
+    /// Transactions already broadcast with it are watched until they resolve
+    async fn release_nonce(&self, network: &str, from: Option<Address>, nonce: Option<u64>) {
+        let (Some(from), Some(nonce)) = (from, nonce) else {
+            return;
+        };
+        if let Some(manager) = &self.nonce_manager {
+            manager.release(network, from, nonce).await;
+        }
+        if let Some(tracker) = &self.tracker {
+            tracker.watch(network, from, nonce);
+        }
+    }
+
+    /// Feeds the transaction belongs to
+    fn feeds<'f>(&'f self, feed_name: &'f str) -> Vec<&'f str> {
+        if self.shared_by.is_empty() {
+            vec![feed_name]
+        } else {
+            self.shared_by.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Splits the gas used, the gas limit and the L1 fee across the feeds
+    /// sharing the transaction
+    fn gas_shares<'f>(
+        &'f self,
+        feed_name: &'f str,
+        receipt: &TransactionReceipt,
+        gas_estimate: &GasEstimate,
+        l1_fee: L1Fee,
+    ) -> Vec<GasShare<'f>> {
+        let gas_limit = gas_estimate.gas_limit.to::<u64>();
+        if self.shared_by.is_empty() {
+            return vec![GasShare {
+                feed_name,
+                gas_used: receipt.gas_used as u64,
+                gas_limit,
+                l1_fee,
+            }];
+        }
+
+        let gas_used = split_gas(receipt.gas_used as u64, self.shared_by.len());
+        let gas_limit = split_gas(gas_limit, self.shared_by.len());
+        let l1_fees = l1_fee.split(self.shared_by.len());
+        self.shared_by
+            .iter()
+            .zip(gas_used)
+            .zip(gas_limit)
+            .zip(l1_fees)
+            .map(|(((feed, gas_used), gas_limit), l1_fee)| GasShare {
+                feed_name: feed.as_str(),
+                gas_used,
+                gas_limit,
+                l1_fee,
+            })
+            .collect()
+    }
+
+    /// Log the receipt to the database, one row per feed
+    ///
+    /// Rows of a transaction short of the confirmation depth stay `pending`.
+    async fn log_receipt(
+        &self,
+        tx_log_repo: Option<&Arc<TransactionLogRepository>>,
+        network_config: &NetworkConfig,
+        receipt: &TransactionReceipt,
+        shares: &[GasShare<'_>],
+        gas_estimate: &GasEstimate,
+        confirmed: bool,
+    ) {
+        let Some(repo) = tx_log_repo else {
+            return;
+        };
+
+        for share in shares {
+            let details =
+                Self::transaction_details(receipt, share, network_config, gas_estimate, confirmed);
+            let result = match details {
+                Ok(details) => repo.save_transaction(details).await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                error!("Failed to log transaction for {}: {}", share.feed_name, e);
+            }
+        }
+    }
+
+    /// Waits until the mined transaction is buried at the network's confirmation depth
+    ///
+    /// Meanwhile its rows are logged as `pending`. If it is reorged out, they
+    /// are marked `reorged` and an error is returned, so the nonce is released
+    /// and the tracker, if any, follows the transaction again.
+    async fn bury(
+        &self,
+        receipt: TransactionReceipt,
+        feed_name: &str,
+        network_config: &NetworkConfig,
+        gas_estimate: &GasEstimate,
+        tx_log_repo: &Option<Arc<TransactionLogRepository>>,
+    ) -> Result<TransactionReceipt> {
+        let confirmations = network_config.confirmations;
+        if confirmations <= 1 {
+            return Ok(receipt);
+        }
+
+        // The L1 fee is added to the rows once the transaction is final
+        let tx_hash = format!("0x{:x}", receipt.transaction_hash);
+        self.log_receipt(
+            tx_log_repo.as_ref(),
+            network_config,
+            &receipt,
+            &self.gas_shares(feed_name, &receipt, gas_estimate, L1Fee::default()),
+            gas_estimate,
+            false,
+        )
+        .await;
+
+        match wait_for_confirmations(&self.provider, receipt, confirmations).await? {
+            Confirmation::Confirmed(receipt) => Ok(receipt),
+            Confirmation::Reorged => {
+                for feed in self.feeds(feed_name) {
+                    ContractMetrics::record_transaction_reorg(feed, &network_config.name, &tx_hash);
+                }
+                if let Some(repo) = tx_log_repo {
+                    if let Err(e) = repo.set_confirmation_status(&tx_hash, "reorged").await {
+                        error!("Failed to mark {} as reorged: {}", tx_hash, e);
+                    }
+                }
+                Err(anyhow::anyhow!(
+                    "Transaction {tx_hash} was reorged out before {confirmations} confirmations"
+                ))
+            }
+        }
+    }
+
+    /// The transaction log row of a feed's share of the receipt
+    fn transaction_details(
+        receipt: &TransactionReceipt,
+        share: &GasShare<'_>,
+        network_config: &NetworkConfig,
+        gas_estimate: &GasEstimate,
+        confirmed: bool,
+    ) -> Result<TransactionDetails> {
+        let GasShare {
+            feed_name,
+            gas_used,
+            gas_limit,
+            l1_fee,
+        } = *share;
+        let efficiency_percent = (gas_used as f64 / gas_limit as f64) * 100.0;
+
+        let gas_price_gwei = if let Some(price) = gas_estimate.gas_price {
+            alloy::primitives::utils::format_units(price, "gwei")?.parse::<f64>()?
+        } else if let Some(max_fee) = gas_estimate.max_fee_per_gas {
+            alloy::primitives::utils::format_units(max_fee, "gwei")?.parse::<f64>()?
+        } else {
+            0.0
+        };
+
+        let total_cost_wei = U256::from(gas_used) * U256::from(receipt.effective_gas_price)
+            + U256::from(l1_fee.extra_wei());
+
+        let details = TransactionDetails {
+            tx_hash: format!("0x{:x}", receipt.transaction_hash),
+            feed_name: feed_name.to_string(),
+            network: network_config.name.clone(),
+            gas_limit,
+            gas_used,
+            gas_price_gwei,
+            total_cost_wei: total_cost_wei.to::<u128>(),
+            l1_fee_wei: l1_fee.wei,
+            efficiency_percent,
+            tx_type: network_config.transaction_type.clone(),
+            status: match (confirmed, receipt.status()) {
+                (false, _) => "pending",
+                (true, true) => "success",
+                (true, false) => "failed",
+            }
+            .to_string(),
+            block_number: receipt.block_number.unwrap_or(0),
+            error_message: None,
+            confirmation_status: Some(if confirmed { "confirmed" } else { "pending" }.to_string()),
+        };
+
+        Ok(details)
+    }
+}
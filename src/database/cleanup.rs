@@ -5,7 +5,7 @@ use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{debug, error, info};
 
 use super::repository::FeedLogRepository;
-use crate::config::models::OmikujiConfig;
+use crate::config::models::{Datafeed, OmikujiConfig};
 
 /// Manages the database cleanup task
 pub struct CleanupManager {
@@ -93,7 +93,11 @@ async fn run_cleanup(config: &OmikujiConfig, repository: &FeedLogRepository) ->
     let mut feed_count = 0;
 
     // Clean up each datafeed based on its retention configuration
-    for datafeed in &config.datafeeds {
+    for datafeed in config
+        .datafeeds
+        .iter()
+        .flat_map(Datafeed::expand_deployments)
+    {
         let retention_days = datafeed.data_retention_days;
 
         match repository
@@ -165,6 +169,14 @@ mod tests {
                 gas_config: Default::default(),
                 gas_token: "ethereum".to_string(),
                 gas_token_symbol: "ETH".to_string(),
+                batching: Default::default(),
+                confirmations: 1,
+                rpc_endpoints: Vec::new(),
+                rpc_failover: Default::default(),
+                read_quorum: Default::default(),
+                check_feeds_on_new_heads: false,
+                chain_id: None,
+                l2_type: "none".to_string(),
             }],
             datafeeds: vec![Datafeed {
                 name: "test-feed".to_string(),
@@ -172,6 +184,9 @@ mod tests {
                 check_frequency: 60,
                 contract_address: "0x1234567890123456789012345678901234567890".to_string(),
                 contract_type: "fluxmon".to_string(),
+                setter_signature: None,
+                custom_abi: None,
+                custom_errors: Vec::new(),
                 read_contract_config: false,
                 minimum_update_frequency: 3600,
                 deviation_threshold_pct: 0.5,
@@ -182,12 +197,26 @@ mod tests {
                 min_value: None,
                 max_value: None,
                 data_retention_days: 7,
+                request: Default::default(),
+                sources: vec![],
+                aggregation: Default::default(),
+                min_sources: 1,
+                transform: None,
+                outlier_guard: None,
+                dry_run: false,
+                gas_config: None,
+                deployments: vec![],
+                stream: None,
+                onchain: None,
             }],
             database_cleanup: Default::default(),
             key_storage: Default::default(),
             metrics: MetricsConfig::default(),
             gas_price_feeds: GasPriceFeedConfig::default(),
             scheduled_tasks: vec![],
+            fetch_cache: Default::default(),
+            fetch_retry: Default::default(),
+            rate_limits: Default::default(),
         }
     }
 
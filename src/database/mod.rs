@@ -1,6 +1,7 @@
 pub mod cleanup;
 pub mod connection;
 pub mod models;
+pub mod pending_transaction_repository;
 pub mod repository;
 pub mod transaction_repository;
 
@@ -8,5 +9,6 @@ pub mod transaction_repository;
 mod tests;
 
 pub use connection::{establish_connection, DatabasePool};
+pub use pending_transaction_repository::PendingTransactionRepository;
 pub use repository::FeedLogRepository;
 pub use transaction_repository::TransactionLogRepository;
@@ -0,0 +1,211 @@
+use super::connection::DatabasePool;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use tracing::debug;
+
+/// Repository for transactions broadcast but not yet resolved
+pub struct PendingTransactionRepository {
+    pool: DatabasePool,
+}
+
+/// Pending transaction entry from database
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PendingTransaction {
+    pub id: i32,
+    pub tx_hash: String,
+    pub network_name: String,
+    pub sender: String,
+    pub nonce: i64,
+    /// Signed EIP-2718 envelope
+    pub raw_tx: Vec<u8>,
+    pub context_type: String,
+    pub names: Vec<String>,
+    pub tx_type: String,
+    pub gas_limit: i64,
+    pub gas_price_wei: Option<String>,
+    pub max_fee_per_gas_wei: Option<String>,
+    pub max_priority_fee_per_gas_wei: Option<String>,
+    pub attempt: i32,
+    /// Zero-value self-transfer cancelling the nonce
+    pub cancellation: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A transaction about to be broadcast
+#[derive(Debug, Clone)]
+pub struct NewPendingTransaction {
+    pub tx_hash: String,
+    pub network_name: String,
+    pub sender: String,
+    pub nonce: u64,
+    pub raw_tx: Vec<u8>,
+    pub context_type: String,
+    pub names: Vec<String>,
+    pub tx_type: String,
+    pub gas_limit: u64,
+    pub gas_price_wei: Option<String>,
+    pub max_fee_per_gas_wei: Option<String>,
+    pub max_priority_fee_per_gas_wei: Option<String>,
+    pub attempt: u32,
+    pub cancellation: bool,
+}
+
+impl PendingTransactionRepository {
+    /// Create a new repository instance
+    pub fn new(pool: DatabasePool) -> Self {
+        Self { pool }
+    }
+
+    /// Save a transaction before it is broadcast
+    pub async fn save(&self, tx: &NewPendingTransaction) -> Result<i32> {
+        let result = sqlx::query_as::<_, (i32,)>(
+            r#"
+            INSERT INTO pending_transactions (
+                tx_hash, network_name, sender, nonce, raw_tx, context_type, names,
+                tx_type, gas_limit, gas_price_wei, max_fee_per_gas_wei,
+                max_priority_fee_per_gas_wei, attempt, cancellation
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9,
+                $10::NUMERIC, $11::NUMERIC, $12::NUMERIC, $13, $14
+            )
+            ON CONFLICT (tx_hash) DO UPDATE SET attempt = EXCLUDED.attempt
+            RETURNING id
+            "#,
+        )
+        .bind(&tx.tx_hash)
+        .bind(&tx.network_name)
+        .bind(&tx.sender)
+        .bind(tx.nonce as i64)
+        .bind(&tx.raw_tx)
+        .bind(&tx.context_type)
+        .bind(&tx.names)
+        .bind(&tx.tx_type)
+        .bind(tx.gas_limit as i64)
+        .bind(&tx.gas_price_wei)
+        .bind(&tx.max_fee_per_gas_wei)
+        .bind(&tx.max_priority_fee_per_gas_wei)
+        .bind(tx.attempt as i32)
+        .bind(tx.cancellation)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to save pending transaction")?;
+
+        debug!(
+            "Saved pending transaction with id={}: {} nonce {} on {}",
+            result.0, tx.tx_hash, tx.nonce, tx.network_name
+        );
+
+        Ok(result.0)
+    }
+
+    /// Get all pending transactions, oldest first
+    pub async fn get_all(&self) -> Result<Vec<PendingTransaction>> {
+        sqlx::query_as::<_, PendingTransaction>(
+            r#"
+            SELECT
+                id, tx_hash, network_name, sender, nonce, raw_tx, context_type, names,
+                tx_type, gas_limit,
+                gas_price_wei::TEXT as gas_price_wei,
+                max_fee_per_gas_wei::TEXT as max_fee_per_gas_wei,
+                max_priority_fee_per_gas_wei::TEXT as max_priority_fee_per_gas_wei,
+                attempt, cancellation, created_at
+            FROM pending_transactions
+            ORDER BY network_name, sender, nonce, attempt
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to get pending transactions")
+    }
+
+    /// Get every transaction sent with a nonce, the original and its replacements
+    pub async fn get_by_nonce(
+        &self,
+        network: &str,
+        sender: &str,
+        nonce: u64,
+    ) -> Result<Vec<PendingTransaction>> {
+        sqlx::query_as::<_, PendingTransaction>(
+            r#"
+            SELECT
+                id, tx_hash, network_name, sender, nonce, raw_tx, context_type, names,
+                tx_type, gas_limit,
+                gas_price_wei::TEXT as gas_price_wei,
+                max_fee_per_gas_wei::TEXT as max_fee_per_gas_wei,
+                max_priority_fee_per_gas_wei::TEXT as max_priority_fee_per_gas_wei,
+                attempt, cancellation, created_at
+            FROM pending_transactions
+            WHERE network_name = $1 AND sender = $2 AND nonce = $3
+            ORDER BY attempt
+            "#,
+        )
+        .bind(network)
+        .bind(sender)
+        .bind(nonce as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to get pending transactions by nonce")
+    }
+
+    /// Check whether any of the named feeds or tasks has a transaction on the network
+    pub async fn has_pending_for(
+        &self,
+        network: &str,
+        context_type: &str,
+        names: &[String],
+    ) -> Result<bool> {
+        let (exists,): (bool,) = sqlx::query_as(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM pending_transactions
+                WHERE network_name = $1 AND context_type = $2 AND names && $3
+            )
+            "#,
+        )
+        .bind(network)
+        .bind(context_type)
+        .bind(names)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to check for pending transactions")?;
+
+        Ok(exists)
+    }
+
+    /// Delete a transaction that was never broadcast
+    pub async fn delete_by_hash(&self, tx_hash: &str) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM pending_transactions WHERE tx_hash = $1")
+            .bind(tx_hash)
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete pending transaction")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete every transaction sent with a nonce once it is resolved
+    pub async fn delete_by_nonce(&self, network: &str, sender: &str, nonce: u64) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM pending_transactions
+            WHERE network_name = $1 AND sender = $2 AND nonce = $3
+            "#,
+        )
+        .bind(network)
+        .bind(sender)
+        .bind(nonce as i64)
+        .execute(&self.pool)
+        .await
+        .context("Failed to delete pending transactions by nonce")?;
+
+        debug!(
+            "Resolved nonce {} of {} on {}, deleted {} pending transactions",
+            nonce,
+            sender,
+            network,
+            result.rows_affected()
+        );
+
+        Ok(result.rows_affected())
+    }
+}
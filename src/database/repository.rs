@@ -65,6 +65,36 @@ impl FeedLogRepository {
         Ok(record)
     }
 
+    /// Returns the most recent successfully fetched values, oldest first
+    pub async fn get_recent_values(
+        &self,
+        feed_name: &str,
+        network_name: &str,
+        limit: usize,
+    ) -> Result<Vec<f64>> {
+        let mut values: Vec<f64> = sqlx::query_scalar(
+            r#"
+            SELECT feed_value
+            FROM feed_log
+            WHERE feed_name = $1
+                AND network_name = $2
+                AND error_status_code IS NULL
+                AND network_error = false
+            ORDER BY created_at DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(feed_name)
+        .bind(network_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch recent feed values")?;
+
+        values.reverse();
+        Ok(values)
+    }
+
     /// Deletes feed logs older than the specified number of days
     #[allow(dead_code)]
     pub async fn delete_older_than(
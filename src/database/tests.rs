@@ -165,6 +165,9 @@ mod tests {
             metrics: MetricsConfig::default(),
             gas_price_feeds: GasPriceFeedConfig::default(),
             scheduled_tasks: vec![],
+            fetch_cache: Default::default(),
+            fetch_retry: Default::default(),
+            rate_limits: Default::default(),
         };
 
         assert!(config.database_cleanup.enabled);
@@ -206,6 +209,9 @@ mod tests {
             metrics: MetricsConfig::default(),
             gas_price_feeds: GasPriceFeedConfig::default(),
             scheduled_tasks: vec![],
+            fetch_cache: Default::default(),
+            fetch_retry: Default::default(),
+            rate_limits: Default::default(),
         };
 
         // Mock repository for testing
@@ -1,5 +1,6 @@
 use super::connection::DatabasePool;
 use crate::metrics::gas_metrics::TransactionDetails;
+use crate::utils::DryRunSubmission;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use tracing::debug;
@@ -14,13 +15,16 @@ pub struct TransactionLogRepository {
 #[allow(dead_code)]
 pub struct TransactionLog {
     pub id: i32,
-    pub tx_hash: String,
+    /// None for transactions skipped because they would revert
+    pub tx_hash: Option<String>,
     pub feed_name: String,
     pub network_name: String,
     pub gas_limit: i64,
     pub gas_used: i64,
     pub gas_price_gwei: f64,
     pub total_cost_wei: String, // Store as string to avoid BigDecimal issues
+    /// L1 fee of a rollup transaction, 0 outside of rollups
+    pub l1_fee_wei: String,
     pub efficiency_percent: f64,
     pub tx_type: String,
     pub status: String,
@@ -28,6 +32,10 @@ pub struct TransactionLog {
     pub error_message: Option<String>,
     pub max_fee_per_gas_gwei: Option<f64>,
     pub max_priority_fee_per_gas_gwei: Option<f64>,
+    /// First transaction sent with the nonce, for replacements
+    pub original_tx_hash: Option<String>,
+    /// `pending`, `confirmed` or `reorged`, None if never mined
+    pub confirmation_status: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -51,16 +59,19 @@ impl TransactionLogRepository {
             INSERT INTO transaction_log (
                 tx_hash, feed_name, network_name, gas_limit, gas_used,
                 gas_price_gwei, total_cost_wei, efficiency_percent,
-                tx_type, status, block_number, error_message
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7::NUMERIC, $8, $9, $10, $11, $12)
-            ON CONFLICT (tx_hash) DO UPDATE SET
+                tx_type, status, block_number, error_message, confirmation_status,
+                l1_fee_wei
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7::NUMERIC, $8, $9, $10, $11, $12, $13, $14::NUMERIC)
+            ON CONFLICT (tx_hash, feed_name) DO UPDATE SET
                 gas_used = EXCLUDED.gas_used,
                 gas_price_gwei = EXCLUDED.gas_price_gwei,
                 total_cost_wei = EXCLUDED.total_cost_wei,
+                l1_fee_wei = EXCLUDED.l1_fee_wei,
                 efficiency_percent = EXCLUDED.efficiency_percent,
                 status = EXCLUDED.status,
                 block_number = EXCLUDED.block_number,
-                error_message = EXCLUDED.error_message
+                error_message = EXCLUDED.error_message,
+                confirmation_status = EXCLUDED.confirmation_status
             RETURNING id
             "#,
         )
@@ -76,6 +87,8 @@ impl TransactionLogRepository {
         .bind(&details.status)
         .bind(details.block_number as i64)
         .bind(&details.error_message)
+        .bind(&details.confirmation_status)
+        .bind(details.l1_fee_wei.to_string())
         .fetch_one(&self.pool)
         .await
         .context("Failed to save transaction log")?;
@@ -87,6 +100,190 @@ impl TransactionLogRepository {
 
         Ok(result.0)
     }
+
+    /// Total cost in wei of the transactions mined on a network in the last `hours`
+    ///
+    /// Includes the L1 fees of rollup transactions. Pending transactions are
+    /// left out until they are final.
+    pub async fn get_network_cost_since(&self, network: &str, hours: i64) -> Result<u128> {
+        let (total,) = sqlx::query_as::<_, (String,)>(
+            r#"
+            SELECT COALESCE(SUM(total_cost_wei), 0)::TEXT
+            FROM transaction_log
+            WHERE network_name = $1
+              AND status IN ('success', 'failed', 'cancellation')
+              AND created_at > CURRENT_TIMESTAMP - make_interval(hours => $2::INT)
+            "#,
+        )
+        .bind(network)
+        .bind(hours)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to get network transaction cost")?;
+
+        total
+            .parse()
+            .with_context(|| format!("Invalid total cost {total}"))
+    }
+
+    /// Save a transaction that was not sent because its pre-flight simulation reverted
+    pub async fn save_skipped_revert(
+        &self,
+        feed_name: &str,
+        network: &str,
+        tx_type: &str,
+        reason: &str,
+    ) -> Result<i32> {
+        let result = sqlx::query_as::<_, (i32,)>(
+            r#"
+            INSERT INTO transaction_log (
+                tx_hash, feed_name, network_name, gas_limit, gas_used,
+                gas_price_gwei, total_cost_wei, efficiency_percent,
+                tx_type, status, block_number, error_message
+            ) VALUES (NULL, $1, $2, 0, 0, 0, 0, 0, $3, 'reverted', 0, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(feed_name)
+        .bind(network)
+        .bind(tx_type)
+        .bind(reason)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to save skipped transaction log")?;
+
+        debug!(
+            "Saved skipped transaction log with id={}: {} on {} - {}",
+            result.0, feed_name, network, reason
+        );
+
+        Ok(result.0)
+    }
+
+    /// Resolve the `pending` rows of a transaction that will not be mined
+    ///
+    /// Used for transactions replaced by another with the same nonce and for
+    /// transactions dropped from the mempool.
+    pub async fn resolve_pending(
+        &self,
+        tx_hash: &str,
+        status: &str,
+        error_message: Option<&str>,
+    ) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE transaction_log
+            SET status = $2, error_message = $3
+            WHERE tx_hash = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(tx_hash)
+        .bind(status)
+        .bind(error_message)
+        .execute(&self.pool)
+        .await
+        .context("Failed to resolve pending transaction log")?;
+
+        debug!(
+            "Resolved {} pending transaction log rows of {} as {}",
+            result.rows_affected(),
+            tx_hash,
+            status
+        );
+
+        Ok(result.rows_affected())
+    }
+
+    /// Set the confirmation status of every row of a mined transaction
+    ///
+    /// Used for transactions whose block was reorged away before they
+    /// reached the confirmation depth.
+    pub async fn set_confirmation_status(&self, tx_hash: &str, status: &str) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE transaction_log
+            SET confirmation_status = $2
+            WHERE tx_hash = $1
+            "#,
+        )
+        .bind(tx_hash)
+        .bind(status)
+        .execute(&self.pool)
+        .await
+        .context("Failed to set confirmation status")?;
+
+        debug!(
+            "Set confirmation status of {} rows of {} to {}",
+            result.rows_affected(),
+            tx_hash,
+            status
+        );
+
+        Ok(result.rows_affected())
+    }
+
+    /// Link a replacement transaction to the first transaction sent with its nonce
+    pub async fn link_replacement(&self, tx_hash: &str, original_tx_hash: &str) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE transaction_log
+            SET original_tx_hash = $2
+            WHERE tx_hash = $1
+            "#,
+        )
+        .bind(tx_hash)
+        .bind(original_tx_hash)
+        .execute(&self.pool)
+        .await
+        .context("Failed to link replacement transaction")?;
+
+        debug!("Linked replacement {} to {}", tx_hash, original_tx_hash);
+
+        Ok(result.rows_affected())
+    }
+
+    /// Save a submission simulated in dry-run mode
+    pub async fn save_dry_run(
+        &self,
+        submission: &DryRunSubmission,
+        estimated_cost_usd: Option<f64>,
+    ) -> Result<i32> {
+        let result = sqlx::query_as::<_, (i32,)>(
+            r#"
+            INSERT INTO dry_run_log (
+                context_type, name, network_name, contract_address, round_id,
+                feed_value, scaled_value, gas_limit, gas_price_gwei,
+                estimated_cost_wei, estimated_cost_usd, status, error_message
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7::NUMERIC, $8, $9, $10::NUMERIC, $11, $12, $13)
+            RETURNING id
+            "#,
+        )
+        .bind(submission.context.context_type())
+        .bind(submission.context.name())
+        .bind(&submission.network)
+        .bind(submission.contract_address.to_string())
+        .bind(submission.round_id.map(|round| round as i64))
+        .bind(submission.value)
+        .bind(submission.scaled_value.map(|value| value.to_string()))
+        .bind(submission.gas_limit as i64)
+        .bind(submission.gas_price_wei as f64 / 1e9)
+        .bind(submission.estimated_cost_wei().to_string())
+        .bind(estimated_cost_usd)
+        .bind(submission.status())
+        .bind(&submission.simulation_error)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to save dry-run log")?;
+
+        debug!(
+            "Saved dry-run log with id={}: {} on {}",
+            result.0,
+            submission.context.name(),
+            submission.network
+        );
+
+        Ok(result.0)
+    }
 }
 
 #[cfg(test)]
@@ -102,11 +299,13 @@ mod tests {
             gas_used: 150000,
             gas_price_gwei: 35.5,
             total_cost_wei: 5325000000000000u128,
+            l1_fee_wei: 0,
             efficiency_percent: 75.0,
             tx_type: "eip1559".to_string(),
             status: "success".to_string(),
             block_number: 15000000,
             error_message: None,
+            confirmation_status: Some("confirmed".to_string()),
         }
     }
 
@@ -115,13 +314,14 @@ mod tests {
         let now = Utc::now();
         let log = TransactionLog {
             id: 1,
-            tx_hash: "0xtest123".to_string(),
+            tx_hash: Some("0xtest123".to_string()),
             feed_name: "btc_usd".to_string(),
             network_name: "base".to_string(),
             gas_limit: 100000,
             gas_used: 80000,
             gas_price_gwei: 20.0,
             total_cost_wei: "1600000000000000".to_string(),
+            l1_fee_wei: "0".to_string(),
             efficiency_percent: 80.0,
             tx_type: "legacy".to_string(),
             status: "success".to_string(),
@@ -129,11 +329,13 @@ mod tests {
             error_message: None,
             max_fee_per_gas_gwei: None,
             max_priority_fee_per_gas_gwei: None,
+            original_tx_hash: None,
+            confirmation_status: Some("confirmed".to_string()),
             created_at: now,
         };
 
         assert_eq!(log.id, 1);
-        assert_eq!(log.tx_hash, "0xtest123");
+        assert_eq!(log.tx_hash.as_deref(), Some("0xtest123"));
         assert_eq!(log.efficiency_percent, 80.0);
         assert_eq!(log.status, "success");
     }
@@ -155,21 +357,24 @@ mod tests {
             INSERT INTO transaction_log (
                 tx_hash, feed_name, network_name, gas_limit, gas_used,
                 gas_price_gwei, total_cost_wei, efficiency_percent,
-                tx_type, status, block_number, error_message
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7::NUMERIC, $8, $9, $10, $11, $12)
-            ON CONFLICT (tx_hash) DO UPDATE SET
+                tx_type, status, block_number, error_message, confirmation_status,
+                l1_fee_wei
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7::NUMERIC, $8, $9, $10, $11, $12, $13, $14::NUMERIC)
+            ON CONFLICT (tx_hash, feed_name) DO UPDATE SET
                 gas_used = EXCLUDED.gas_used,
                 gas_price_gwei = EXCLUDED.gas_price_gwei,
                 total_cost_wei = EXCLUDED.total_cost_wei,
+                l1_fee_wei = EXCLUDED.l1_fee_wei,
                 efficiency_percent = EXCLUDED.efficiency_percent,
                 status = EXCLUDED.status,
                 block_number = EXCLUDED.block_number,
-                error_message = EXCLUDED.error_message
+                error_message = EXCLUDED.error_message,
+                confirmation_status = EXCLUDED.confirmation_status
             RETURNING id
             "#;
 
         assert!(query.contains("INSERT INTO transaction_log"));
-        assert!(query.contains("ON CONFLICT (tx_hash) DO UPDATE"));
+        assert!(query.contains("ON CONFLICT (tx_hash, feed_name) DO UPDATE"));
         assert!(query.contains("RETURNING id"));
     }
 
@@ -0,0 +1,226 @@
+use anyhow::Result;
+use thiserror::Error;
+
+use crate::config::models::AggregationConfig;
+
+/// Relative distance (in percent) within which a source is considered to agree
+/// with the aggregated value
+pub const SOURCE_AGREEMENT_TOLERANCE_PCT: f64 = 1.0;
+
+/// Errors that can occur when aggregating source values
+#[derive(Debug, Error)]
+pub enum AggregationError {
+    #[error("Insufficient sources: {available} of {required} required sources returned a value")]
+    InsufficientSources { available: usize, required: usize },
+
+    #[error("Unknown aggregation method: {0}")]
+    UnknownMethod(String),
+
+    #[error("Total source weight must be greater than zero")]
+    ZeroWeight,
+}
+
+/// A value fetched from one source of a multi-source datafeed
+#[derive(Debug, Clone)]
+pub struct SourceValue {
+    pub url: String,
+    pub value: f64,
+    pub timestamp: u64,
+    pub weight: f64,
+}
+
+/// Combines values from multiple sources into a single feed value
+pub struct Aggregator;
+
+impl Aggregator {
+    /// Aggregates source values using the configured method
+    ///
+    /// # Arguments
+    /// * `values` - Values returned by the sources that responded successfully
+    /// * `config` - Aggregation method and parameters
+    /// * `min_sources` - Quorum below which the round is rejected
+    ///
+    /// # Returns
+    /// Tuple of (aggregated value, timestamp). The timestamp is the oldest
+    /// source timestamp, as the result is only as fresh as its stalest input.
+    pub fn aggregate(
+        values: &[SourceValue],
+        config: &AggregationConfig,
+        min_sources: usize,
+    ) -> Result<(f64, u64)> {
+        if values.is_empty() || values.len() < min_sources {
+            return Err(AggregationError::InsufficientSources {
+                available: values.len(),
+                required: min_sources.max(1),
+            }
+            .into());
+        }
+
+        let value = match config.method.as_str() {
+            "median" => Self::median(values.iter().map(|v| v.value).collect()),
+            "weighted_mean" => Self::weighted_mean(values)?,
+            "trimmed_mean" => {
+                Self::trimmed_mean(values.iter().map(|v| v.value).collect(), config.trim_pct)
+            }
+            other => return Err(AggregationError::UnknownMethod(other.to_string()).into()),
+        };
+
+        let timestamp = values.iter().map(|v| v.timestamp).min().unwrap_or_default();
+
+        Ok((value, timestamp))
+    }
+
+    /// Calculates the percentage of sources whose value lies within
+    /// `SOURCE_AGREEMENT_TOLERANCE_PCT` of the aggregated value
+    pub fn agreement_percent(values: &[SourceValue], aggregate: f64) -> f64 {
+        if values.is_empty() {
+            return 0.0;
+        }
+
+        let agreeing = values
+            .iter()
+            .filter(|v| {
+                if aggregate == 0.0 {
+                    v.value == 0.0
+                } else {
+                    ((v.value - aggregate).abs() / aggregate.abs()) * 100.0
+                        <= SOURCE_AGREEMENT_TOLERANCE_PCT
+                }
+            })
+            .count();
+
+        (agreeing as f64 / values.len() as f64) * 100.0
+    }
+
+    /// Returns the median of the given values
+    fn median(mut values: Vec<f64>) -> f64 {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let mid = values.len() / 2;
+
+        if values.len() % 2 == 0 {
+            (values[mid - 1] + values[mid]) / 2.0
+        } else {
+            values[mid]
+        }
+    }
+
+    /// Returns the mean of the values weighted by each source's weight
+    fn weighted_mean(values: &[SourceValue]) -> Result<f64> {
+        let total_weight: f64 = values.iter().map(|v| v.weight).sum();
+        if total_weight <= 0.0 {
+            return Err(AggregationError::ZeroWeight.into());
+        }
+
+        let weighted_sum: f64 = values.iter().map(|v| v.value * v.weight).sum();
+        Ok(weighted_sum / total_weight)
+    }
+
+    /// Returns the mean after discarding `trim_pct` percent of values at each end
+    fn trimmed_mean(mut values: Vec<f64>, trim_pct: f64) -> f64 {
+        values.sort_by(|a, b| a.total_cmp(b));
+
+        let trim = ((values.len() as f64) * trim_pct / 100.0).floor() as usize;
+        let kept = &values[trim..values.len() - trim];
+
+        kept.iter().sum::<f64>() / kept.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_values(values: &[f64]) -> Vec<SourceValue> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| SourceValue {
+                url: format!("https://source{i}.example.com"),
+                value: *v,
+                timestamp: 1_700_000_000 + i as u64,
+                weight: 1.0,
+            })
+            .collect()
+    }
+
+    fn config(method: &str) -> AggregationConfig {
+        AggregationConfig {
+            method: method.to_string(),
+            trim_pct: 20.0,
+        }
+    }
+
+    #[test]
+    fn test_median_odd_and_even() {
+        let values = source_values(&[3.0, 1.0, 2.0]);
+        let (value, _) = Aggregator::aggregate(&values, &config("median"), 1).unwrap();
+        assert_eq!(value, 2.0);
+
+        let values = source_values(&[4.0, 1.0, 3.0, 2.0]);
+        let (value, _) = Aggregator::aggregate(&values, &config("median"), 1).unwrap();
+        assert_eq!(value, 2.5);
+    }
+
+    #[test]
+    fn test_median_ignores_single_outlier() {
+        let values = source_values(&[2000.0, 2001.0, 9999.0]);
+        let (value, _) = Aggregator::aggregate(&values, &config("median"), 1).unwrap();
+        assert_eq!(value, 2001.0);
+    }
+
+    #[test]
+    fn test_weighted_mean() {
+        let mut values = source_values(&[100.0, 200.0]);
+        values[0].weight = 3.0;
+        values[1].weight = 1.0;
+
+        let (value, _) = Aggregator::aggregate(&values, &config("weighted_mean"), 1).unwrap();
+        assert_eq!(value, 125.0);
+    }
+
+    #[test]
+    fn test_weighted_mean_zero_weight() {
+        let mut values = source_values(&[100.0, 200.0]);
+        values.iter_mut().for_each(|v| v.weight = 0.0);
+
+        let result = Aggregator::aggregate(&values, &config("weighted_mean"), 1);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_trimmed_mean() {
+        // 20% of 5 values trims one value from each end
+        let values = source_values(&[1.0, 10.0, 11.0, 12.0, 1000.0]);
+        let (value, _) = Aggregator::aggregate(&values, &config("trimmed_mean"), 1).unwrap();
+        assert_eq!(value, 11.0);
+    }
+
+    #[test]
+    fn test_quorum_not_met() {
+        let values = source_values(&[100.0]);
+        let result = Aggregator::aggregate(&values, &config("median"), 2);
+
+        let err = result.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<AggregationError>(),
+            Some(AggregationError::InsufficientSources {
+                available: 1,
+                required: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_timestamp_is_oldest_source() {
+        let values = source_values(&[1.0, 2.0, 3.0]);
+        let (_, timestamp) = Aggregator::aggregate(&values, &config("median"), 1).unwrap();
+        assert_eq!(timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_agreement_percent() {
+        let values = source_values(&[100.0, 100.5, 110.0, 99.5]);
+        let agreement = Aggregator::agreement_percent(&values, 100.0);
+        assert_eq!(agreement, 75.0);
+    }
+}
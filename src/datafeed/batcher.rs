@@ -0,0 +1,311 @@
+//! Batching of datafeed submissions into Multicall3 transactions
+//!
+//! Every network with `batching.enabled` gets a worker that collects the
+//! submissions of all datafeeds on the network for `window_ms` and sends them
+//! as one `aggregate3` transaction. Each submitter gets the shared receipt
+//! back with its share of the gas.
+
+use super::contract_updater::signer_provider;
+use super::contract_utils::parse_address;
+use crate::config::models::{Network, OmikujiConfig};
+use crate::contracts::multicall::{
+    aggregate3_calldata, decode_aggregate3, split_gas, BatchCall, BatchCallResult,
+};
+use crate::contracts::{record_skipped_revert, CallSubmitter, RevertDecoder, WouldRevert};
+use crate::database::TransactionLogRepository;
+use crate::gas::L1Fee;
+use crate::network::{NetworkManager, TransactionTracker};
+use alloy::{
+    network::Ethereum,
+    primitives::{Address, Bytes},
+    providers::Provider,
+    rpc::types::{BlockId, TransactionReceipt, TransactionRequest},
+    transports::Transport,
+};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{timeout_at, Duration, Instant};
+use tracing::{debug, error, info, warn};
+
+/// Feed name the contract write metrics of a batch transaction are recorded under
+const BATCH_FEED_NAME: &str = "multicall_batch";
+
+/// The receipt of the batch a submission was sent in
+#[derive(Debug, Clone)]
+pub struct BatchReceipt {
+    pub receipt: TransactionReceipt,
+    /// The submission's share of the gas used by the batch
+    pub gas_used: u64,
+    /// The submission's share of the batch's L1 fee on rollups
+    pub l1_fee: L1Fee,
+    /// Number of submissions in the batch
+    pub batch_size: usize,
+}
+
+/// A submission waiting for its batch
+struct PendingSubmission {
+    feed_name: String,
+    call: BatchCall,
+    revert_decoder: RevertDecoder,
+    respond: oneshot::Sender<Result<BatchReceipt, String>>,
+}
+
+/// Queues submissions for the batching worker of their network
+pub struct SubmissionBatcher {
+    queues: HashMap<String, mpsc::Sender<PendingSubmission>>,
+}
+
+impl SubmissionBatcher {
+    /// Starts a batching worker for every network with batching enabled
+    pub fn start(
+        config: &OmikujiConfig,
+        network_manager: Arc<NetworkManager>,
+        tx_log_repo: Option<Arc<TransactionLogRepository>>,
+        tracker: Option<Arc<TransactionTracker>>,
+    ) -> Result<Self> {
+        let mut queues = HashMap::new();
+
+        for network in config.networks.iter().filter(|n| n.batching.enabled) {
+            let worker = BatchWorker {
+                multicall: parse_address(&network.batching.multicall_address)?,
+                network: network.clone(),
+                network_manager: Arc::clone(&network_manager),
+                tx_log_repo: tx_log_repo.clone(),
+                tracker: tracker.clone(),
+            };
+
+            let (sender, receiver) = mpsc::channel(network.batching.max_batch_size * 4);
+            tokio::spawn(worker.run(receiver));
+            queues.insert(network.name.clone(), sender);
+
+            info!(
+                "Batching submissions on {} every {}ms through {}",
+                network.name, network.batching.window_ms, network.batching.multicall_address
+            );
+        }
+
+        Ok(Self { queues })
+    }
+
+    /// Returns true if submissions on the network are batched
+    pub fn is_batched(&self, network: &str) -> bool {
+        self.queues.contains_key(network)
+    }
+
+    /// Queues a submission and waits until its batch is mined
+    pub async fn submit(
+        &self,
+        network: &str,
+        feed_name: &str,
+        target: Address,
+        calldata: Bytes,
+        revert_decoder: RevertDecoder,
+    ) -> Result<BatchReceipt> {
+        let queue = self
+            .queues
+            .get(network)
+            .with_context(|| format!("Batching is not enabled on network {network}"))?;
+
+        let (respond, response) = oneshot::channel();
+        queue
+            .send(PendingSubmission {
+                feed_name: feed_name.to_string(),
+                call: BatchCall { target, calldata },
+                revert_decoder,
+                respond,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("Batching worker for network {network} has stopped"))?;
+
+        response
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!("Batching worker for network {network} dropped the submission")
+            })?
+            .map_err(|e| anyhow::anyhow!(e))
+    }
+}
+
+/// Collects and sends the batches of one network
+struct BatchWorker {
+    network: Network,
+    multicall: Address,
+    network_manager: Arc<NetworkManager>,
+    tx_log_repo: Option<Arc<TransactionLogRepository>>,
+    tracker: Option<Arc<TransactionTracker>>,
+}
+
+impl BatchWorker {
+    async fn run(self, mut queue: mpsc::Receiver<PendingSubmission>) {
+        let window = Duration::from_millis(self.network.batching.window_ms);
+
+        // The window starts with the first submission of a batch
+        while let Some(first) = queue.recv().await {
+            let mut batch = vec![first];
+            let deadline = Instant::now() + window;
+
+            while batch.len() < self.network.batching.max_batch_size {
+                match timeout_at(deadline, queue.recv()).await {
+                    Ok(Some(submission)) => batch.push(submission),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+
+            self.send_batch(batch).await;
+        }
+
+        debug!("Batching worker for {} stopped", self.network.name);
+    }
+
+    async fn send_batch(&self, batch: Vec<PendingSubmission>) {
+        let provider = match signer_provider(&self.network_manager, &self.network.name) {
+            Ok(provider) => provider,
+            Err(e) => return Self::fail_all(batch, &e),
+        };
+        let wallet_address = self
+            .network_manager
+            .get_wallet_address(&self.network.name)
+            .ok();
+
+        // Calls that would revert are dropped, so they don't cost gas
+        let calls: Vec<BatchCall> = batch.iter().map(|s| s.call.clone()).collect();
+        let batch = match self.simulate(&calls, &provider, wallet_address).await {
+            Ok(results) => self.drop_reverting(batch, results).await,
+            Err(e) => return Self::fail_all(batch, &e),
+        };
+        if batch.is_empty() {
+            return;
+        }
+
+        let feed_names: Vec<String> = batch.iter().map(|s| s.feed_name.clone()).collect();
+        let calls: Vec<BatchCall> = batch.iter().map(|s| s.call.clone()).collect();
+        info!(
+            "Sending batch of {} submissions on {}: {}",
+            batch.len(),
+            self.network.name,
+            feed_names.join(", ")
+        );
+
+        let mut submitter = CallSubmitter::new(self.multicall, provider)
+            .with_shared_by(feed_names)
+            .with_nonce_manager(Arc::clone(self.network_manager.nonce_manager()));
+        if let Some(ref tracker) = self.tracker {
+            submitter = submitter.with_tracker(Arc::clone(tracker));
+        }
+
+        let result = submitter
+            .submit_with_gas_estimation(
+                aggregate3_calldata(&calls),
+                &self.network,
+                BATCH_FEED_NAME,
+                self.tx_log_repo.clone(),
+                wallet_address,
+            )
+            .await;
+
+        match result {
+            Ok(receipt) => {
+                let batch_size = batch.len();
+                let shares = split_gas(receipt.gas_used as u64, batch_size);
+                let l1_fees = self
+                    .network_manager
+                    .get_l1_fee(
+                        &self.network.name,
+                        receipt.transaction_hash,
+                        receipt.effective_gas_price,
+                    )
+                    .await
+                    .split(batch_size);
+                for ((submission, gas_used), l1_fee) in batch.into_iter().zip(shares).zip(l1_fees) {
+                    let _ = submission.respond.send(Ok(BatchReceipt {
+                        receipt: receipt.clone(),
+                        gas_used,
+                        l1_fee,
+                        batch_size,
+                    }));
+                }
+            }
+            Err(e) => Self::fail_all(batch, &e),
+        }
+    }
+
+    /// Simulates the batch with `eth_call` and returns the result of every call
+    async fn simulate<T, P>(
+        &self,
+        calls: &[BatchCall],
+        provider: &P,
+        from: Option<Address>,
+    ) -> Result<Vec<BatchCallResult>>
+    where
+        T: Transport + Clone,
+        P: Provider<T, Ethereum>,
+    {
+        let mut tx = TransactionRequest::default()
+            .to(self.multicall)
+            .input(aggregate3_calldata(calls).into());
+        if let Some(from) = from {
+            tx = tx.from(from);
+        }
+
+        let data = provider
+            .call(&tx)
+            .block(BlockId::pending())
+            .await
+            .context("Batch simulation failed")?;
+        let results = decode_aggregate3(&data)?;
+        if results.len() != calls.len() {
+            anyhow::bail!(
+                "Batch simulation returned {} results for {} calls",
+                results.len(),
+                calls.len()
+            );
+        }
+
+        Ok(results)
+    }
+
+    /// Answers the submissions that would revert and returns the others
+    async fn drop_reverting(
+        &self,
+        batch: Vec<PendingSubmission>,
+        results: Vec<BatchCallResult>,
+    ) -> Vec<PendingSubmission> {
+        let mut kept = Vec::with_capacity(batch.len());
+        for (submission, result) in batch.into_iter().zip(results) {
+            if result.success {
+                kept.push(submission);
+                continue;
+            }
+
+            let revert = WouldRevert {
+                reason: submission.revert_decoder.decode(&result.return_data),
+            };
+            warn!(
+                "Dropping submission of {} from the batch on {}, {}",
+                submission.feed_name, self.network.name, revert
+            );
+            record_skipped_revert(
+                &submission.feed_name,
+                &self.network.name,
+                &self.network.transaction_type,
+                self.tx_log_repo.as_ref(),
+                &revert.reason,
+            )
+            .await;
+            let _ = submission.respond.send(Err(revert.to_string()));
+        }
+
+        kept
+    }
+
+    fn fail_all(batch: Vec<PendingSubmission>, error: &anyhow::Error) {
+        error!("Batch failed with {} submissions: {}", batch.len(), error);
+        for submission in batch {
+            let _ = submission
+                .respond
+                .send(Err(format!("Batch failed: {error}")));
+        }
+    }
+}
@@ -1,20 +1,15 @@
-use alloy::primitives::I256;
 use anyhow::{Context, Result};
 use std::sync::Arc;
 use tracing::info;
 
-use super::contract_utils::{create_contract_with_provider, parse_address};
+use super::contract_utils::parse_address;
+use crate::config::models::Datafeed;
+use crate::contracts::create_oracle_contract;
 use crate::network::NetworkManager;
 
-/// Configuration values read from a FluxAggregator contract
-#[derive(Debug, Clone)]
-pub struct ContractConfig {
-    pub decimals: u8,
-    pub min_value: I256, // Store as I256 to match contract's int256
-    pub max_value: I256, // Store as I256 to match contract's int256
-}
+pub use crate::contracts::oracle::ContractConfig;
 
-/// Reads configuration from FluxAggregator contracts
+/// Reads configuration from oracle contracts
 pub struct ContractConfigReader<'a> {
     network_manager: &'a Arc<NetworkManager>,
 }
@@ -25,26 +20,22 @@ impl<'a> ContractConfigReader<'a> {
         Self { network_manager }
     }
 
-    /// Reads configuration from a FluxAggregator contract
+    /// Reads configuration from the contract of a single-network datafeed
     ///
     /// # Arguments
-    /// * `network_name` - The network the contract is deployed on
-    /// * `contract_address` - The address of the FluxAggregator contract
+    /// * `datafeed` - The datafeed whose network, contract address and contract type are used
     ///
     /// # Returns
     /// The contract configuration or an error
-    pub async fn read_config(
-        &self,
-        network_name: &str,
-        contract_address: &str,
-    ) -> Result<ContractConfig> {
+    pub async fn read_config(&self, datafeed: &Datafeed) -> Result<ContractConfig> {
+        let network_name = &datafeed.networks;
         info!(
-            "Reading contract config from {} on network {}",
-            contract_address, network_name
+            "Reading {} contract config from {} on network {}",
+            datafeed.contract_type, datafeed.contract_address, network_name
         );
 
         // Parse the contract address
-        let address = parse_address(contract_address)?;
+        let address = parse_address(&datafeed.contract_address)?;
 
         // Get provider for the network
         let provider = self
@@ -53,34 +44,15 @@ impl<'a> ContractConfigReader<'a> {
             .with_context(|| format!("Failed to get provider for network: {network_name}"))?;
 
         // Create contract instance
-        let contract = create_contract_with_provider(address, provider.as_ref().clone());
-
-        // Read decimals
-        let decimals = contract
-            .decimals()
-            .await
-            .with_context(|| "Failed to read decimals from contract")?;
-
-        // Read min submission value
-        let min_value = contract
-            .min_submission_value()
-            .await
-            .with_context(|| "Failed to read minSubmissionValue from contract")?;
+        let contract = create_oracle_contract(datafeed, address, provider.as_ref().clone())?;
 
-        // Read max submission value
-        let max_value = contract
-            .max_submission_value()
+        let config = contract
+            .read_config()
             .await
-            .with_context(|| "Failed to read maxSubmissionValue from contract")?;
-
-        let config = ContractConfig {
-            decimals,
-            min_value,
-            max_value,
-        };
+            .with_context(|| "Failed to read configuration from contract")?;
 
         info!(
-            "Successfully read contract config: decimals={}, min_value={}, max_value={}",
+            "Successfully read contract config: decimals={}, min_value={:?}, max_value={:?}",
             config.decimals, config.min_value, config.max_value
         );
 
@@ -91,18 +63,19 @@ impl<'a> ContractConfigReader<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy::primitives::I256;
 
     #[test]
     fn test_contract_config_struct() {
         let config = ContractConfig {
             decimals: 8,
-            min_value: I256::try_from(-1000000).unwrap(),
-            max_value: I256::try_from(1000000).unwrap(),
+            min_value: Some(I256::try_from(-1000000).unwrap()),
+            max_value: Some(I256::try_from(1000000).unwrap()),
         };
 
         assert_eq!(config.decimals, 8);
-        assert_eq!(config.min_value, I256::try_from(-1000000).unwrap());
-        assert_eq!(config.max_value, I256::try_from(1000000).unwrap());
+        assert_eq!(config.min_value, Some(I256::try_from(-1000000).unwrap()));
+        assert_eq!(config.max_value, Some(I256::try_from(1000000).unwrap()));
     }
 
     #[test]
@@ -111,12 +84,12 @@ mod tests {
         let large_value = I256::try_from(10000000000000000000i128).unwrap();
         let config = ContractConfig {
             decimals: 6,
-            min_value: I256::ZERO,
-            max_value: large_value,
+            min_value: Some(I256::ZERO),
+            max_value: Some(large_value),
         };
 
         assert_eq!(config.decimals, 6);
-        assert_eq!(config.min_value, I256::ZERO);
-        assert_eq!(config.max_value, large_value);
+        assert_eq!(config.min_value, Some(I256::ZERO));
+        assert_eq!(config.max_value, Some(large_value));
     }
 }
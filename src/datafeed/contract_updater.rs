@@ -1,26 +1,57 @@
 use alloy::{
     network::{Ethereum, EthereumWallet},
-    primitives::{I256, U256},
-    providers::{Provider, ProviderBuilder, RootProvider},
+    primitives::{Address, Bytes, I256},
+    providers::{Provider, ProviderBuilder},
+    rpc::client::RpcClient,
     signers::local::PrivateKeySigner,
-    transports::http::{Client, Http},
 };
 use anyhow::{Context, Result};
 use std::sync::Arc;
 use tracing::{debug, error, info};
-use url::Url;
 
+use super::batcher::SubmissionBatcher;
 use super::contract_utils::{
-    calculate_deviation_percentage, create_contract_with_provider, current_timestamp, errors,
-    parse_address, scale_value_for_contract, validate_value_bounds,
+    calculate_deviation_percentage, current_timestamp, errors, parse_address,
+    scale_value_for_contract, validate_value_bounds,
 };
 use crate::config::models::{Datafeed, OmikujiConfig};
-use crate::contracts::FluxAggregatorContract;
+use crate::contracts::{
+    create_oracle_contract, CallSubmitter, Ineligible, OracleContract, RevertDecoder, RoundData,
+};
 use crate::database::TransactionLogRepository;
 use crate::gas_price::GasPriceManager;
+use crate::metrics::ContractMetrics;
 use crate::metrics::{FeedMetrics, SkipReason, UpdateMetrics, UpdateReason};
-use crate::network::NetworkManager;
-use crate::utils::{TransactionContext, TransactionHandler};
+use crate::network::{FailoverTransport, NetworkManager, TrackedOwner, TransactionTracker};
+use crate::utils::{DryRunHandler, DryRunSubmission, TransactionContext, TransactionHandler};
+
+/// Creates a provider signing with the network's wallet
+pub(crate) fn signer_provider(
+    network_manager: &NetworkManager,
+    network_name: &str,
+) -> Result<impl Provider<FailoverTransport, Ethereum> + Clone> {
+    // Get the private key and the network's transport
+    let private_key = network_manager
+        .get_private_key(network_name)
+        .with_context(|| format!("{} {}", errors::NO_SIGNER_AVAILABLE, network_name))?;
+
+    let transport = network_manager.get_transport(network_name)?;
+
+    // Parse the private key
+    let signer = private_key
+        .parse::<PrivateKeySigner>()
+        .with_context(|| "Failed to parse private key as signer")?;
+
+    let wallet = EthereumWallet::from(signer);
+
+    // Create a provider with wallet, failing over like the read provider
+    let provider_with_wallet = ProviderBuilder::new()
+        .with_recommended_fillers()
+        .wallet(wallet)
+        .on_client(RpcClient::new(transport, false));
+
+    Ok(provider_with_wallet)
+}
 
 /// Handles contract updates based on time and deviation thresholds
 pub struct ContractUpdater<'a> {
@@ -28,6 +59,8 @@ pub struct ContractUpdater<'a> {
     config: &'a OmikujiConfig,
     tx_log_repo: Option<Arc<TransactionLogRepository>>,
     gas_price_manager: Option<&'a Arc<GasPriceManager>>,
+    batcher: Option<&'a Arc<SubmissionBatcher>>,
+    tracker: Option<&'a Arc<TransactionTracker>>,
 }
 
 impl<'a> ContractUpdater<'a> {
@@ -38,6 +71,8 @@ impl<'a> ContractUpdater<'a> {
             config,
             tx_log_repo: None,
             gas_price_manager: None,
+            batcher: None,
+            tracker: None,
         }
     }
 
@@ -52,6 +87,8 @@ impl<'a> ContractUpdater<'a> {
             config,
             tx_log_repo: Some(tx_log_repo),
             gas_price_manager: None,
+            batcher: None,
+            tracker: None,
         }
     }
 
@@ -61,77 +98,102 @@ impl<'a> ContractUpdater<'a> {
         self
     }
 
+    /// Sets the batcher used on networks with batching enabled
+    pub fn with_batcher(mut self, batcher: &'a Arc<SubmissionBatcher>) -> Self {
+        self.batcher = Some(batcher);
+        self
+    }
+
+    /// Sets the tracker persisting sent transactions until they resolve
+    pub fn with_tracker(mut self, tracker: &'a Arc<TransactionTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
     /// Gets the network configuration for a datafeed
-    fn get_network_config(&self, datafeed: &Datafeed) -> Result<&crate::config::models::Network> {
-        self.config
+    ///
+    /// The datafeed's gas_config, if set, replaces the network's.
+    fn get_network_config(&self, datafeed: &Datafeed) -> Result<crate::config::models::Network> {
+        let mut network = self
+            .config
             .networks
             .iter()
             .find(|n| n.name == datafeed.networks)
+            .cloned()
             .ok_or_else(|| {
                 anyhow::anyhow!("Network {} not found in configuration", datafeed.networks)
-            })
+            })?;
+
+        if let Some(gas_config) = &datafeed.gas_config {
+            network.gas_config = gas_config.clone();
+        }
+
+        Ok(network)
     }
 
-    /// Gets a contract instance with provider for read operations
-    async fn get_contract_for_read(
-        &self,
-        datafeed: &Datafeed,
-    ) -> Result<FluxAggregatorContract<Http<Client>, RootProvider<Http<Client>>>> {
+    /// Address the oracle contract sees as the sender of submissions
+    ///
+    /// With batching enabled on the network this is the multicall contract,
+    /// otherwise the wallet.
+    fn submitter_address(&self, datafeed: &Datafeed) -> Option<Address> {
+        let batching = self
+            .config
+            .networks
+            .iter()
+            .find(|n| n.name == datafeed.networks)
+            .map(|n| &n.batching);
+
+        match batching {
+            Some(batching) if batching.enabled => parse_address(&batching.multicall_address).ok(),
+            _ => self
+                .network_manager
+                .get_wallet_address(&datafeed.networks)
+                .ok(),
+        }
+    }
+
+    /// Gets a contract instance of the datafeed's contract type for read operations
+    async fn get_contract_for_read(&self, datafeed: &Datafeed) -> Result<Box<dyn OracleContract>> {
         let provider = self.network_manager.get_provider(&datafeed.networks)?;
         let address = parse_address(&datafeed.contract_address)?;
-        Ok(create_contract_with_provider(
-            address,
-            provider.as_ref().clone(),
-        ))
+        create_oracle_contract(datafeed, address, provider.as_ref().clone())
+    }
+
+    /// Reads the latest round of the datafeed's contract
+    ///
+    /// Goes through the network's read quorum if it has one, as update decisions
+    /// are based on this state.
+    async fn read_round_data(&self, datafeed: &Datafeed) -> Result<RoundData> {
+        let provider = self
+            .network_manager
+            .get_quorum_provider(&datafeed.networks)?;
+        let address = parse_address(&datafeed.contract_address)?;
+        create_oracle_contract(datafeed, address, provider.as_ref().clone())?
+            .latest_round_data()
+            .await
     }
 
     /// Creates a provider with signer for write operations
     async fn create_signer_provider(
         &self,
         network_name: &str,
-    ) -> Result<impl Provider<Http<Client>, Ethereum> + Clone> {
-        // Get the private key and RPC URL
-        let private_key = self
-            .network_manager
-            .get_private_key(network_name)
-            .with_context(|| format!("{} {}", errors::NO_SIGNER_AVAILABLE, network_name))?;
-
-        let rpc_url = self.network_manager.get_rpc_url(network_name)?;
-
-        // Parse the private key
-        let signer = private_key
-            .parse::<PrivateKeySigner>()
-            .with_context(|| "Failed to parse private key as signer")?;
-
-        let wallet = EthereumWallet::from(signer);
-
-        // Create a provider with wallet
-        let url =
-            Url::parse(rpc_url).with_context(|| format!("Failed to parse RPC URL: {rpc_url}"))?;
-
-        let provider_with_wallet = ProviderBuilder::new()
-            .with_recommended_fillers()
-            .wallet(wallet)
-            .on_http(url);
-
-        Ok(provider_with_wallet)
+    ) -> Result<impl Provider<FailoverTransport, Ethereum> + Clone> {
+        signer_provider(self.network_manager, network_name)
     }
 
     /// Checks if a contract update is needed based on time elapsed
     /// Returns true if minimum_update_frequency has passed since last update
     pub async fn should_update_based_on_time(&self, datafeed: &Datafeed) -> Result<bool> {
-        // Get contract instance
-        let contract = self.get_contract_for_read(datafeed).await?;
-
         // Get latest timestamp from contract
-        let latest_timestamp = contract
-            .latest_timestamp()
+        let latest_timestamp = self
+            .read_round_data(datafeed)
             .await
-            .with_context(|| "Failed to get latest timestamp from contract")?;
+            .with_context(|| "Failed to get latest timestamp from contract")?
+            .updated_at;
 
         // Get current timestamp
         let now = current_timestamp()?;
-        let time_since_update = now.saturating_sub(latest_timestamp.to::<u64>());
+        let time_since_update = now.saturating_sub(latest_timestamp);
 
         debug!(
             "Datafeed {}: last update {}s ago, minimum frequency {}s",
@@ -168,12 +230,9 @@ impl<'a> ContractUpdater<'a> {
         datafeed: &Datafeed,
         new_value: f64,
     ) -> Result<bool> {
-        // Get contract instance
-        let contract = self.get_contract_for_read(datafeed).await?;
-
         // Get latest answer from contract
-        let latest_answer = match contract.latest_answer().await {
-            Ok(answer) => answer,
+        let latest_answer = match self.read_round_data(datafeed).await {
+            Ok(round) => round.answer,
             Err(e) => {
                 error!(
                     "Failed to get latest answer from contract for datafeed {}: {}. Skipping deviation check.",
@@ -224,6 +283,22 @@ impl<'a> ContractUpdater<'a> {
         Ok(exceeds_threshold)
     }
 
+    /// Reads the current on-chain answer, unscaled by the datafeed decimals
+    pub async fn latest_value(&self, datafeed: &Datafeed) -> Result<f64> {
+        let latest_answer = self
+            .read_round_data(datafeed)
+            .await
+            .with_context(|| "Failed to get latest answer from contract")?
+            .answer;
+
+        let decimals = datafeed.decimals.unwrap_or(8);
+        let answer_i128: i128 = latest_answer
+            .try_into()
+            .context("Failed to convert latest answer to i128")?;
+
+        Ok(answer_i128 as f64 / 10f64.powi(decimals as i32))
+    }
+
     /// Checks if an update is needed based on either time or deviation thresholds
     /// Returns (should_update, reason) where reason describes what triggered the update
     pub async fn check_update_needed(
@@ -258,9 +333,35 @@ impl<'a> ContractUpdater<'a> {
                     Some(UpdateReason::DeviationThreshold),
                     None,
                 ),
-                (false, false) => (false, "", None, Some(SkipReason::NoDeviation)),
+                (false, false) => (
+                    false,
+                    "neither time nor deviation thresholds met",
+                    None,
+                    Some(SkipReason::NoDeviation),
+                ),
             };
 
+        // An ineligible oracle's submission would revert
+        let (should_update, reason_str, update_reason, skip_reason) = if should_update {
+            match self.check_eligibility(datafeed).await? {
+                Some(ineligible) => {
+                    info!(
+                        "Datafeed {}: skipping update on {}, {}",
+                        datafeed.name, datafeed.networks, ineligible
+                    );
+                    (
+                        false,
+                        "oracle not eligible to submit",
+                        None,
+                        Some(ineligible.skip_reason()),
+                    )
+                }
+                None => (should_update, reason_str, update_reason, skip_reason),
+            }
+        } else {
+            (should_update, reason_str, update_reason, skip_reason)
+        };
+
         // Record update decision
         UpdateMetrics::record_update_decision(
             &datafeed.name,
@@ -273,27 +374,136 @@ impl<'a> ContractUpdater<'a> {
         Ok((should_update, reason_str))
     }
 
-    /// Submits a new value to the contract
-    pub async fn submit_value(&self, datafeed: &Datafeed, value: f64) -> Result<()> {
+    /// Checks whether the wallet may submit to the datafeed's contract now
+    async fn check_eligibility(&self, datafeed: &Datafeed) -> Result<Option<Ineligible>> {
+        // Without a wallet the submission fails later with a clearer error
+        let Some(submitter) = self.submitter_address(datafeed) else {
+            return Ok(None);
+        };
+
+        let contract = self.get_contract_for_read(datafeed).await?;
+        contract
+            .check_eligibility(submitter)
+            .await
+            .with_context(|| "Failed to check oracle eligibility")
+    }
+
+    /// Records a submission skipped because the oracle became ineligible after the update check
+    fn record_ineligible(datafeed: &Datafeed, error: &anyhow::Error) -> bool {
+        let Some(ineligible) = error.downcast_ref::<Ineligible>() else {
+            return false;
+        };
+
+        info!(
+            "Datafeed {}: skipping submission on {}, {}",
+            datafeed.name, datafeed.networks, ineligible
+        );
+        UpdateMetrics::record_update_decision(
+            &datafeed.name,
+            &datafeed.networks,
+            false,
+            None,
+            Some(ineligible.skip_reason()),
+        );
+        true
+    }
+
+    /// Records a skipped round if an earlier transaction of the datafeed is
+    /// still unresolved on its network
+    async fn has_unresolved_transaction(&self, datafeed: &Datafeed) -> Result<bool> {
+        let Some(tracker) = self.tracker else {
+            return Ok(false);
+        };
+
+        let owner = TrackedOwner::datafeeds(vec![datafeed.name.clone()]);
+        if !tracker.has_unresolved(&datafeed.networks, &owner).await? {
+            return Ok(false);
+        }
+
         info!(
-            "Submitting value {} to contract {} on network {}",
-            value, datafeed.contract_address, datafeed.networks
+            "Datafeed {}: skipping submission on {}, an earlier transaction is still pending",
+            datafeed.name, datafeed.networks
+        );
+        UpdateMetrics::record_update_decision(
+            &datafeed.name,
+            &datafeed.networks,
+            false,
+            None,
+            Some(SkipReason::PendingTransaction),
         );
+        Ok(true)
+    }
 
-        // Create provider with signer
-        let provider = self.create_signer_provider(&datafeed.networks).await?;
+    /// Builds, estimates and simulates a submission without broadcasting it
+    async fn simulate_value(&self, datafeed: &Datafeed, value: f64) -> Result<()> {
+        let contract = self.get_contract_for_read(datafeed).await?;
 
-        // Create contract instance with the signing provider
-        let address = parse_address(&datafeed.contract_address)?;
-        let contract = create_contract_with_provider(address, provider);
+        let decimals = datafeed.decimals.unwrap_or(8);
+        let scaled_value = scale_value_for_contract(value, decimals);
+        validate_value_bounds(scaled_value, datafeed)?;
+        let answer =
+            I256::try_from(scaled_value).context("Failed to convert scaled value to I256")?;
 
-        // Get current round ID
-        let latest_round = contract
-            .latest_round()
-            .await
-            .with_context(|| "Failed to get latest round from contract")?;
+        // Simulated from the address the contract sees as the sender
+        let submitter = self.submitter_address(datafeed);
 
-        let next_round = latest_round + U256::from(1);
+        let submission = match contract.submission(answer, submitter).await {
+            Ok(submission) => submission,
+            Err(e) if Self::record_ineligible(datafeed, &e) => return Ok(()),
+            Err(e) => return Err(e.context("Failed to build submission")),
+        };
+
+        let network_config = self.get_network_config(datafeed)?;
+
+        let provider = self.network_manager.get_provider(&datafeed.networks)?;
+        let (gas_estimate, simulation_error) =
+            CallSubmitter::new(contract.address(), provider.as_ref().clone())
+                .with_revert_decoder(RevertDecoder::new(&datafeed.custom_errors)?)
+                .simulate(submission.calldata, &network_config, submitter)
+                .await?;
+
+        let dry_run = DryRunSubmission {
+            context: TransactionContext::Datafeed {
+                feed_name: datafeed.name.clone(),
+            },
+            network: datafeed.networks.clone(),
+            contract_address: contract.address(),
+            round_id: submission
+                .round_id
+                .map(|round| round.saturating_to::<u64>()),
+            value: Some(value),
+            scaled_value: Some(scaled_value),
+            gas_limit: gas_estimate.gas_limit.saturating_to::<u64>(),
+            gas_price_wei: gas_estimate.max_price_per_gas().saturating_to::<u128>(),
+            simulation_error,
+        };
+
+        DryRunHandler::new(dry_run)
+            .with_gas_price_manager(self.gas_price_manager)
+            .with_tx_log_repo(self.tx_log_repo.as_ref())
+            .process()
+            .await;
+
+        Ok(())
+    }
+
+    /// Submits a new value to the contract
+    pub async fn submit_value(&self, datafeed: &Datafeed, value: f64) -> Result<()> {
+        if datafeed.dry_run {
+            return self.simulate_value(datafeed, value).await;
+        }
+
+        if self.has_unresolved_transaction(datafeed).await? {
+            return Ok(());
+        }
+
+        info!(
+            "Submitting value {} to {} contract {} on network {}",
+            value, datafeed.contract_type, datafeed.contract_address, datafeed.networks
+        );
+
+        // Create contract instance for building the submission
+        let contract = self.get_contract_for_read(datafeed).await?;
 
         // Convert value to contract format
         let decimals = datafeed.decimals.unwrap_or(8);
@@ -303,14 +513,42 @@ impl<'a> ContractUpdater<'a> {
         validate_value_bounds(scaled_value, datafeed)?;
 
         // Convert to I256 for contract
-        let submission =
+        let answer =
             I256::try_from(scaled_value).context("Failed to convert scaled value to I256")?;
 
-        info!(
-            "Submitting to round {} with value {} (scaled from {})",
-            next_round, submission, value
+        let submission = match contract
+            .submission(answer, self.submitter_address(datafeed))
+            .await
+        {
+            Ok(submission) => submission,
+            Err(e) if Self::record_ineligible(datafeed, &e) => return Ok(()),
+            Err(e) => return Err(e.context("Failed to build submission")),
+        };
+
+        match submission.round_id {
+            Some(round_id) => info!(
+                "Submitting to round {} with value {} (scaled from {})",
+                round_id, answer, value
+            ),
+            None => info!("Submitting value {} (scaled from {})", answer, value),
+        }
+
+        // Record update attempt
+        UpdateMetrics::record_update_attempt(
+            &datafeed.name,
+            &datafeed.networks,
+            false, // Will update to true if successful
         );
 
+        if let Some(batcher) = self.batcher.filter(|b| b.is_batched(&datafeed.networks)) {
+            return self
+                .submit_batched(batcher, datafeed, contract.address(), submission.calldata)
+                .await;
+        }
+
+        // Create provider with signer
+        let provider = self.create_signer_provider(&datafeed.networks).await?;
+
         // Get network configuration for gas settings
         let network_config = self.get_network_config(datafeed)?;
 
@@ -320,19 +558,18 @@ impl<'a> ContractUpdater<'a> {
             .get_wallet_address(&datafeed.networks)
             .ok(); // It's optional, so we use ok() to convert Result to Option
 
-        // Record update attempt
-        UpdateMetrics::record_update_attempt(
-            &datafeed.name,
-            &datafeed.networks,
-            false, // Will update to true if successful
-        );
+        let mut submitter = CallSubmitter::new(contract.address(), provider)
+            .with_revert_decoder(RevertDecoder::new(&datafeed.custom_errors)?)
+            .with_nonce_manager(Arc::clone(self.network_manager.nonce_manager()));
+        if let Some(tracker) = self.tracker {
+            submitter = submitter.with_tracker(Arc::clone(tracker));
+        }
 
         // Submit the transaction with gas estimation
-        match contract
-            .submit_price_with_gas_estimation(
-                next_round,
-                submission,
-                network_config,
+        match submitter
+            .submit_with_gas_estimation(
+                submission.calldata,
+                &network_config,
                 &datafeed.name,
                 self.tx_log_repo.clone(),
                 wallet_address,
@@ -344,8 +581,17 @@ impl<'a> ContractUpdater<'a> {
                 let context = TransactionContext::Datafeed {
                     feed_name: datafeed.name.clone(),
                 };
+                let l1_fee = self
+                    .network_manager
+                    .get_l1_fee(
+                        &datafeed.networks,
+                        receipt.transaction_hash,
+                        receipt.effective_gas_price,
+                    )
+                    .await;
 
                 TransactionHandler::new(receipt, context, datafeed.networks.clone())
+                    .with_l1_fee(l1_fee)
                     .with_gas_price_manager(self.gas_price_manager)
                     .with_tx_log_repo(self.tx_log_repo.as_ref())
                     .process()
@@ -367,6 +613,75 @@ impl<'a> ContractUpdater<'a> {
         }
     }
 
+    /// Queues a submission for the network's next batch and waits until it is mined
+    async fn submit_batched(
+        &self,
+        batcher: &SubmissionBatcher,
+        datafeed: &Datafeed,
+        target: Address,
+        calldata: Bytes,
+    ) -> Result<()> {
+        match batcher
+            .submit(
+                &datafeed.networks,
+                &datafeed.name,
+                target,
+                calldata,
+                RevertDecoder::new(&datafeed.custom_errors)?,
+            )
+            .await
+        {
+            Ok(batch) => {
+                info!(
+                    "Datafeed {}: submitted in a batch of {}, gas share {}",
+                    datafeed.name, batch.batch_size, batch.gas_used
+                );
+
+                let context = TransactionContext::Datafeed {
+                    feed_name: datafeed.name.clone(),
+                };
+
+                TransactionHandler::new(batch.receipt, context, datafeed.networks.clone())
+                    .with_gas_used(batch.gas_used)
+                    .with_l1_fee(batch.l1_fee)
+                    .with_gas_price_manager(self.gas_price_manager)
+                    .with_tx_log_repo(self.tx_log_repo.as_ref())
+                    .process()
+                    .await
+            }
+            Err(e) => {
+                error!("Failed to submit value in a batch: {}", e);
+
+                Err(anyhow::anyhow!(
+                    "{}: {}",
+                    errors::CONTRACT_SUBMISSION_FAILED,
+                    e
+                ))
+            }
+        }
+    }
+
+    /// Updates the payment the oracle can withdraw, for contracts that pay oracles
+    async fn update_payment_metrics(
+        &self,
+        datafeed: &Datafeed,
+        contract: &dyn OracleContract,
+        oracle: Address,
+    ) {
+        match contract.withdrawable_payment(oracle).await {
+            Ok(Some(payment)) => ContractMetrics::update_withdrawable_payment(
+                &datafeed.name,
+                &datafeed.networks,
+                f64::from(payment),
+            ),
+            Ok(None) => {}
+            Err(e) => debug!(
+                "Failed to read withdrawable payment for {} on {}: {}",
+                datafeed.name, datafeed.networks, e
+            ),
+        }
+    }
+
     /// Read current contract state and update metrics
     pub async fn update_contract_metrics(
         &self,
@@ -376,23 +691,15 @@ impl<'a> ContractUpdater<'a> {
         // Get contract instance
         let contract = self.get_contract_for_read(datafeed).await?;
 
-        // Get latest answer from contract
-        let latest_answer = contract
-            .latest_answer()
-            .await
-            .with_context(|| "Failed to get latest answer from contract")?;
-
-        // Get latest timestamp
-        let latest_timestamp = contract
-            .latest_timestamp()
-            .await
-            .with_context(|| "Failed to get latest timestamp from contract")?;
-
-        // Get latest round
-        let latest_round = contract
-            .latest_round()
+        // Get latest answer, timestamp and round from contract
+        let RoundData {
+            round_id: latest_round,
+            answer: latest_answer,
+            updated_at: latest_timestamp,
+        } = self
+            .read_round_data(datafeed)
             .await
-            .with_context(|| "Failed to get latest round from contract")?;
+            .with_context(|| "Failed to get latest round data from contract")?;
 
         // Convert contract value from scaled integer to float
         let decimals = datafeed.decimals.unwrap_or(8);
@@ -407,8 +714,8 @@ impl<'a> ContractUpdater<'a> {
             &datafeed.name,
             &datafeed.networks,
             contract_value,
-            latest_round.to::<u64>(),
-            latest_timestamp.to::<u64>(),
+            latest_round.saturating_to::<u64>(),
+            latest_timestamp,
         );
 
         // Calculate and update deviation
@@ -419,6 +726,11 @@ impl<'a> ContractUpdater<'a> {
             contract_value,
         );
 
+        if let Some(submitter) = self.submitter_address(datafeed) {
+            self.update_payment_metrics(datafeed, contract.as_ref(), submitter)
+                .await;
+        }
+
         debug!(
             "Updated contract metrics for {}: value={}, round={}, timestamp={}",
             datafeed.name, contract_value, latest_round, latest_timestamp
@@ -441,6 +753,9 @@ mod tests {
             check_frequency: 60,
             contract_address: "0x1234567890123456789012345678901234567890".to_string(),
             contract_type: "fluxmon".to_string(),
+            setter_signature: None,
+            custom_abi: None,
+            custom_errors: Vec::new(),
             read_contract_config: false,
             decimals: Some(8),
             min_value: Some(I256::try_from(-1000000000).unwrap()),
@@ -451,6 +766,17 @@ mod tests {
             feed_json_path: "data.price".to_string(),
             feed_json_path_timestamp: Some("data.timestamp".to_string()),
             data_retention_days: 7,
+            request: Default::default(),
+            sources: vec![],
+            aggregation: Default::default(),
+            min_sources: 1,
+            transform: None,
+            outlier_guard: None,
+            dry_run: false,
+            gas_config: None,
+            deployments: vec![],
+            stream: None,
+            onchain: None,
         }
     }
 
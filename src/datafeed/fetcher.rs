@@ -1,14 +1,26 @@
-use anyhow::Result;
-use reqwest::Client;
+use anyhow::{Context, Result};
+use backoff::backoff::Backoff;
+use backoff::ExponentialBackoffBuilder;
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER};
+use reqwest::{Client, Method, RequestBuilder};
 use serde_json::Value;
-use std::time::Instant;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
-use crate::metrics::DatasourceMetrics;
+use super::rate_limiter::RateLimiter;
+use super::secrets::SecretResolver;
+use crate::config::models::{FetchCacheConfig, FetchRetryConfig, RateLimitConfig, RequestConfig};
+use crate::metrics::{DatasourceMetrics, PerformanceMetrics};
+use crate::wallet::KeyStorage;
+
+/// Cache name used for fetch cache metrics
+const FETCH_CACHE_NAME: &str = "datasource_fetch";
 
 /// Errors that can occur when fetching data
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum FetchError {
     #[error("HTTP error with status code: {0}")]
     Http(u16),
@@ -20,42 +32,320 @@ pub enum FetchError {
     Json(String),
 }
 
+/// A failed request attempt, with the delay requested by a `Retry-After` header
+struct AttemptError {
+    error: anyhow::Error,
+    retry_after: Option<Duration>,
+}
+
+impl From<FetchError> for AttemptError {
+    fn from(error: FetchError) -> Self {
+        Self {
+            error: error.into(),
+            retry_after: None,
+        }
+    }
+}
+
+impl From<anyhow::Error> for AttemptError {
+    fn from(error: anyhow::Error) -> Self {
+        Self {
+            error,
+            retry_after: None,
+        }
+    }
+}
+
+/// Returns the metric label for errors that are worth retrying
+fn retry_reason(error: &anyhow::Error) -> Option<&'static str> {
+    match error.downcast_ref::<FetchError>()? {
+        FetchError::Network(_) => Some("network"),
+        FetchError::Http(429) => Some("rate_limited"),
+        FetchError::Http(500..=599) => Some("server_error"),
+        _ => None,
+    }
+}
+
+/// Parses a `Retry-After` header given in seconds or as an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.timestamp() - chrono::Utc::now().timestamp();
+    Some(Duration::from_secs(delay.max(0) as u64))
+}
+
+/// Last response for a URL and request settings, shared between datafeeds
+struct CachedResponse {
+    result: Result<Value, FetchError>,
+    fetched_at: Instant,
+}
+
+/// Cache slot for one URL and request settings. The async lock is held while
+/// fetching, so concurrent requests wait for the response instead of sending their own.
+type CacheSlot = Arc<tokio::sync::Mutex<Option<CachedResponse>>>;
+
 /// Fetches JSON data from a given URL
 pub struct Fetcher {
     client: Client,
+    secrets: SecretResolver,
+    /// Time successful responses are reused, `None` when the cache is disabled
+    cache_ttl: Option<Duration>,
+    cache: Mutex<HashMap<(String, RequestConfig), CacheSlot>>,
+    /// Retry settings, `None` for a single attempt per request
+    retry: Option<FetchRetryConfig>,
+    rate_limiter: RateLimiter,
 }
 
 impl Fetcher {
     /// Creates a new Fetcher with a reusable HTTP client
     pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
+        Self {
+            client: Self::build_client(crate::constants::network::HTTP_REQUEST_TIMEOUT_SECS),
+            secrets: SecretResolver::new(),
+            cache_ttl: None,
+            cache: Mutex::new(HashMap::new()),
+            retry: None,
+            rate_limiter: RateLimiter::default(),
+        }
+    }
+
+    fn build_client(timeout_secs: u64) -> Client {
+        Client::builder()
+            .timeout(Duration::from_secs(timeout_secs))
             .build()
-            .expect("Failed to create HTTP client");
+            .expect("Failed to create HTTP client")
+    }
+
+    /// Retries network errors, 5xx and 429 responses with exponential backoff
+    pub fn with_retry(mut self, config: &FetchRetryConfig) -> Self {
+        self.client = Self::build_client(config.timeout_secs);
+        self.retry = Some(config.clone());
+        self
+    }
+
+    /// Limits the request rate to the configured hosts
+    pub fn with_rate_limits(mut self, limits: &BTreeMap<String, RateLimitConfig>) -> Self {
+        self.rate_limiter = RateLimiter::new(limits);
+        self
+    }
+
+    /// Enables the fetch cache, so datafeeds sharing a source share its responses
+    pub fn with_cache(mut self, config: &FetchCacheConfig) -> Self {
+        self.cache_ttl = config.enabled.then(|| Duration::from_millis(config.ttl_ms));
+        self
+    }
+
+    /// Sets the key storage backend used to resolve `${secret:NAME}` placeholders
+    pub fn with_key_storage(mut self, key_storage: Arc<dyn KeyStorage>) -> Self {
+        self.secrets = SecretResolver::with_key_storage(key_storage);
+        self
+    }
 
-        Self { client }
+    /// Returns the resolver used for `${...}` placeholders in request templates
+    pub fn secrets(&self) -> &SecretResolver {
+        &self.secrets
     }
 
     /// Fetches JSON data from the specified URL
     /// Returns the parsed JSON value on success
     pub async fn fetch_json(&self, url: &str, feed_name: &str, network: &str) -> Result<Value> {
+        self.fetch_json_with_request(url, &RequestConfig::default(), feed_name, network)
+            .await
+    }
+
+    /// Fetches JSON data from the specified URL using custom request settings
+    ///
+    /// Only the configured URL is used in logs and metric labels; resolved
+    /// header values, query parameters and bodies are never logged.
+    ///
+    /// With the cache enabled, requests with the same URL and request settings
+    /// are coalesced and successful responses are reused within the TTL.
+    pub async fn fetch_json_with_request(
+        &self,
+        url: &str,
+        request: &RequestConfig,
+        feed_name: &str,
+        network: &str,
+    ) -> Result<Value> {
+        let Some(ttl) = self.cache_ttl else {
+            return self.fetch_uncached(url, request, feed_name, network).await;
+        };
+
+        let requested_at = Instant::now();
+
+        // Placeholders are part of the key unresolved, so resolved secrets are never kept
+        let slot = {
+            let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+            cache
+                .entry((url.to_string(), request.clone()))
+                .or_default()
+                .clone()
+        };
+
+        let mut cached = slot.lock().await;
+
+        if let Some(entry) = cached.as_ref() {
+            // Responses completed while this request was waiting are shared,
+            // including errors; successful responses are also reused within the TTL
+            let coalesced = entry.fetched_at >= requested_at;
+            let fresh = entry.result.is_ok() && entry.fetched_at.elapsed() < ttl;
+
+            if coalesced || fresh {
+                PerformanceMetrics::record_cache_operation(FETCH_CACHE_NAME, "get", true);
+                debug!(
+                    "Using shared response from {} for feed {}/{}",
+                    url, feed_name, network
+                );
+                return entry.result.clone().map_err(Into::into);
+            }
+        }
+
+        PerformanceMetrics::record_cache_operation(FETCH_CACHE_NAME, "get", false);
+
+        let result = self.fetch_uncached(url, request, feed_name, network).await;
+
+        // Errors other than FetchError (e.g. unresolvable secrets) are not shared
+        *cached = match &result {
+            Ok(json) => Some(CachedResponse {
+                result: Ok(json.clone()),
+                fetched_at: Instant::now(),
+            }),
+            Err(e) => e
+                .downcast_ref::<FetchError>()
+                .map(|fetch_error| CachedResponse {
+                    result: Err(fetch_error.clone()),
+                    fetched_at: Instant::now(),
+                }),
+        };
+
+        result
+    }
+
+    /// Sends the request and parses the JSON response, bypassing the cache
+    ///
+    /// Each attempt waits for the host's rate limit; retryable failures are
+    /// retried with exponential backoff and jitter, or after `Retry-After`.
+    async fn fetch_uncached(
+        &self,
+        url: &str,
+        request: &RequestConfig,
+        feed_name: &str,
+        network: &str,
+    ) -> Result<Value> {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(str::to_string));
+        let mut backoff = None;
+        let mut retries = 0;
+
+        loop {
+            if let Some(host) = &host {
+                let waited = self.rate_limiter.acquire(host).await;
+                if !waited.is_zero() {
+                    debug!(
+                        "Request to {} for feed {}/{} throttled for {:.3}s",
+                        host,
+                        feed_name,
+                        network,
+                        waited.as_secs_f64()
+                    );
+                    DatasourceMetrics::record_throttle_wait(feed_name, network, host, waited);
+                }
+            }
+
+            let failure = match self.fetch_once(url, request, feed_name, network).await {
+                Ok(json) => return Ok(json),
+                Err(failure) => failure,
+            };
+
+            let Some(retry) = &self.retry else {
+                return Err(failure.error);
+            };
+            let Some(reason) = retry_reason(&failure.error) else {
+                return Err(failure.error);
+            };
+            if retries >= retry.max_retries {
+                return Err(failure.error);
+            }
+
+            let max_backoff = Duration::from_millis(retry.max_backoff_ms);
+            let delay = match failure.retry_after {
+                Some(delay) if delay > max_backoff => {
+                    warn!(
+                        "{} asked to retry after {}s, longer than the maximum backoff, not retrying",
+                        url,
+                        delay.as_secs()
+                    );
+                    return Err(failure.error);
+                }
+                Some(delay) => delay,
+                None => backoff
+                    .get_or_insert_with(|| {
+                        ExponentialBackoffBuilder::new()
+                            .with_initial_interval(Duration::from_millis(retry.initial_backoff_ms))
+                            .with_max_interval(max_backoff)
+                            .with_max_elapsed_time(None)
+                            .build()
+                    })
+                    .next_backoff()
+                    .unwrap_or(max_backoff),
+            };
+
+            retries += 1;
+            warn!(
+                "Request to {} for feed {}/{} failed: {}, retrying in {:.1}s ({}/{})",
+                url,
+                feed_name,
+                network,
+                failure.error,
+                delay.as_secs_f64(),
+                retries,
+                retry.max_retries
+            );
+            DatasourceMetrics::record_retry(feed_name, network, reason, delay);
+
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Sends a single request attempt and parses the JSON response
+    async fn fetch_once(
+        &self,
+        url: &str,
+        request: &RequestConfig,
+        feed_name: &str,
+        network: &str,
+    ) -> Result<Value, AttemptError> {
+        let method = request.method.to_uppercase();
+
         debug!(
-            "Fetching data from: {} for feed {}/{}",
-            url, feed_name, network
+            "Fetching data from: {} for feed {}/{} ({} with headers [{}])",
+            url,
+            feed_name,
+            network,
+            method,
+            request
+                .headers
+                .keys()
+                .map(|name| format!("{name}: <redacted>"))
+                .collect::<Vec<_>>()
+                .join(", ")
         );
 
         let start_time = Instant::now();
 
-        let response = match self
-            .client
-            .get(url)
-            .header("Accept", "application/json")
-            .send()
-            .await
-        {
+        let builder = self.build_request(url, request).await?;
+
+        let response = match builder.send().await {
             Ok(resp) => resp,
             Err(e) => {
                 let duration = start_time.elapsed();
+                // Strip the URL, it may carry resolved query parameters
+                let e = e.without_url();
                 error!("Network error fetching from {}: {}", url, e);
 
                 // Record HTTP error metric
@@ -86,14 +376,23 @@ impl Fetcher {
             DatasourceMetrics::record_http_request(
                 feed_name,
                 network,
-                "GET",
+                &method,
                 url,
                 status.as_u16(),
                 duration,
                 content_length,
             );
 
-            return Err(FetchError::Http(status.as_u16()).into());
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after);
+
+            return Err(AttemptError {
+                error: FetchError::Http(status.as_u16()).into(),
+                retry_after,
+            });
         }
 
         // Parse JSON and measure parsing time
@@ -107,7 +406,7 @@ impl Fetcher {
                 DatasourceMetrics::record_http_request(
                     feed_name,
                     network,
-                    "GET",
+                    &method,
                     url,
                     status.as_u16(),
                     total_duration,
@@ -160,6 +459,46 @@ impl Fetcher {
         debug!("Successfully fetched and parsed JSON data");
         Ok(json)
     }
+
+    /// Builds the HTTP request, resolving placeholders in query parameters, headers and body
+    async fn build_request(&self, url: &str, request: &RequestConfig) -> Result<RequestBuilder> {
+        let method = Method::from_bytes(request.method.to_uppercase().as_bytes())
+            .with_context(|| format!("Invalid HTTP method '{}'", request.method))?;
+
+        let mut builder = self
+            .client
+            .request(method, url)
+            .header("Accept", "application/json");
+
+        if !request.query_params.is_empty() {
+            let mut query = Vec::with_capacity(request.query_params.len());
+            for (name, value) in &request.query_params {
+                query.push((name.as_str(), self.secrets.resolve(value).await?));
+            }
+            builder = builder.query(&query);
+        }
+
+        let mut has_content_type = false;
+        for (name, value) in &request.headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("Invalid header name '{name}'"))?;
+            let mut header_value = HeaderValue::from_str(&self.secrets.resolve(value).await?)
+                .with_context(|| format!("Invalid value for header '{name}'"))?;
+            header_value.set_sensitive(true);
+
+            has_content_type |= header_name == CONTENT_TYPE;
+            builder = builder.header(header_name, header_value);
+        }
+
+        if let Some(body) = &request.body {
+            if !has_content_type {
+                builder = builder.header(CONTENT_TYPE, "application/json");
+            }
+            builder = builder.body(self.secrets.resolve(body).await?);
+        }
+
+        Ok(builder)
+    }
 }
 
 impl Default for Fetcher {
@@ -1,34 +1,99 @@
 use anyhow::{Context, Result};
+use jsonpath_rust::JsonPathInst;
 use serde_json::Value;
+use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::debug;
 
-/// Extracts values from JSON using dot-notation paths
+/// Extracts values from JSON using dot-notation paths or JSONPath expressions
 pub struct JsonExtractor;
 
 impl JsonExtractor {
-    /// Extracts a float value from JSON using a dot-notation path
+    /// Extracts a float value from JSON using a dot-notation path or JSONPath expression
     ///
     /// # Arguments
     /// * `json` - The JSON value to extract from
-    /// * `path` - Dot-notation path (e.g., "RAW.ETH.USD.PRICE")
+    /// * `path` - Dot-notation path (e.g., "RAW.ETH.USD.PRICE") or JSONPath
+    ///   expression (e.g., "$.tickers[?(@.symbol=='ETHUSD')].last")
     ///
     /// # Returns
     /// The extracted float value
     pub fn extract_float(json: &Value, path: &str) -> Result<f64> {
+        if Self::is_json_path(path) {
+            let value = Self::query_single(json, path)?;
+            return Self::value_to_f64(&value, path);
+        }
+
         let components: Vec<&str> = path.split('.').collect();
         let mut current = json;
 
         for (index, component) in components.iter().enumerate() {
-            current = current.get(component).with_context(|| {
+            let next = match current {
+                Value::Array(items) => component.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => current.get(component),
+            };
+
+            current = next.with_context(|| {
                 format!(
                     "Failed to extract path component '{component}' at position {index} in path '{path}'"
                 )
             })?;
         }
 
-        // Try to extract as float
-        match current {
+        Self::value_to_f64(current, path)
+    }
+
+    /// Validates that a path is a usable dot-notation path or a well-formed JSONPath expression
+    ///
+    /// This is run at config load so syntax errors surface before any data is fetched.
+    pub fn validate_path(path: &str) -> Result<()> {
+        if path.is_empty() {
+            anyhow::bail!("JSON path must not be empty");
+        }
+
+        if Self::is_json_path(path) {
+            Self::compile(path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the path needs JSONPath evaluation rather than plain dot notation
+    fn is_json_path(path: &str) -> bool {
+        path.starts_with('$') || path.contains('[')
+    }
+
+    /// Compiles a JSONPath expression, adding the root selector to bare paths like "data[0].price"
+    fn compile(path: &str) -> Result<JsonPathInst> {
+        let expression = if path.starts_with('$') {
+            path.to_string()
+        } else {
+            format!("$.{path}")
+        };
+
+        JsonPathInst::from_str(&expression)
+            .map_err(|e| anyhow::anyhow!("Invalid JSONPath expression '{path}': {e}"))
+    }
+
+    /// Evaluates a JSONPath expression that must match exactly one value
+    fn query_single(json: &Value, path: &str) -> Result<Value> {
+        let instance = Self::compile(path)?;
+        let matches = instance.find_slice(json);
+
+        match matches.as_slice() {
+            [single] => Ok((**single).clone()),
+            [] => anyhow::bail!("JSONPath expression '{}' matched no values", path),
+            _ => anyhow::bail!(
+                "JSONPath expression '{}' matched {} values, expected exactly one",
+                path,
+                matches.len()
+            ),
+        }
+    }
+
+    /// Converts a JSON number or numeric string to f64
+    fn value_to_f64(value: &Value, path: &str) -> Result<f64> {
+        match value {
             Value::Number(n) => n
                 .as_f64()
                 .with_context(|| format!("Failed to convert number to f64 at path '{path}'")),
@@ -39,17 +104,17 @@ impl JsonExtractor {
                 anyhow::bail!(
                     "Value at path '{}' is not a number or string, found: {:?}",
                     path,
-                    current
+                    value
                 );
             }
         }
     }
 
-    /// Extracts a timestamp from JSON using a dot-notation path
+    /// Extracts a timestamp from JSON using a dot-notation path or JSONPath expression
     ///
     /// # Arguments
     /// * `json` - The JSON value to extract from
-    /// * `path` - Optional dot-notation path
+    /// * `path` - Optional dot-notation path or JSONPath expression
     ///
     /// # Returns
     /// The extracted timestamp or current time if path is None
@@ -73,8 +138,8 @@ impl JsonExtractor {
     ///
     /// # Arguments
     /// * `json` - The JSON value to extract from
-    /// * `value_path` - Dot-notation path for the value
-    /// * `timestamp_path` - Optional dot-notation path for the timestamp
+    /// * `value_path` - Dot-notation path or JSONPath expression for the value
+    /// * `timestamp_path` - Optional dot-notation path or JSONPath expression for the timestamp
     ///
     /// # Returns
     /// Tuple of (value, timestamp)
@@ -1,10 +1,13 @@
+use super::batcher::SubmissionBatcher;
 use super::contract_config::ContractConfigReader;
 use super::fetcher::Fetcher;
 use super::monitor::FeedMonitor;
+use super::transform::FeedValueStore;
 use crate::config::models::{Datafeed, OmikujiConfig};
 use crate::database::{DatabasePool, FeedLogRepository, TransactionLogRepository};
 use crate::gas_price::GasPriceManager;
-use crate::network::NetworkManager;
+use crate::network::{NetworkManager, TransactionTracker};
+use crate::wallet::KeyStorage;
 use alloy::primitives::I256;
 use std::sync::Arc;
 use tokio::task::JoinHandle;
@@ -15,9 +18,12 @@ pub struct FeedManager {
     config: OmikujiConfig,
     network_manager: Arc<NetworkManager>,
     fetcher: Arc<Fetcher>,
+    feed_values: Arc<FeedValueStore>,
     repository: Option<Arc<FeedLogRepository>>,
     tx_log_repo: Option<Arc<TransactionLogRepository>>,
     gas_price_manager: Option<Arc<GasPriceManager>>,
+    batcher: Option<Arc<SubmissionBatcher>>,
+    tracker: Option<Arc<TransactionTracker>>,
     handles: Vec<JoinHandle<()>>,
 }
 
@@ -25,16 +31,27 @@ impl FeedManager {
     /// Creates a new FeedManager with the given configuration
     pub fn new(config: OmikujiConfig, network_manager: Arc<NetworkManager>) -> Self {
         Self {
+            fetcher: Arc::new(Self::build_fetcher(&config)),
             config,
             network_manager,
-            fetcher: Arc::new(Fetcher::new()),
+            feed_values: Arc::new(FeedValueStore::new()),
             repository: None,
             tx_log_repo: None,
             gas_price_manager: None,
+            batcher: None,
+            tracker: None,
             handles: Vec::new(),
         }
     }
 
+    /// Creates the fetcher shared by all monitors from the cache, retry and rate limit settings
+    fn build_fetcher(config: &OmikujiConfig) -> Fetcher {
+        Fetcher::new()
+            .with_cache(&config.fetch_cache)
+            .with_retry(&config.fetch_retry)
+            .with_rate_limits(&config.rate_limits)
+    }
+
     /// Sets the database repository for feed logging
     pub fn with_repository(mut self, pool: DatabasePool) -> Self {
         self.repository = Some(Arc::new(FeedLogRepository::new(pool.clone())));
@@ -42,12 +59,24 @@ impl FeedManager {
         self
     }
 
+    /// Sets the key storage backend used to resolve secrets in feed request settings
+    pub fn with_key_storage(mut self, key_storage: Arc<dyn KeyStorage>) -> Self {
+        self.fetcher = Arc::new(Self::build_fetcher(&self.config).with_key_storage(key_storage));
+        self
+    }
+
     /// Sets the gas price manager for USD cost tracking
     pub fn with_gas_price_manager(mut self, gas_price_manager: Arc<GasPriceManager>) -> Self {
         self.gas_price_manager = Some(gas_price_manager);
         self
     }
 
+    /// Sets the tracker persisting sent transactions until they resolve
+    pub fn with_tracker(mut self, tracker: Arc<TransactionTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
     /// Starts monitoring all configured datafeeds
     /// Each datafeed runs in its own tokio task
     pub async fn start(&mut self) {
@@ -56,23 +85,46 @@ impl FeedManager {
             self.config.datafeeds.len()
         );
 
+        if self.config.networks.iter().any(|n| n.batching.enabled) {
+            match SubmissionBatcher::start(
+                &self.config,
+                Arc::clone(&self.network_manager),
+                self.tx_log_repo.clone(),
+                self.tracker.clone(),
+            ) {
+                Ok(batcher) => self.batcher = Some(Arc::new(batcher)),
+                Err(e) => error!("Failed to start submission batching: {}", e),
+            }
+        }
+
         let contract_reader = ContractConfigReader::new(&self.network_manager);
 
-        for mut datafeed in self.config.datafeeds.clone() {
-            // Configure the datafeed (either from contract or YAML)
-            match self
-                .configure_datafeed(&mut datafeed, &contract_reader)
-                .await
-            {
-                Ok(_) => {
-                    let handle = self.spawn_monitor(datafeed);
-                    self.handles.push(handle);
-                }
-                Err(feed_name) => {
-                    // Feed was skipped due to error - already logged
-                    info!("Skipping datafeed '{}'", feed_name);
+        for datafeed in self.config.datafeeds.clone() {
+            // Configure every deployment (either from contract or YAML)
+            let mut deployments = Vec::new();
+            for mut deployment in datafeed.expand_deployments() {
+                match self
+                    .configure_datafeed(&mut deployment, &contract_reader)
+                    .await
+                {
+                    Ok(_) => deployments.push(deployment),
+                    Err(feed_name) => {
+                        // Deployment was skipped due to error - already logged
+                        info!(
+                            "Skipping datafeed '{}' on network '{}'",
+                            feed_name, deployment.networks
+                        );
+                    }
                 }
             }
+
+            if deployments.is_empty() {
+                info!("Skipping datafeed '{}'", datafeed.name);
+                continue;
+            }
+
+            let handle = self.spawn_monitor(datafeed, deployments);
+            self.handles.push(handle);
         }
 
         info!(
@@ -90,18 +142,15 @@ impl FeedManager {
     ) -> Result<(), String> {
         if datafeed.read_contract_config {
             // Try to read from contract
-            match contract_reader
-                .read_config(&datafeed.networks, &datafeed.contract_address)
-                .await
-            {
+            match contract_reader.read_config(datafeed).await {
                 Ok(contract_config) => {
                     self.apply_contract_config(datafeed, contract_config);
                     Ok(())
                 }
                 Err(e) => {
                     error!(
-                        "Failed to read contract config for datafeed '{}': {}. Skipping this feed.",
-                        datafeed.name, e
+                        "Failed to read contract config for datafeed '{}' on network '{}': {}. Skipping this deployment.",
+                        datafeed.name, datafeed.networks, e
                     );
                     Err(datafeed.name.clone())
                 }
@@ -119,17 +168,18 @@ impl FeedManager {
         datafeed: &mut Datafeed,
         config: crate::datafeed::contract_config::ContractConfig,
     ) {
+        // Contracts without submission bounds keep the bounds from the YAML config
+        datafeed.decimals = Some(config.decimals);
+        datafeed.min_value = config.min_value.or(datafeed.min_value);
+        datafeed.max_value = config.max_value.or(datafeed.max_value);
+
         self.log_config_values(
             &datafeed.name,
             "contract config",
             config.decimals,
-            config.min_value,
-            config.max_value,
+            datafeed.min_value.unwrap_or(I256::ZERO),
+            datafeed.max_value.unwrap_or(I256::ZERO),
         );
-
-        datafeed.decimals = Some(config.decimals);
-        datafeed.min_value = Some(config.min_value);
-        datafeed.max_value = Some(config.max_value);
     }
 
     /// Logs datafeed configuration from YAML
@@ -158,8 +208,8 @@ impl FeedManager {
         );
     }
 
-    /// Spawns a monitor task for a single datafeed
-    fn spawn_monitor(&self, datafeed: Datafeed) -> JoinHandle<()> {
+    /// Spawns a monitor task for a single datafeed and its configured deployments
+    fn spawn_monitor(&self, datafeed: Datafeed, deployments: Vec<Datafeed>) -> JoinHandle<()> {
         let mut monitor = FeedMonitor::new(
             datafeed.clone(),
             Arc::clone(&self.fetcher),
@@ -167,13 +217,23 @@ impl FeedManager {
             self.config.clone(),
             self.repository.clone(),
             self.tx_log_repo.clone(),
-        );
+        )
+        .with_feed_values(Arc::clone(&self.feed_values))
+        .with_deployments(deployments);
 
         // Set gas price manager if available
         if let Some(ref gas_price_manager) = self.gas_price_manager {
             monitor = monitor.with_gas_price_manager(Arc::clone(gas_price_manager));
         }
 
+        if let Some(ref batcher) = self.batcher {
+            monitor = monitor.with_batcher(Arc::clone(batcher));
+        }
+
+        if let Some(ref tracker) = self.tracker {
+            monitor = monitor.with_tracker(Arc::clone(tracker));
+        }
+
         let feed_name = datafeed.name.clone();
 
         tokio::spawn(async move {
@@ -1,3 +1,5 @@
+pub mod aggregator;
+pub mod batcher;
 pub mod contract_config;
 pub mod contract_updater;
 pub mod contract_utils;
@@ -5,7 +7,13 @@ pub mod fetcher;
 pub mod json_extractor;
 pub mod manager;
 pub mod monitor;
+pub mod onchain;
+pub mod outlier_guard;
+pub mod rate_limiter;
+pub mod secrets;
+pub mod stream;
 #[cfg(test)]
 mod tests;
+pub mod transform;
 
 pub use manager::FeedManager;
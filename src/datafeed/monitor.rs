@@ -1,27 +1,59 @@
+use super::aggregator::{AggregationError, Aggregator, SourceValue};
+use super::batcher::SubmissionBatcher;
 use super::contract_updater::ContractUpdater;
 use super::fetcher::Fetcher;
 use super::json_extractor::JsonExtractor;
-use crate::config::models::{Datafeed, OmikujiConfig};
+use super::onchain::OnChainReader;
+use super::outlier_guard::{GuardDecision, OutlierGuard, Violation};
+use super::stream::StreamSource;
+use super::transform::{FeedValueStore, Transform};
+use crate::config::models::{Datafeed, FeedSource, OmikujiConfig, StreamConfig};
+use crate::contracts::oracle;
 use crate::database::models::NewFeedLog;
 use crate::database::{FeedLogRepository, TransactionLogRepository};
 use crate::gas_price::GasPriceManager;
-use crate::metrics::{FeedMetrics, QualityMetrics, UpdateMetrics};
-use crate::network::NetworkManager;
+use crate::metrics::{FeedMetrics, QualityMetrics, SkipReason, UpdateMetrics};
+use crate::network::{HeadTicker, NetworkManager, TransactionTracker};
 use anyhow::Result;
+use futures::future::join_all;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::time::{interval, Duration};
 use tracing::{debug, error, info, warn};
 
-/// Monitors a single datafeed, polling at regular intervals
+/// A network and contract the monitored value is submitted to
+struct Deployment {
+    /// Single-network datafeed with the deployment's overrides applied
+    datafeed: Datafeed,
+    outlier_guard: Option<OutlierGuard>,
+}
+
+impl Deployment {
+    fn new(datafeed: Datafeed) -> Self {
+        let outlier_guard = datafeed.outlier_guard.clone().map(OutlierGuard::new);
+        Self {
+            datafeed,
+            outlier_guard,
+        }
+    }
+}
+
+/// Monitors a single datafeed, polling at regular intervals or following a stream
+///
+/// The value is fetched once per check and evaluated separately for every
+/// deployment of the datafeed.
 pub struct FeedMonitor {
     pub(crate) datafeed: Datafeed,
+    deployments: Vec<Deployment>,
     fetcher: Arc<Fetcher>,
     network_manager: Arc<NetworkManager>,
     config: OmikujiConfig,
     repository: Option<Arc<FeedLogRepository>>,
     tx_log_repo: Option<Arc<TransactionLogRepository>>,
     gas_price_manager: Option<Arc<GasPriceManager>>,
+    batcher: Option<Arc<SubmissionBatcher>>,
+    tracker: Option<Arc<TransactionTracker>>,
+    feed_values: Arc<FeedValueStore>,
     last_value: Option<f64>,
     last_check_time: Option<Instant>,
 }
@@ -36,14 +68,24 @@ impl FeedMonitor {
         repository: Option<Arc<FeedLogRepository>>,
         tx_log_repo: Option<Arc<TransactionLogRepository>>,
     ) -> Self {
+        let deployments = datafeed
+            .expand_deployments()
+            .into_iter()
+            .map(Deployment::new)
+            .collect();
+
         Self {
             datafeed,
+            deployments,
             fetcher,
             network_manager,
             config,
             repository,
             tx_log_repo,
             gas_price_manager: None,
+            batcher: None,
+            tracker: None,
+            feed_values: Arc::new(FeedValueStore::new()),
             last_value: None,
             last_check_time: None,
         }
@@ -55,196 +97,609 @@ impl FeedMonitor {
         self
     }
 
+    /// Sets the batcher for networks with submission batching enabled
+    pub fn with_batcher(mut self, batcher: Arc<SubmissionBatcher>) -> Self {
+        self.batcher = Some(batcher);
+        self
+    }
+
+    /// Sets the tracker persisting sent transactions until they resolve
+    pub fn with_tracker(mut self, tracker: Arc<TransactionTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Replaces the deployments the value is submitted to
+    ///
+    /// Used once the per-deployment settings have been read from the contracts.
+    pub fn with_deployments(mut self, deployments: Vec<Datafeed>) -> Self {
+        self.deployments = deployments.into_iter().map(Deployment::new).collect();
+        self
+    }
+
+    /// Sets the store of latest feed values shared with the other monitors
+    pub fn with_feed_values(mut self, feed_values: Arc<FeedValueStore>) -> Self {
+        self.feed_values = feed_values;
+        self
+    }
+
     /// Starts monitoring the datafeed
-    /// This runs indefinitely, polling at the configured interval
+    /// This runs indefinitely, polling at the configured interval or
+    /// evaluating every tick of the configured stream
     pub async fn start(mut self) {
+        self.seed_outlier_guard().await;
+
+        match self.datafeed.stream.clone() {
+            Some(stream) => self.start_streaming(stream).await,
+            None => self.start_polling().await,
+        }
+    }
+
+    /// Polls the datafeed sources at the configured interval, or on every new
+    /// block of a network with `check_feeds_on_new_heads`
+    async fn start_polling(mut self) {
+        let heads = self.deployments.iter().find_map(|d| {
+            self.network_manager
+                .subscribe_feed_check_heads(&d.datafeed.networks)
+        });
+        let mut ticker = HeadTicker::new(heads, Duration::from_secs(self.datafeed.check_frequency));
+
+        if ticker.follows_heads() {
+            info!(
+                "Starting feed monitor for '{}' on new blocks with {}s fallback interval",
+                self.datafeed.name, self.datafeed.check_frequency
+            );
+        } else {
+            info!(
+                "Starting feed monitor for '{}' with {}s interval",
+                self.datafeed.name, self.datafeed.check_frequency
+            );
+        }
+
+        loop {
+            ticker.tick().await;
+
+            let (check_start, time_since_last_check) = self.begin_check();
+            let result = self.poll_once().await;
+            self.handle_result(result, check_start, time_since_last_check)
+                .await;
+        }
+    }
+
+    /// Evaluates every value received from the stream
+    ///
+    /// The latest value is also re-evaluated every `check_frequency` seconds so
+    /// `minimum_update_frequency` is honoured when the stream is quiet.
+    async fn start_streaming(mut self, stream: StreamConfig) {
         let mut interval = interval(Duration::from_secs(self.datafeed.check_frequency));
 
         info!(
-            "Starting feed monitor for '{}' with {}s interval",
-            self.datafeed.name, self.datafeed.check_frequency
+            "Starting streaming feed monitor for '{}' from {} with {}s heartbeat",
+            self.datafeed.name, stream.url, self.datafeed.check_frequency
         );
 
+        let mut ticks = StreamSource::new(
+            stream,
+            &self.datafeed.name,
+            self.datafeed.network_label(),
+            self.fetcher.secrets().clone(),
+        )
+        .spawn();
+
         loop {
-            interval.tick().await;
+            tokio::select! {
+                changed = ticks.changed() => {
+                    if changed.is_err() {
+                        error!("Stream for datafeed {} stopped unexpectedly", self.datafeed.name);
+                        return;
+                    }
+                }
+                _ = interval.tick() => {}
+            }
+
+            // Ticks that arrive while a value is being evaluated are coalesced into the latest one
+            let Some(tick) = *ticks.borrow_and_update() else {
+                debug!(
+                    "Datafeed {}: stream has no live value, skipping evaluation",
+                    self.datafeed.name
+                );
+                continue;
+            };
+
+            let (check_start, time_since_last_check) = self.begin_check();
+            let result = self
+                .apply_transform(tick.value)
+                .map(|value| (value, tick.timestamp));
+            self.handle_result(result, check_start, time_since_last_check)
+                .await;
+        }
+    }
 
-            // Record check interval if we have a previous check time
-            if let Some(last_check) = self.last_check_time {
-                let interval_seconds = last_check.elapsed().as_secs_f64();
+    /// Records the check interval and returns the check start time and the
+    /// seconds elapsed since the previous check
+    fn begin_check(&self) -> (Instant, f64) {
+        // Record check interval if we have a previous check time
+        if let Some(last_check) = self.last_check_time {
+            let interval_seconds = last_check.elapsed().as_secs_f64();
+            for deployment in &self.deployments {
                 UpdateMetrics::record_check_interval(
-                    &self.datafeed.name,
-                    &self.datafeed.networks,
+                    &deployment.datafeed.name,
+                    &deployment.datafeed.networks,
                     interval_seconds,
                 );
             }
+        }
 
-            let check_start = Instant::now();
+        let check_start = Instant::now();
 
-            // Calculate time since last check BEFORE updating last_check_time
-            let time_since_last_check = if let Some(last_check) = self.last_check_time {
-                last_check.elapsed().as_secs_f64()
-            } else {
-                // For the first check, use the configured check frequency
-                self.datafeed.check_frequency as f64
-            };
+        // Calculate time since last check BEFORE updating last_check_time
+        let time_since_last_check = if let Some(last_check) = self.last_check_time {
+            last_check.elapsed().as_secs_f64()
+        } else {
+            // For the first check, use the configured check frequency
+            self.datafeed.check_frequency as f64
+        };
 
-            match self.poll_once().await {
-                Ok((value, timestamp)) => {
-                    info!(
-                        "Datafeed {}: value={}, timestamp={}",
-                        self.datafeed.name, value, timestamp
-                    );
+        (check_start, time_since_last_check)
+    }
 
-                    // Update Prometheus metrics
-                    FeedMetrics::set_feed_value(
-                        &self.datafeed.name,
-                        &self.datafeed.networks,
+    /// Records, logs and stores a fetched value and updates the contract if needed
+    async fn handle_result(
+        &mut self,
+        result: Result<(f64, u64)>,
+        check_start: Instant,
+        time_since_last_check: f64,
+    ) {
+        match result {
+            Ok((value, timestamp)) => {
+                info!(
+                    "Datafeed {}: value={}, timestamp={}",
+                    self.datafeed.name, value, timestamp
+                );
+
+                let current_time = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                let last_value = self.last_value.replace(value);
+                self.last_check_time = Some(check_start);
+
+                // Run the quality checks of every deployment before anything is submitted
+                let mut deployments = std::mem::take(&mut self.deployments);
+                for deployment in &deployments {
+                    self.record_quality_metrics(
+                        &deployment.datafeed,
                         value,
                         timestamp,
+                        current_time,
+                        last_value,
+                        time_since_last_check,
                     );
+                }
+                let allowed = join_all(deployments.iter_mut().map(|deployment| {
+                    self.passes_outlier_guard(deployment, value, timestamp, current_time)
+                }))
+                .await;
+                self.deployments = deployments;
+
+                // Publish the value for transforms of other feeds
+                if allowed.contains(&true) {
+                    self.feed_values.set(&self.datafeed.name, value);
+                }
 
-                    // Update quality metrics
-                    if let Some(last_val) = self.last_value {
-                        let time_delta = time_since_last_check;
-
-                        // Record value change rate
-                        QualityMetrics::record_value_change_rate(
-                            &self.datafeed.name,
-                            &self.datafeed.networks,
-                            last_val,
-                            value,
-                            time_delta,
-                        );
+                // Deployments are independent, a slow or failing network must
+                // not hold back the submissions to the others
+                join_all(
+                    self.deployments
+                        .iter()
+                        .zip(allowed)
+                        .map(|(deployment, allowed)| {
+                            self.process_deployment(&deployment.datafeed, value, timestamp, allowed)
+                        }),
+                )
+                .await;
+            }
+            Err(e) => {
+                error!("Datafeed {}: {}", self.datafeed.name, e);
+
+                // Update last check time even on error to maintain accurate timing
+                self.last_check_time = Some(check_start);
 
-                        // Check for outliers (simple range check - could be enhanced)
-                        let expected_min = last_val * 0.5; // 50% below last value
-                        let expected_max = last_val * 2.0; // 200% of last value
-                        if value < expected_min || value > expected_max {
-                            QualityMetrics::record_outlier(
-                                &self.datafeed.name,
-                                &self.datafeed.networks,
-                                value,
-                                (expected_min, expected_max),
-                                "logged",
-                            );
-                        }
+                // Save error to database if repository is available
+                if let Some(ref repository) = self.repository {
+                    for deployment in &self.deployments {
+                        self.save_error_log(repository, &deployment.datafeed, &e)
+                            .await;
                     }
+                }
+            }
+        }
+    }
 
-                    // Update timestamp drift
-                    let current_time = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs();
-                    QualityMetrics::record_timestamp_drift(
-                        &self.datafeed.name,
-                        &self.datafeed.networks,
-                        timestamp,
-                        current_time,
+    /// Records the value and quality metrics of a deployment
+    fn record_quality_metrics(
+        &self,
+        datafeed: &Datafeed,
+        value: f64,
+        timestamp: u64,
+        current_time: u64,
+        last_value: Option<f64>,
+        time_since_last_check: f64,
+    ) {
+        // Update Prometheus metrics
+        FeedMetrics::set_feed_value(&datafeed.name, &datafeed.networks, value, timestamp);
+
+        // Record value change rate
+        if let Some(last_val) = last_value {
+            QualityMetrics::record_value_change_rate(
+                &datafeed.name,
+                &datafeed.networks,
+                last_val,
+                value,
+                time_since_last_check,
+            );
+
+            // Without an outlier guard, still flag values far from the last
+            // one (simple range check - could be enhanced)
+            if datafeed.outlier_guard.is_none() {
+                let expected_min = last_val * 0.5; // 50% below last value
+                let expected_max = last_val * 2.0; // 200% of last value
+                if value < expected_min || value > expected_max {
+                    QualityMetrics::record_outlier(
+                        &datafeed.name,
+                        &datafeed.networks,
+                        value,
+                        (expected_min, expected_max),
+                        "logged",
                     );
+                }
+            }
+        }
 
-                    self.last_value = Some(value);
-                    self.last_check_time = Some(check_start);
-
-                    // Update contract metrics (read current contract state)
-                    let updater = if let Some(ref tx_repo) = self.tx_log_repo {
-                        ContractUpdater::with_tx_logging(
-                            &self.network_manager,
-                            &self.config,
-                            tx_repo.clone(),
-                        )
-                    } else {
-                        ContractUpdater::new(&self.network_manager, &self.config)
-                    };
-
-                    if let Err(e) = updater.update_contract_metrics(&self.datafeed, value).await {
-                        error!(
-                            "Failed to update contract metrics for {}: {}",
-                            self.datafeed.name, e
-                        );
-                    }
+        // Update timestamp drift
+        QualityMetrics::record_timestamp_drift(
+            &datafeed.name,
+            &datafeed.networks,
+            timestamp,
+            current_time,
+        );
+    }
 
-                    // Save to database if repository is available
-                    if let Some(ref repository) = self.repository {
-                        debug!(
-                            "Saving feed log to database for {}: value={}, timestamp={}",
-                            self.datafeed.name, value, timestamp
-                        );
+    /// Updates the contract metrics of a deployment, stores the value and
+    /// updates the contract if needed
+    async fn process_deployment(
+        &self,
+        datafeed: &Datafeed,
+        value: f64,
+        timestamp: u64,
+        allowed: bool,
+    ) {
+        // Update contract metrics (read current contract state)
+        let updater = if let Some(ref tx_repo) = self.tx_log_repo {
+            ContractUpdater::with_tx_logging(&self.network_manager, &self.config, tx_repo.clone())
+        } else {
+            ContractUpdater::new(&self.network_manager, &self.config)
+        };
 
-                        let log = NewFeedLog {
-                            feed_name: self.datafeed.name.clone(),
-                            network_name: self.datafeed.networks.clone(),
-                            feed_value: value,
-                            feed_timestamp: timestamp as i64,
-                            error_status_code: None,
-                            network_error: false,
-                        };
-
-                        match repository.save(log).await {
-                            Ok(saved_log) => {
-                                debug!(
-                                    "Feed log saved successfully for {} with id={}",
-                                    self.datafeed.name, saved_log.id
-                                );
-                            }
-                            Err(e) => {
-                                error!("Failed to save feed log for {}: {}", self.datafeed.name, e);
-                            }
-                        }
-                    } else {
-                        debug!(
-                            "No database repository configured for feed {}, skipping database save",
-                            self.datafeed.name
-                        );
-                    }
+        if let Err(e) = updater.update_contract_metrics(datafeed, value).await {
+            error!(
+                "Failed to update contract metrics for {} on {}: {}",
+                datafeed.name, datafeed.networks, e
+            );
+        }
 
-                    // Check if contract update is needed based on time
-                    if let Err(e) = self.check_and_update_contract(value).await {
-                        error!(
-                            "Failed to update contract for datafeed {}: {}",
-                            self.datafeed.name, e
-                        );
-                    }
+        // Save to database if repository is available
+        if let Some(ref repository) = self.repository {
+            debug!(
+                "Saving feed log to database for {} on {}: value={}, timestamp={}",
+                datafeed.name, datafeed.networks, value, timestamp
+            );
+
+            let log = NewFeedLog {
+                feed_name: datafeed.name.clone(),
+                network_name: datafeed.networks.clone(),
+                feed_value: value,
+                feed_timestamp: timestamp as i64,
+                error_status_code: None,
+                network_error: false,
+            };
+
+            match repository.save(log).await {
+                Ok(saved_log) => {
+                    debug!(
+                        "Feed log saved successfully for {} with id={}",
+                        datafeed.name, saved_log.id
+                    );
                 }
                 Err(e) => {
-                    error!("Datafeed {}: {}", self.datafeed.name, e);
+                    error!("Failed to save feed log for {}: {}", datafeed.name, e);
+                }
+            }
+        } else {
+            debug!(
+                "No database repository configured for feed {}, skipping database save",
+                datafeed.name
+            );
+        }
+
+        // Check if contract update is needed based on time
+        if !allowed {
+            return;
+        }
+        if let Err(e) = self.check_and_update_contract(datafeed, value).await {
+            error!(
+                "Failed to update contract for datafeed {} on {}: {}",
+                datafeed.name, datafeed.networks, e
+            );
+        }
+    }
 
-                    // Update last check time even on error to maintain accurate timing
-                    self.last_check_time = Some(check_start);
+    /// Loads recent values from the feed log into the outlier guards' median windows
+    async fn seed_outlier_guard(&mut self) {
+        let Some(repository) = &self.repository else {
+            return;
+        };
 
-                    // Save error to database if repository is available
-                    if let Some(ref repository) = self.repository {
-                        self.save_error_log(repository, &e).await;
-                    }
+        for deployment in &mut self.deployments {
+            let (Some(guard), Some(config)) = (
+                &mut deployment.outlier_guard,
+                &deployment.datafeed.outlier_guard,
+            ) else {
+                continue;
+            };
+
+            match repository
+                .get_recent_values(
+                    &deployment.datafeed.name,
+                    &deployment.datafeed.networks,
+                    config.median_window,
+                )
+                .await
+            {
+                Ok(values) => {
+                    debug!(
+                        "Datafeed {} on {}: seeded outlier guard with {} recent values",
+                        deployment.datafeed.name,
+                        deployment.datafeed.networks,
+                        values.len()
+                    );
+                    guard.seed(values);
                 }
+                Err(e) => warn!(
+                    "Datafeed {} on {}: failed to load recent values for outlier guard: {}",
+                    deployment.datafeed.name, deployment.datafeed.networks, e
+                ),
             }
         }
     }
 
+    /// Runs the outlier checks and circuit breaker
+    /// Returns false if the value must not be submitted
+    async fn passes_outlier_guard(
+        &self,
+        deployment: &mut Deployment,
+        value: f64,
+        timestamp: u64,
+        now: u64,
+    ) -> bool {
+        let datafeed = &deployment.datafeed;
+        let Some(config) = &datafeed.outlier_guard else {
+            return true;
+        };
+
+        let onchain = if config.max_onchain_jump_pct.is_some() {
+            match ContractUpdater::new(&self.network_manager, &self.config)
+                .latest_value(datafeed)
+                .await
+            {
+                Ok(onchain) => Some(onchain),
+                Err(e) => {
+                    warn!(
+                        "Datafeed {}: skipping on-chain jump check, {}",
+                        datafeed.name, e
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let Some(guard) = &mut deployment.outlier_guard else {
+            return true;
+        };
+        let was_open = guard.is_open();
+        let decision = guard.evaluate(value, timestamp, now, onchain);
+        QualityMetrics::set_circuit_breaker_open(
+            &datafeed.name,
+            &datafeed.networks,
+            guard.is_open(),
+        );
+
+        match &decision {
+            GuardDecision::Allow => {
+                if was_open {
+                    info!(
+                        "Datafeed {}: circuit breaker closed, resuming submissions",
+                        datafeed.name
+                    );
+                }
+                return true;
+            }
+            GuardDecision::Reject(violation) => {
+                if let Violation::Stale { age_secs, .. } = violation {
+                    QualityMetrics::update_stale_data_duration(
+                        &datafeed.name,
+                        &datafeed.networks,
+                        "source_timestamp",
+                        *age_secs as f64,
+                    );
+                } else if let Some(expected_range) = violation.expected_range() {
+                    QualityMetrics::record_outlier(
+                        &datafeed.name,
+                        &datafeed.networks,
+                        value,
+                        expected_range,
+                        "rejected",
+                    );
+                }
+                warn!(
+                    "Datafeed {}: rejected value {} and opened circuit breaker: {}",
+                    datafeed.name, value, violation
+                );
+            }
+            GuardDecision::Recovering { remaining } => info!(
+                "Datafeed {}: circuit breaker open, {} more sane value(s) needed before submitting",
+                datafeed.name, remaining
+            ),
+        }
+
+        UpdateMetrics::record_update_decision(
+            &datafeed.name,
+            &datafeed.networks,
+            false,
+            None,
+            decision.skip_reason(),
+        );
+
+        false
+    }
+
     /// Performs a single poll of the datafeed
-    /// Returns (value, timestamp) on success
+    /// Returns the (value, timestamp) after aggregation and transformation on success
     async fn poll_once(&self) -> Result<(f64, u64)> {
-        // Fetch JSON from the feed URL
+        let (value, timestamp) = self.fetch_value().await?;
+        Ok((self.apply_transform(value)?, timestamp))
+    }
+
+    /// Applies the datafeed's transform expression, if configured
+    fn apply_transform(&self, value: f64) -> Result<f64> {
+        let Some(expression) = &self.datafeed.transform else {
+            return Ok(value);
+        };
+
+        let transformed = Transform::parse(expression)?.apply(value, &self.feed_values)?;
+
+        debug!(
+            "Datafeed {}: transformed {} to {} using '{}'",
+            self.datafeed.name, value, transformed, expression
+        );
+
+        Ok(transformed)
+    }
+
+    /// Fetches the raw value of the datafeed, aggregating multiple sources if configured
+    async fn fetch_value(&self) -> Result<(f64, u64)> {
+        let sources = self.datafeed.effective_sources();
+
+        // Single source: propagate fetch errors unchanged so they are logged with their status
+        if let [source] = sources.as_slice() {
+            return self.poll_source(source).await;
+        }
+
+        // Fetch all sources concurrently
+        let results = join_all(sources.iter().map(|source| self.poll_source(source))).await;
+
+        let mut values = Vec::with_capacity(sources.len());
+        for (source, result) in sources.iter().zip(results) {
+            match result {
+                Ok((value, timestamp)) => values.push(SourceValue {
+                    url: source.describe(),
+                    value,
+                    timestamp,
+                    weight: source.weight,
+                }),
+                Err(e) => warn!(
+                    "Datafeed {}: source {} failed: {}",
+                    self.datafeed.name,
+                    source.describe(),
+                    e
+                ),
+            }
+        }
+
+        let (value, timestamp) = match Aggregator::aggregate(
+            &values,
+            &self.datafeed.aggregation,
+            self.datafeed.min_sources,
+        ) {
+            Ok(result) => result,
+            Err(e) => {
+                if let Some(AggregationError::InsufficientSources { .. }) = e.downcast_ref() {
+                    for deployment in &self.deployments {
+                        UpdateMetrics::record_update_decision(
+                            &deployment.datafeed.name,
+                            &deployment.datafeed.networks,
+                            false,
+                            None,
+                            Some(SkipReason::InsufficientSources),
+                        );
+                    }
+                }
+                return Err(e);
+            }
+        };
+
+        let agreement = Aggregator::agreement_percent(&values, value);
+        for deployment in &self.deployments {
+            QualityMetrics::update_source_agreement(
+                &deployment.datafeed.name,
+                &deployment.datafeed.networks,
+                agreement,
+                values.len(),
+            );
+        }
+
+        debug!(
+            "Datafeed {}: aggregated {} of {} sources using {} to {}",
+            self.datafeed.name,
+            values.len(),
+            sources.len(),
+            self.datafeed.aggregation.method,
+            value
+        );
+
+        Ok((value, timestamp))
+    }
+
+    /// Fetches and extracts a single source of the datafeed
+    async fn poll_source(&self, source: &FeedSource) -> Result<(f64, u64)> {
+        if let Some(onchain) = &source.onchain {
+            return OnChainReader::read(&self.network_manager, onchain, &self.datafeed.name).await;
+        }
+
+        // Fetch JSON from the source URL
         let json = self
             .fetcher
-            .fetch_json(
-                &self.datafeed.feed_url,
+            .fetch_json_with_request(
+                &source.url,
+                &source.request.clone().unwrap_or_default(),
                 &self.datafeed.name,
-                &self.datafeed.networks,
+                &self.datafeed.network_label(),
             )
             .await?;
 
         // Extract value and timestamp
         let (value, timestamp) = JsonExtractor::extract_feed_data(
             &json,
-            &self.datafeed.feed_json_path,
-            self.datafeed.feed_json_path_timestamp.as_deref(),
+            &source.json_path,
+            source.json_path_timestamp.as_deref(),
         )?;
 
         Ok((value, timestamp))
     }
 
     /// Checks if contract update is needed and submits if necessary
-    async fn check_and_update_contract(&self, value: f64) -> Result<()> {
+    async fn check_and_update_contract(&self, datafeed: &Datafeed, value: f64) -> Result<()> {
+        // Read-only contracts are monitored but never updated
+        if !oracle::is_writable(&datafeed.contract_type) {
+            debug!(
+                "Datafeed {} uses read-only contract type {}, skipping update check",
+                datafeed.name, datafeed.contract_type
+            );
+            return Ok(());
+        }
+
         let mut updater = if let Some(ref tx_repo) = self.tx_log_repo {
             ContractUpdater::with_tx_logging(&self.network_manager, &self.config, tx_repo.clone())
         } else {
@@ -256,21 +711,29 @@ impl FeedMonitor {
             updater = updater.with_gas_price_manager(gas_price_manager);
         }
 
+        if let Some(ref batcher) = self.batcher {
+            updater = updater.with_batcher(batcher);
+        }
+
+        if let Some(ref tracker) = self.tracker {
+            updater = updater.with_tracker(tracker);
+        }
+
         // Check if update is needed
-        let (should_update, reason) = updater.check_update_needed(&self.datafeed, value).await?;
+        let (should_update, reason) = updater.check_update_needed(datafeed, value).await?;
 
         if should_update {
             info!(
                 "Update triggered for datafeed {} due to {}",
-                self.datafeed.name, reason
+                datafeed.name, reason
             );
 
             // Submit the value to the contract
-            updater.submit_value(&self.datafeed, value).await?;
+            updater.submit_value(datafeed, value).await?;
         } else {
             debug!(
-                "No update needed for datafeed {} - neither time nor deviation thresholds met",
-                self.datafeed.name
+                "No update needed for datafeed {} - {}",
+                datafeed.name, reason
             );
         }
 
@@ -278,7 +741,12 @@ impl FeedMonitor {
     }
 
     /// Saves an error log entry to the database
-    async fn save_error_log(&self, repository: &FeedLogRepository, error: &anyhow::Error) {
+    async fn save_error_log(
+        &self,
+        repository: &FeedLogRepository,
+        datafeed: &Datafeed,
+        error: &anyhow::Error,
+    ) {
         // Try to determine if it's an HTTP error or network error
         let (error_status_code, network_error) = if let Some(http_err) =
             error.downcast_ref::<super::fetcher::FetchError>()
@@ -293,12 +761,12 @@ impl FeedMonitor {
 
         debug!(
             "Saving error log for feed {}: error_status={:?}, network_error={}, error_message={}",
-            self.datafeed.name, error_status_code, network_error, error
+            datafeed.name, error_status_code, network_error, error
         );
 
         let log = NewFeedLog {
-            feed_name: self.datafeed.name.clone(),
-            network_name: self.datafeed.networks.clone(),
+            feed_name: datafeed.name.clone(),
+            network_name: datafeed.networks.clone(),
             feed_value: 0.0, // Default value for errors
             feed_timestamp: chrono::Utc::now().timestamp(),
             error_status_code,
@@ -309,14 +777,11 @@ impl FeedMonitor {
             Ok(saved_log) => {
                 debug!(
                     "Error log saved successfully for {} with id={}",
-                    self.datafeed.name, saved_log.id
+                    datafeed.name, saved_log.id
                 );
             }
             Err(e) => {
-                warn!(
-                    "Failed to save error log for feed {}: {}",
-                    self.datafeed.name, e
-                );
+                warn!("Failed to save error log for feed {}: {}", datafeed.name, e);
             }
         }
     }
@@ -0,0 +1,187 @@
+use alloy::{
+    dyn_abi::DynSolType,
+    json_abi::StateMutability,
+    network::Ethereum,
+    primitives::{Address, Bytes},
+};
+use anyhow::{anyhow, Context, Result};
+use std::str::FromStr;
+use tracing::debug;
+
+use crate::config::models::OnChainSource;
+use crate::contracts::abi_utils::{
+    create_function_definition_with_outputs, decode_function_return, dyn_value_to_f64,
+    encode_function_call, parse_function_signature,
+};
+use crate::contracts::MetricsAwareContractCaller;
+use crate::network::NetworkManager;
+
+/// Reads datafeed values from contracts with an `eth_call`
+pub struct OnChainReader;
+
+impl OnChainReader {
+    /// Checks that the call can be encoded and its result decoded, without calling the contract
+    pub fn validate(source: &OnChainSource) -> Result<()> {
+        encode_function_call(&source.function, &source.args, StateMutability::View)?;
+
+        for return_type in &source.returns {
+            DynSolType::parse(return_type)
+                .with_context(|| format!("Invalid return type '{return_type}'"))?;
+        }
+
+        let indices = std::iter::once(("value_index", source.value_index))
+            .chain(source.timestamp_index.map(|i| ("timestamp_index", i)));
+        for (name, index) in indices {
+            if index >= source.returns.len() {
+                return Err(anyhow!(
+                    "{} {} is out of range for {} return value(s)",
+                    name,
+                    index,
+                    source.returns.len()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calls the contract and returns the decoded (value, timestamp)
+    pub async fn read(
+        network_manager: &NetworkManager,
+        source: &OnChainSource,
+        feed_name: &str,
+    ) -> Result<(f64, u64)> {
+        let provider = network_manager.get_provider(&source.network)?;
+        let address = Address::from_str(&source.address)
+            .with_context(|| format!("Invalid contract address {}", source.address))?;
+        let (method_name, _) = parse_function_signature(&source.function)?;
+        let call_data =
+            encode_function_call(&source.function, &source.args, StateMutability::View)?;
+
+        let (value, timestamp) =
+            MetricsAwareContractCaller::<_, Ethereum, _>::new(provider, address, &source.network)
+                .with_feed_name(feed_name)
+                .call(call_data, &method_name, |bytes| Self::decode(source, bytes))
+                .await?;
+
+        debug!(
+            "Read {} from {} on {} for feed {}: value={}, timestamp={}",
+            source.function, source.address, source.network, feed_name, value, timestamp
+        );
+
+        Ok((value, timestamp))
+    }
+
+    /// Decodes the raw call result into a (value, timestamp) pair
+    pub fn decode(source: &OnChainSource, data: &Bytes) -> Result<(f64, u64)> {
+        let function = create_function_definition_with_outputs(
+            &source.function,
+            &source.returns,
+            StateMutability::View,
+        )?;
+        let decoded = decode_function_return(&function, data)?;
+
+        let value = decoded
+            .get(source.value_index)
+            .with_context(|| format!("No return value at index {}", source.value_index))?;
+        let value = dyn_value_to_f64(value, source.decimals)?;
+
+        let timestamp = match source.timestamp_index {
+            Some(index) => {
+                let timestamp = decoded
+                    .get(index)
+                    .with_context(|| format!("No return value at index {index}"))?;
+                dyn_value_to_f64(timestamp, 0)? as u64
+            }
+            None => std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        };
+
+        Ok((value, timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::dyn_abi::DynSolValue;
+    use alloy::primitives::{I256, U256};
+
+    fn latest_round_data() -> OnChainSource {
+        OnChainSource {
+            network: "ethereum".to_string(),
+            address: "0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419".to_string(),
+            function: "latestRoundData()".to_string(),
+            args: vec![],
+            returns: ["uint80", "int256", "uint256", "uint256", "uint80"]
+                .iter()
+                .map(|t| t.to_string())
+                .collect(),
+            value_index: 1,
+            timestamp_index: Some(3),
+            decimals: 8,
+        }
+    }
+
+    #[test]
+    fn test_decode_latest_round_data() {
+        let data = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(U256::from(42), 80),
+            DynSolValue::Int(I256::try_from(312_345_000_000i64).unwrap(), 256),
+            DynSolValue::Uint(U256::from(1_699_999_000u64), 256),
+            DynSolValue::Uint(U256::from(1_700_000_000u64), 256),
+            DynSolValue::Uint(U256::from(42), 80),
+        ])
+        .abi_encode_params();
+
+        let (value, timestamp) =
+            OnChainReader::decode(&latest_round_data(), &Bytes::from(data)).unwrap();
+        assert_eq!(value, 3123.45);
+        assert_eq!(timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_decode_without_timestamp_uses_current_time() {
+        let source = OnChainSource {
+            function: "convertToAssets(uint256)".to_string(),
+            args: vec!["1000000000000000000".to_string()],
+            returns: vec!["uint256".to_string()],
+            value_index: 0,
+            timestamp_index: None,
+            decimals: 18,
+            ..latest_round_data()
+        };
+        let data = DynSolValue::Uint(U256::from(1_050_000_000_000_000_000u64), 256).abi_encode();
+
+        let (value, timestamp) = OnChainReader::decode(&source, &Bytes::from(data)).unwrap();
+        assert_eq!(value, 1.05);
+        assert!(timestamp > 1_700_000_000);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(OnChainReader::validate(&latest_round_data()).is_ok());
+
+        let out_of_range = OnChainSource {
+            value_index: 5,
+            ..latest_round_data()
+        };
+        assert!(OnChainReader::validate(&out_of_range).is_err());
+
+        let bad_type = OnChainSource {
+            returns: vec!["uint257".to_string()],
+            value_index: 0,
+            timestamp_index: None,
+            ..latest_round_data()
+        };
+        assert!(OnChainReader::validate(&bad_type).is_err());
+
+        let missing_arg = OnChainSource {
+            function: "convertToAssets(uint256)".to_string(),
+            ..latest_round_data()
+        };
+        assert!(OnChainReader::validate(&missing_arg).is_err());
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::config::models::OutlierGuardConfig;
+use crate::metrics::SkipReason;
+
+/// A sanity check a datafeed value failed
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    /// The value moved too far from the current on-chain answer
+    OnChainJump {
+        reference: f64,
+        change_pct: f64,
+        limit_pct: f64,
+    },
+    /// The value is too far from the median of recent values
+    MedianDeviation {
+        reference: f64,
+        change_pct: f64,
+        limit_pct: f64,
+    },
+    /// The source timestamp is too old
+    Stale { age_secs: u64, limit_secs: u64 },
+}
+
+impl Violation {
+    /// Returns the skip reason recorded for the blocked submission
+    pub fn skip_reason(&self) -> SkipReason {
+        match self {
+            Violation::OnChainJump { .. } | Violation::MedianDeviation { .. } => {
+                SkipReason::OutlierRejected
+            }
+            Violation::Stale { .. } => SkipReason::StaleSource,
+        }
+    }
+
+    /// Returns the range of values that would have passed, for value checks
+    pub fn expected_range(&self) -> Option<(f64, f64)> {
+        match self {
+            Violation::OnChainJump {
+                reference,
+                limit_pct,
+                ..
+            }
+            | Violation::MedianDeviation {
+                reference,
+                limit_pct,
+                ..
+            } => {
+                let delta = reference.abs() * limit_pct / 100.0;
+                Some((reference - delta, reference + delta))
+            }
+            Violation::Stale { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::OnChainJump {
+                reference,
+                change_pct,
+                limit_pct,
+            } => write!(
+                f,
+                "{change_pct:.2}% change from on-chain answer {reference} exceeds {limit_pct}%"
+            ),
+            Violation::MedianDeviation {
+                reference,
+                change_pct,
+                limit_pct,
+            } => write!(
+                f,
+                "{change_pct:.2}% deviation from recent median {reference} exceeds {limit_pct}%"
+            ),
+            Violation::Stale {
+                age_secs,
+                limit_secs,
+            } => write!(
+                f,
+                "source timestamp is {age_secs}s old, limit is {limit_secs}s"
+            ),
+        }
+    }
+}
+
+/// Outcome of evaluating a value
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardDecision {
+    /// The value may be submitted
+    Allow,
+    /// The value failed a check and the circuit breaker is open
+    Reject(Violation),
+    /// The value passed, but the circuit breaker needs more sane values to close
+    Recovering { remaining: u32 },
+}
+
+impl GuardDecision {
+    /// Returns the skip reason for a blocked submission, or `None` if it is allowed
+    pub fn skip_reason(&self) -> Option<SkipReason> {
+        match self {
+            GuardDecision::Allow => None,
+            GuardDecision::Reject(violation) => Some(violation.skip_reason()),
+            GuardDecision::Recovering { .. } => Some(SkipReason::CircuitOpen),
+        }
+    }
+}
+
+/// Outlier rejection and circuit breaker for a single datafeed
+#[derive(Debug)]
+pub struct OutlierGuard {
+    config: OutlierGuardConfig,
+    history: VecDeque<f64>,
+    open: bool,
+    sane_readings: u32,
+    /// Consecutive values whose only violation was their jump from the
+    /// on-chain answer, fresh and within the median deviation
+    onchain_jumps: VecDeque<f64>,
+}
+
+impl OutlierGuard {
+    /// Creates a guard with a closed circuit breaker and no history
+    pub fn new(config: OutlierGuardConfig) -> Self {
+        Self {
+            history: VecDeque::with_capacity(config.median_window),
+            config,
+            open: false,
+            sane_readings: 0,
+            onchain_jumps: VecDeque::new(),
+        }
+    }
+
+    /// Seeds the median window with previous values, oldest first
+    pub fn seed(&mut self, values: impl IntoIterator<Item = f64>) {
+        for value in values {
+            self.push_history(value);
+        }
+    }
+
+    /// Returns true while the circuit breaker blocks submissions
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Checks a value and updates the circuit breaker
+    ///
+    /// `onchain` is the current on-chain answer, if it could be read. Every
+    /// value joins the median window, including rejected ones, so a lasting
+    /// level shift becomes the new median instead of being rejected forever.
+    ///
+    /// A rejected value is never submitted, so the on-chain answer doesn't
+    /// follow a genuine move beyond `max_onchain_jump_pct`. Once
+    /// `recovery_readings` consecutive values (at least 2) failed only that
+    /// check and agree with each other, the move is accepted and the circuit
+    /// breaker closes. A value that is also stale or off the median is never
+    /// counted, so a source stuck on a bad value stays blocked.
+    pub fn evaluate(
+        &mut self,
+        value: f64,
+        timestamp: u64,
+        now: u64,
+        onchain: Option<f64>,
+    ) -> GuardDecision {
+        let violations = self.check(value, timestamp, now, onchain);
+        self.push_history(value);
+
+        if !matches!(violations.as_slice(), [Violation::OnChainJump { .. }]) {
+            self.onchain_jumps.clear();
+        } else if self.is_sustained_move(value) {
+            self.open = false;
+            self.sane_readings = 0;
+            return GuardDecision::Allow;
+        }
+
+        if let Some(violation) = violations.into_iter().next() {
+            self.open = true;
+            self.sane_readings = 0;
+            return GuardDecision::Reject(violation);
+        }
+
+        if self.open {
+            self.sane_readings += 1;
+            if self.sane_readings < self.config.recovery_readings {
+                return GuardDecision::Recovering {
+                    remaining: self.config.recovery_readings - self.sane_readings,
+                };
+            }
+            self.open = false;
+            self.sane_readings = 0;
+        }
+
+        GuardDecision::Allow
+    }
+
+    /// Returns every check the value fails, staleness first, then the
+    /// on-chain jump and the median deviation
+    fn check(&self, value: f64, timestamp: u64, now: u64, onchain: Option<f64>) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        if let Some(limit_secs) = self.config.max_staleness_secs {
+            let age_secs = now.saturating_sub(timestamp);
+            if age_secs > limit_secs {
+                violations.push(Violation::Stale {
+                    age_secs,
+                    limit_secs,
+                });
+            }
+        }
+
+        if let (Some(limit_pct), Some(reference)) = (self.config.max_onchain_jump_pct, onchain) {
+            if let Some(change_pct) = change_pct(reference, value) {
+                if change_pct > limit_pct {
+                    violations.push(Violation::OnChainJump {
+                        reference,
+                        change_pct,
+                        limit_pct,
+                    });
+                }
+            }
+        }
+
+        if let (Some(limit_pct), Some(reference)) =
+            (self.config.max_median_deviation_pct, self.median())
+        {
+            if let Some(change_pct) = change_pct(reference, value) {
+                if change_pct > limit_pct {
+                    violations.push(Violation::MedianDeviation {
+                        reference,
+                        change_pct,
+                        limit_pct,
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// Records a value that only failed the on-chain jump check and returns
+    /// true once enough consecutive ones agree with each other
+    fn is_sustained_move(&mut self, value: f64) -> bool {
+        let readings = self.config.recovery_readings.max(2) as usize;
+        if self.onchain_jumps.len() == readings {
+            self.onchain_jumps.pop_front();
+        }
+        self.onchain_jumps.push_back(value);
+        if self.onchain_jumps.len() < readings {
+            return false;
+        }
+
+        // Readings agree if they are as close to each other as a sane value
+        // is to the median, or to the on-chain answer without a median check
+        let Some(limit_pct) = self
+            .config
+            .max_median_deviation_pct
+            .or(self.config.max_onchain_jump_pct)
+        else {
+            return false;
+        };
+        let Some(reference) = median(&self.onchain_jumps) else {
+            return false;
+        };
+        self.onchain_jumps
+            .iter()
+            .all(|&reading| change_pct(reference, reading).is_some_and(|pct| pct <= limit_pct))
+    }
+
+    /// Returns the median of the values in the window
+    fn median(&self) -> Option<f64> {
+        median(&self.history)
+    }
+
+    fn push_history(&mut self, value: f64) {
+        if self.history.len() == self.config.median_window {
+            self.history.pop_front();
+        }
+        self.history.push_back(value);
+    }
+}
+
+/// Returns the median of the values, or `None` without any
+fn median(values: &VecDeque<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted: Vec<f64> = values.iter().copied().collect();
+    sorted.sort_by(f64::total_cmp);
+
+    let mid = sorted.len() / 2;
+    Some(if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    })
+}
+
+/// Percentage change from the reference, or `None` if the reference is zero
+fn change_pct(reference: f64, value: f64) -> Option<f64> {
+    if reference == 0.0 {
+        return None;
+    }
+    Some((value - reference).abs() / reference.abs() * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn guard() -> OutlierGuard {
+        OutlierGuard::new(OutlierGuardConfig {
+            max_onchain_jump_pct: Some(10.0),
+            max_median_deviation_pct: Some(5.0),
+            median_window: 5,
+            max_staleness_secs: Some(60),
+            recovery_readings: 2,
+        })
+    }
+
+    #[test]
+    fn test_allows_sane_values() {
+        let mut guard = guard();
+        guard.seed([100.0, 101.0, 99.0]);
+
+        assert_eq!(
+            guard.evaluate(102.0, NOW, NOW, Some(100.0)),
+            GuardDecision::Allow
+        );
+        assert!(!guard.is_open());
+    }
+
+    #[test]
+    fn test_rejects_onchain_jump() {
+        let mut guard = guard();
+
+        let decision = guard.evaluate(120.0, NOW, NOW, Some(100.0));
+        assert!(matches!(
+            decision,
+            GuardDecision::Reject(Violation::OnChainJump { .. })
+        ));
+        assert!(matches!(
+            decision.skip_reason(),
+            Some(SkipReason::OutlierRejected)
+        ));
+        assert!(guard.is_open());
+    }
+
+    #[test]
+    fn test_rejects_median_deviation() {
+        let mut guard = guard();
+        guard.seed([100.0, 100.0, 500.0, 100.0, 100.0]);
+
+        let decision = guard.evaluate(110.0, NOW, NOW, None);
+        match decision {
+            GuardDecision::Reject(violation @ Violation::MedianDeviation { .. }) => {
+                assert_eq!(violation.expected_range(), Some((95.0, 105.0)));
+            }
+            other => panic!("Expected median deviation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_rejects_stale_timestamp() {
+        let mut guard = guard();
+
+        let decision = guard.evaluate(100.0, NOW - 120, NOW, None);
+        assert_eq!(
+            decision,
+            GuardDecision::Reject(Violation::Stale {
+                age_secs: 120,
+                limit_secs: 60
+            })
+        );
+        assert!(matches!(
+            decision.skip_reason(),
+            Some(SkipReason::StaleSource)
+        ));
+    }
+
+    #[test]
+    fn test_circuit_breaker_recovers_after_consecutive_sane_values() {
+        let mut guard = guard();
+        guard.seed([100.0; 5]);
+
+        assert!(matches!(
+            guard.evaluate(200.0, NOW, NOW, None),
+            GuardDecision::Reject(_)
+        ));
+        assert_eq!(
+            guard.evaluate(100.0, NOW, NOW, None),
+            GuardDecision::Recovering { remaining: 1 }
+        );
+
+        // Another bad value restarts the recovery
+        assert!(matches!(
+            guard.evaluate(100.0, NOW - 600, NOW, None),
+            GuardDecision::Reject(_)
+        ));
+        assert_eq!(
+            guard.evaluate(100.0, NOW, NOW, None),
+            GuardDecision::Recovering { remaining: 1 }
+        );
+        assert_eq!(guard.evaluate(100.0, NOW, NOW, None), GuardDecision::Allow);
+        assert!(!guard.is_open());
+    }
+
+    #[test]
+    fn test_level_shift_becomes_new_median() {
+        let mut guard = guard();
+        guard.seed([100.0; 5]);
+
+        let decisions: Vec<_> = (0..5)
+            .map(|_| guard.evaluate(150.0, NOW, NOW, None))
+            .collect();
+
+        assert!(matches!(decisions[0], GuardDecision::Reject(_)));
+        assert_eq!(decisions[4], GuardDecision::Allow);
+    }
+
+    #[test]
+    fn test_sustained_onchain_move_is_accepted() {
+        let mut guard = guard();
+        guard.seed([100.0; 5]);
+
+        // The on-chain answer stays at 100 because nothing gets submitted.
+        // The first values are also off the median, which takes three of them
+        // to move to the new level.
+        let decisions: Vec<_> = [150.0, 151.0, 150.0, 150.5]
+            .into_iter()
+            .map(|value| guard.evaluate(value, NOW, NOW, Some(100.0)))
+            .collect();
+        assert!(decisions
+            .iter()
+            .all(|decision| matches!(decision, GuardDecision::Reject(_))));
+
+        // The second value failing only the on-chain jump is accepted
+        assert_eq!(
+            guard.evaluate(151.0, NOW, NOW, Some(100.0)),
+            GuardDecision::Allow
+        );
+        assert!(!guard.is_open());
+        // Readings keep being accepted until the on-chain answer catches up
+        assert_eq!(
+            guard.evaluate(150.5, NOW, NOW, Some(100.0)),
+            GuardDecision::Allow
+        );
+    }
+
+    #[test]
+    fn test_repeated_value_off_the_median_stays_rejected() {
+        let mut guard = guard();
+        guard.seed([100.0; 5]);
+
+        // A source stuck on a bad value breaks both the on-chain and the
+        // median limit, agreeing readings don't make it a sustained move
+        for _ in 0..3 {
+            assert!(matches!(
+                guard.evaluate(151.0, NOW, NOW, Some(100.0)),
+                GuardDecision::Reject(Violation::OnChainJump { .. })
+            ));
+            assert!(guard.is_open());
+        }
+    }
+
+    #[test]
+    fn test_erratic_onchain_jumps_stay_rejected() {
+        let mut guard = guard();
+
+        let decisions: Vec<_> = [150.0, 200.0, 150.0, 60.0]
+            .into_iter()
+            .map(|value| guard.evaluate(value, NOW, NOW, Some(100.0)))
+            .collect();
+
+        assert!(decisions
+            .iter()
+            .all(|decision| matches!(decision, GuardDecision::Reject(_))));
+        assert!(guard.is_open());
+    }
+}
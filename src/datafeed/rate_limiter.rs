@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::config::models::RateLimitConfig;
+
+/// Token bucket for a single host
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    capacity: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(config: &RateLimitConfig) -> Self {
+        let capacity = config.burst as f64;
+        Self {
+            tokens: capacity,
+            capacity,
+            refill_per_sec: config.requests_per_second,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes a token if one is available, otherwise returns the time until the next one
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+}
+
+/// Per-host token-bucket rate limiter for datafeed HTTP requests
+///
+/// Hosts without a configured limit are never throttled.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter from limits keyed by host
+    pub fn new(limits: &BTreeMap<String, RateLimitConfig>) -> Self {
+        let buckets = limits
+            .iter()
+            .map(|(host, config)| (host.to_lowercase(), TokenBucket::new(config)))
+            .collect();
+
+        Self {
+            buckets: Mutex::new(buckets),
+        }
+    }
+
+    /// Waits until a request to the host is allowed and returns the time spent waiting
+    pub async fn acquire(&self, host: &str) -> Duration {
+        let host = host.to_lowercase();
+        let mut waited = Duration::ZERO;
+
+        loop {
+            let wait = {
+                let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+                let Some(bucket) = buckets.get_mut(&host) else {
+                    return waited;
+                };
+
+                match bucket.try_take(Instant::now()) {
+                    Ok(()) => return waited,
+                    Err(wait) => wait,
+                }
+            };
+
+            tokio::time::sleep(wait).await;
+            waited += wait;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(host: &str, requests_per_second: f64, burst: u32) -> RateLimiter {
+        RateLimiter::new(&BTreeMap::from([(
+            host.to_string(),
+            RateLimitConfig {
+                requests_per_second,
+                burst,
+            },
+        )]))
+    }
+
+    #[test]
+    fn test_token_bucket_refill() {
+        let mut bucket = TokenBucket::new(&RateLimitConfig {
+            requests_per_second: 2.0,
+            burst: 2,
+        });
+        let start = bucket.last_refill;
+
+        assert!(bucket.try_take(start).is_ok());
+        assert!(bucket.try_take(start).is_ok());
+
+        let wait = bucket.try_take(start).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+
+        assert!(bucket.try_take(start + Duration::from_millis(500)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_waits_for_tokens() {
+        let limiter = limits("api.example.com", 20.0, 1);
+
+        assert_eq!(limiter.acquire("api.example.com").await, Duration::ZERO);
+
+        let start = Instant::now();
+        let waited = limiter.acquire("API.example.com").await;
+        assert!(waited >= Duration::from_millis(40), "waited {waited:?}");
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_host_is_not_limited() {
+        let limiter = limits("api.example.com", 1.0, 1);
+
+        for _ in 0..10 {
+            assert_eq!(limiter.acquire("other.example.com").await, Duration::ZERO);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::{Context, Result};
+use secrecy::ExposeSecret;
+use std::sync::Arc;
+
+use crate::wallet::KeyStorage;
+
+/// Placeholder prefix for values looked up in the configured key storage backend
+const SECRET_PREFIX: &str = "secret:";
+
+/// Resolves `${...}` placeholders in request templates
+///
+/// * `${NAME}` is replaced by the value of the environment variable `NAME`
+/// * `${secret:NAME}` is replaced by the secret `NAME` from the key storage backend
+#[derive(Clone, Default)]
+pub struct SecretResolver {
+    key_storage: Option<Arc<dyn KeyStorage>>,
+}
+
+impl SecretResolver {
+    /// Creates a resolver that only supports environment variables
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a resolver that can also look up secrets in a key storage backend
+    pub fn with_key_storage(key_storage: Arc<dyn KeyStorage>) -> Self {
+        Self {
+            key_storage: Some(key_storage),
+        }
+    }
+
+    /// Replaces all placeholders in the template
+    ///
+    /// Errors never include the resolved values, only the placeholder names.
+    pub async fn resolve(&self, template: &str) -> Result<String> {
+        let mut output = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find("${") {
+            output.push_str(&rest[..start]);
+
+            let end = rest[start..]
+                .find('}')
+                .map(|offset| start + offset)
+                .with_context(|| format!("Unterminated placeholder in template '{template}'"))?;
+
+            let name = &rest[start + 2..end];
+            output.push_str(&self.lookup(name).await?);
+            rest = &rest[end + 1..];
+        }
+
+        output.push_str(rest);
+        Ok(output)
+    }
+
+    /// Looks up a single placeholder
+    async fn lookup(&self, name: &str) -> Result<String> {
+        if let Some(secret_name) = name.strip_prefix(SECRET_PREFIX) {
+            let key_storage = self.key_storage.as_ref().with_context(|| {
+                format!(
+                    "Cannot resolve '${{{name}}}': secret lookups require the keyring, vault or \
+                     aws-secrets key storage backend, use '${{ENV_VAR}}' with env storage"
+                )
+            })?;
+
+            let secret = key_storage
+                .get_key(secret_name)
+                .await
+                .with_context(|| format!("Failed to resolve secret '{secret_name}'"))?;
+
+            return Ok(secret.expose_secret().to_string());
+        }
+
+        std::env::var(name).with_context(|| {
+            format!("Environment variable '{name}' referenced in request config is not set")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use async_trait::async_trait;
+    use secrecy::SecretString;
+
+    struct StaticStorage;
+
+    #[async_trait]
+    impl KeyStorage for StaticStorage {
+        async fn get_key(&self, name: &str) -> Result<SecretString> {
+            match name {
+                "api_key" => Ok(SecretString::from("s3cr3t")),
+                _ => Err(anyhow!("not found")),
+            }
+        }
+
+        async fn store_key(&self, _name: &str, _key: SecretString) -> Result<()> {
+            Ok(())
+        }
+
+        async fn remove_key(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn list_keys(&self) -> Result<Vec<String>> {
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_plain_text() {
+        let resolver = SecretResolver::new();
+        assert_eq!(resolver.resolve("ETHUSD").await.unwrap(), "ETHUSD");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_env_var() {
+        std::env::set_var("OMIKUJI_TEST_SECRETS_TOKEN", "abc123");
+
+        let resolver = SecretResolver::new();
+        let resolved = resolver
+            .resolve("Bearer ${OMIKUJI_TEST_SECRETS_TOKEN}")
+            .await
+            .unwrap();
+        assert_eq!(resolved, "Bearer abc123");
+
+        std::env::remove_var("OMIKUJI_TEST_SECRETS_TOKEN");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_missing_env_var() {
+        let resolver = SecretResolver::new();
+        let err = resolver
+            .resolve("${OMIKUJI_TEST_SECRETS_MISSING}")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("OMIKUJI_TEST_SECRETS_MISSING"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_key_storage_secret() {
+        let resolver = SecretResolver::with_key_storage(Arc::new(StaticStorage));
+        let resolved = resolver
+            .resolve(r#"{"key": "${secret:api_key}"}"#)
+            .await
+            .unwrap();
+        assert_eq!(resolved, r#"{"key": "s3cr3t"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_secret_without_key_storage() {
+        let resolver = SecretResolver::new();
+        let err = resolver.resolve("${secret:api_key}").await.unwrap_err();
+        assert!(err.to_string().contains("secret lookups require"));
+    }
+
+    #[tokio::test]
+    async fn test_unterminated_placeholder() {
+        let resolver = SecretResolver::new();
+        assert!(resolver.resolve("${API_KEY").await.is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use anyhow::{bail, Context, Result};
+use backoff::backoff::Backoff;
+use backoff::{ExponentialBackoff, ExponentialBackoffBuilder};
+use futures::{SinkExt, StreamExt};
+use serde_json::Value;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::{sleep, timeout};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, info, warn};
+
+use super::json_extractor::JsonExtractor;
+use super::secrets::SecretResolver;
+use crate::config::models::StreamConfig;
+use crate::metrics::DatasourceMetrics;
+
+/// A value received from a streaming source
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamTick {
+    pub value: f64,
+    pub timestamp: u64,
+}
+
+/// Holds a WebSocket subscription for a datafeed and publishes every value it receives
+pub struct StreamSource {
+    config: StreamConfig,
+    feed_name: String,
+    network: String,
+    secrets: SecretResolver,
+}
+
+impl StreamSource {
+    /// Creates a new stream source for the given datafeed
+    pub fn new(
+        config: StreamConfig,
+        feed_name: impl Into<String>,
+        network: impl Into<String>,
+        secrets: SecretResolver,
+    ) -> Self {
+        Self {
+            config,
+            feed_name: feed_name.into(),
+            network: network.into(),
+            secrets,
+        }
+    }
+
+    /// Spawns the subscription in the background
+    ///
+    /// The receiver always holds the latest value, or `None` while the stream
+    /// is disconnected, so a stale value is never mistaken for a live one.
+    /// The subscription ends when the receiver is dropped.
+    pub fn spawn(self) -> watch::Receiver<Option<StreamTick>> {
+        let (tx, rx) = watch::channel(None);
+        tokio::spawn(self.run(tx));
+        rx
+    }
+
+    /// Connects, reads and reconnects with exponential backoff until the receiver is dropped
+    async fn run(self, tx: watch::Sender<Option<StreamTick>>) {
+        let mut backoff = ExponentialBackoffBuilder::new()
+            .with_initial_interval(Duration::from_millis(self.config.reconnect_initial_ms))
+            .with_max_interval(Duration::from_millis(self.config.reconnect_max_ms))
+            .with_max_elapsed_time(None)
+            .build();
+
+        while !tx.is_closed() {
+            match self.connect_and_read(&tx, &mut backoff).await {
+                Ok(()) => info!(
+                    "Stream for datafeed {} closed by {}",
+                    self.feed_name, self.config.url
+                ),
+                Err(e) => warn!("Stream for datafeed {} failed: {:#}", self.feed_name, e),
+            }
+
+            DatasourceMetrics::set_stream_connected(&self.feed_name, &self.network, false);
+            tx.send_replace(None);
+
+            if tx.is_closed() {
+                break;
+            }
+
+            let delay = backoff
+                .next_backoff()
+                .unwrap_or(Duration::from_millis(self.config.reconnect_max_ms));
+            DatasourceMetrics::record_stream_reconnect(&self.feed_name, &self.network, delay);
+            sleep(delay).await;
+        }
+
+        debug!("Stream for datafeed {} stopped", self.feed_name);
+    }
+
+    /// Runs a single connection until it is closed or fails
+    async fn connect_and_read(
+        &self,
+        tx: &watch::Sender<Option<StreamTick>>,
+        backoff: &mut ExponentialBackoff,
+    ) -> Result<()> {
+        let (mut ws, _) = connect_async(self.config.url.as_str())
+            .await
+            .with_context(|| format!("Failed to connect to {}", self.config.url))?;
+
+        DatasourceMetrics::set_stream_connected(&self.feed_name, &self.network, true);
+        info!(
+            "Stream for datafeed {} connected to {}",
+            self.feed_name, self.config.url
+        );
+
+        if let Some(template) = &self.config.subscribe_message {
+            let message = self.secrets.resolve(template).await?;
+            ws.send(Message::Text(message))
+                .await
+                .context("Failed to send subscribe message")?;
+        }
+
+        let idle_timeout = Duration::from_secs(self.config.idle_timeout_secs);
+
+        while !tx.is_closed() {
+            let message = match timeout(idle_timeout, ws.next()).await {
+                Err(_) => bail!("No message received for {}s", self.config.idle_timeout_secs),
+                Ok(None) => return Ok(()),
+                Ok(Some(message)) => message.context("WebSocket error")?,
+            };
+
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Binary(bytes) => match String::from_utf8(bytes) {
+                    Ok(text) => text,
+                    Err(_) => continue,
+                },
+                Message::Close(_) => return Ok(()),
+                // Pings are answered by the WebSocket implementation
+                _ => continue,
+            };
+
+            let tick = parse_message(
+                &text,
+                &self.config.json_path,
+                self.config.json_path_timestamp.as_deref(),
+            );
+            DatasourceMetrics::record_stream_message(
+                &self.feed_name,
+                &self.network,
+                tick.is_some(),
+            );
+
+            if let Some(tick) = tick {
+                // Only a connection that delivers values counts as healthy
+                backoff.reset();
+                tx.send_replace(Some(tick));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Extracts a tick from a stream message
+///
+/// Returns `None` for messages that are not JSON or don't contain the value,
+/// such as subscription acknowledgements and heartbeats.
+pub fn parse_message(
+    text: &str,
+    json_path: &str,
+    timestamp_path: Option<&str>,
+) -> Option<StreamTick> {
+    let json: Value = match serde_json::from_str(text) {
+        Ok(json) => json,
+        Err(e) => {
+            debug!("Ignoring non-JSON stream message: {}", e);
+            return None;
+        }
+    };
+
+    match JsonExtractor::extract_feed_data(&json, json_path, timestamp_path) {
+        Ok((value, timestamp)) => Some(StreamTick { value, timestamp }),
+        Err(e) => {
+            debug!("Ignoring stream message without a value: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    fn stream_config(url: String) -> StreamConfig {
+        StreamConfig {
+            url,
+            subscribe_message: Some(r#"{"op": "subscribe", "channel": "ETHUSD"}"#.to_string()),
+            json_path: "data.price".to_string(),
+            json_path_timestamp: Some("data.ts".to_string()),
+            reconnect_initial_ms: 10,
+            reconnect_max_ms: 50,
+            idle_timeout_secs: 5,
+        }
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let tick = parse_message(
+            r#"{"data": {"price": 2500.5, "ts": 1700000000}}"#,
+            "data.price",
+            Some("data.ts"),
+        );
+        assert_eq!(
+            tick,
+            Some(StreamTick {
+                value: 2500.5,
+                timestamp: 1_700_000_000
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_message_ignores_acks_and_non_json() {
+        assert_eq!(
+            parse_message(r#"{"event": "subscribed"}"#, "data.price", None),
+            None
+        );
+        assert_eq!(parse_message("pong", "data.price", None), None);
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_and_receive_ticks() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(socket).await.unwrap();
+
+            let subscribe = ws.next().await.unwrap().unwrap();
+            assert_eq!(
+                subscribe.into_text().unwrap(),
+                r#"{"op": "subscribe", "channel": "ETHUSD"}"#
+            );
+
+            ws.send(Message::Text(r#"{"event": "subscribed"}"#.to_string()))
+                .await
+                .unwrap();
+            ws.send(Message::Text(
+                r#"{"data": {"price": 2500.5, "ts": 1700000000}}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+
+            // Keep the connection open until the client goes away
+            while let Some(Ok(_)) = ws.next().await {}
+        });
+
+        let mut rx = StreamSource::new(
+            stream_config(url),
+            "eth_usd",
+            "testnet",
+            SecretResolver::new(),
+        )
+        .spawn();
+
+        let tick = timeout(Duration::from_secs(5), async {
+            loop {
+                rx.changed().await.unwrap();
+                if let Some(tick) = *rx.borrow_and_update() {
+                    return tick;
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(tick.value, 2500.5);
+        assert_eq!(tick.timestamp, 1_700_000_000);
+
+        drop(rx);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_disconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            // The first connection is closed right after its value
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(socket).await.unwrap();
+            let _subscribe = ws.next().await;
+            ws.send(Message::Text(
+                r#"{"data": {"price": 100.0, "ts": 1700000000}}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+            ws.close(None).await.unwrap();
+
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(socket).await.unwrap();
+            let _subscribe = ws.next().await;
+            ws.send(Message::Text(
+                r#"{"data": {"price": 200.0, "ts": 1700000000}}"#.to_string(),
+            ))
+            .await
+            .unwrap();
+            while let Some(Ok(_)) = ws.next().await {}
+        });
+
+        let mut rx = StreamSource::new(
+            stream_config(url),
+            "eth_usd",
+            "testnet",
+            SecretResolver::new(),
+        )
+        .spawn();
+
+        // The second value can only arrive after a reconnect
+        let value = timeout(Duration::from_secs(5), async {
+            loop {
+                rx.changed().await.unwrap();
+                if let Some(tick) = *rx.borrow_and_update() {
+                    if tick.value == 200.0 {
+                        return tick.value;
+                    }
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(value, 200.0);
+        server.abort();
+    }
+}
@@ -146,6 +146,102 @@ mod tests {
             let result = JsonExtractor::extract_float(&json, "");
             assert!(result.is_err()); // Empty path should fail
         }
+
+        #[test]
+        fn test_dot_notation_array_index() {
+            let json = json!({
+                "result": {
+                    "XETHZUSD": {
+                        "c": ["2557.96", "0.01"]
+                    }
+                }
+            });
+
+            let result = JsonExtractor::extract_float(&json, "result.XETHZUSD.c.0");
+            assert_eq!(result.unwrap(), 2557.96);
+        }
+
+        #[test]
+        fn test_json_path_array_index() {
+            let json = json!({
+                "data": [
+                    {"price": 2045.34},
+                    {"price": 2046.00}
+                ]
+            });
+
+            let result = JsonExtractor::extract_float(&json, "data[0].price");
+            assert_eq!(result.unwrap(), 2045.34);
+
+            let result = JsonExtractor::extract_float(&json, "$.data[1].price");
+            assert_eq!(result.unwrap(), 2046.00);
+        }
+
+        #[test]
+        fn test_json_path_filter() {
+            let json = json!({
+                "tickers": [
+                    {"symbol": "BTCUSD", "last": "108245.90"},
+                    {"symbol": "ETHUSD", "last": "2557.96"}
+                ]
+            });
+
+            let result =
+                JsonExtractor::extract_float(&json, "$.tickers[?(@.symbol=='ETHUSD')].last");
+            assert_eq!(result.unwrap(), 2557.96);
+        }
+
+        #[test]
+        fn test_json_path_no_match() {
+            let json = json!({
+                "tickers": [
+                    {"symbol": "BTCUSD", "last": 108245.90}
+                ]
+            });
+
+            let result =
+                JsonExtractor::extract_float(&json, "$.tickers[?(@.symbol=='ETHUSD')].last");
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("matched no values"));
+        }
+
+        #[test]
+        fn test_json_path_multiple_matches() {
+            let json = json!({
+                "tickers": [
+                    {"symbol": "BTCUSD", "last": 108245.90},
+                    {"symbol": "ETHUSD", "last": 2557.96}
+                ]
+            });
+
+            let result = JsonExtractor::extract_float(&json, "$.tickers[*].last");
+            assert!(result
+                .unwrap_err()
+                .to_string()
+                .contains("matched 2 values, expected exactly one"));
+        }
+
+        #[test]
+        fn test_json_path_timestamp() {
+            let json = json!({
+                "data": [{"price": 1.0, "ts": 1748071295}]
+            });
+
+            let result =
+                JsonExtractor::extract_feed_data(&json, "data[0].price", Some("$.data[0].ts"));
+            assert_eq!(result.unwrap(), (1.0, 1748071295));
+        }
+
+        #[test]
+        fn test_validate_path() {
+            assert!(JsonExtractor::validate_path("RAW.ETH.USD.PRICE").is_ok());
+            assert!(JsonExtractor::validate_path("$.tickers[?(@.symbol=='ETHUSD')].last").is_ok());
+            assert!(JsonExtractor::validate_path("data[0].price").is_ok());
+            assert!(JsonExtractor::validate_path("").is_err());
+            assert!(JsonExtractor::validate_path("$.data[?(@.price >").is_err());
+        }
     }
 
     mod fetcher_tests {
@@ -241,9 +337,426 @@ mod tests {
         }
     }
 
+    mod request_config_tests {
+        use crate::config::models::RequestConfig;
+        use crate::datafeed::fetcher::Fetcher;
+        use mockito::Matcher;
+        use std::collections::BTreeMap;
+
+        #[tokio::test]
+        async fn test_fetch_with_headers_and_query_params() {
+            std::env::set_var("OMIKUJI_TEST_FETCH_API_KEY", "key-123");
+
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/api/price")
+                .match_header("x-api-key", "key-123")
+                .match_query(Matcher::AllOf(vec![
+                    Matcher::UrlEncoded("symbol".into(), "ETHUSD".into()),
+                    Matcher::UrlEncoded("apikey".into(), "key-123".into()),
+                ]))
+                .with_status(200)
+                .with_body(r#"{"price": 2557.96}"#)
+                .expect(1)
+                .create_async()
+                .await;
+
+            let request = RequestConfig {
+                headers: BTreeMap::from([(
+                    "X-Api-Key".to_string(),
+                    "${OMIKUJI_TEST_FETCH_API_KEY}".to_string(),
+                )]),
+                query_params: BTreeMap::from([
+                    ("symbol".to_string(), "ETHUSD".to_string()),
+                    (
+                        "apikey".to_string(),
+                        "${OMIKUJI_TEST_FETCH_API_KEY}".to_string(),
+                    ),
+                ]),
+                ..Default::default()
+            };
+
+            let fetcher = Fetcher::new();
+            let url = format!("{}/api/price", server.url());
+            let result = fetcher
+                .fetch_json_with_request(&url, &request, "test_feed", "test_network")
+                .await;
+
+            assert_eq!(result.unwrap()["price"], 2557.96);
+            mock.assert_async().await;
+
+            std::env::remove_var("OMIKUJI_TEST_FETCH_API_KEY");
+        }
+
+        #[tokio::test]
+        async fn test_fetch_with_post_body() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("POST", "/graphql")
+                .match_header("content-type", "application/json")
+                .match_body(Matcher::JsonString(
+                    r#"{"query": "{ price(id: \"eth\") }"}"#.to_string(),
+                ))
+                .with_status(200)
+                .with_body(r#"{"data": {"price": "2557.96"}}"#)
+                .expect(1)
+                .create_async()
+                .await;
+
+            let request = RequestConfig {
+                method: "post".to_string(),
+                body: Some(r#"{"query": "{ price(id: \"eth\") }"}"#.to_string()),
+                ..Default::default()
+            };
+
+            let fetcher = Fetcher::new();
+            let url = format!("{}/graphql", server.url());
+            let result = fetcher
+                .fetch_json_with_request(&url, &request, "test_feed", "test_network")
+                .await;
+
+            assert_eq!(result.unwrap()["data"]["price"], "2557.96");
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_fetch_with_unresolvable_secret() {
+            let request = RequestConfig {
+                headers: BTreeMap::from([(
+                    "Authorization".to_string(),
+                    "Bearer ${secret:data_api_token}".to_string(),
+                )]),
+                ..Default::default()
+            };
+
+            let fetcher = Fetcher::new();
+            let result = fetcher
+                .fetch_json_with_request(
+                    "http://localhost:1/api",
+                    &request,
+                    "test_feed",
+                    "test_network",
+                )
+                .await;
+
+            let err = result.unwrap_err().to_string();
+            assert!(err.contains("secret lookups require"));
+        }
+    }
+
     // Monitor tests removed - FeedMonitor now requires NetworkManager which is not easily mockable
     // This functionality is tested through integration tests
 
+    mod fetch_cache_tests {
+        use crate::config::models::{FetchCacheConfig, RequestConfig};
+        use crate::datafeed::fetcher::{FetchError, Fetcher};
+        use std::collections::BTreeMap;
+
+        fn cached_fetcher(ttl_ms: u64) -> Fetcher {
+            Fetcher::new().with_cache(&FetchCacheConfig {
+                enabled: true,
+                ttl_ms,
+            })
+        }
+
+        #[tokio::test]
+        async fn test_concurrent_requests_are_coalesced() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/price")
+                .with_status(200)
+                .with_body(r#"{"price": 2557.96}"#)
+                .expect(1)
+                .create_async()
+                .await;
+
+            // A zero TTL leaves only request coalescing
+            let fetcher = cached_fetcher(0);
+            let url = format!("{}/price", server.url());
+
+            let results = futures::future::join_all(
+                ["ethereum", "base", "arbitrum"]
+                    .iter()
+                    .map(|network| fetcher.fetch_json(&url, "eth_usd", network)),
+            )
+            .await;
+
+            for result in results {
+                assert_eq!(result.unwrap()["price"], 2557.96);
+            }
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_responses_are_reused_within_ttl() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/price")
+                .with_status(200)
+                .with_body(r#"{"price": 2557.96}"#)
+                .expect(1)
+                .create_async()
+                .await;
+
+            let fetcher = cached_fetcher(60_000);
+            let url = format!("{}/price", server.url());
+
+            for network in ["ethereum", "base"] {
+                let json = fetcher.fetch_json(&url, "eth_usd", network).await.unwrap();
+                assert_eq!(json["price"], 2557.96);
+            }
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_expired_responses_are_refetched() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/price")
+                .with_status(200)
+                .with_body(r#"{"price": 2557.96}"#)
+                .expect(2)
+                .create_async()
+                .await;
+
+            let fetcher = cached_fetcher(0);
+            let url = format!("{}/price", server.url());
+
+            fetcher
+                .fetch_json(&url, "eth_usd", "ethereum")
+                .await
+                .unwrap();
+            fetcher.fetch_json(&url, "eth_usd", "base").await.unwrap();
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_different_request_settings_are_not_shared() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/price")
+                .with_status(200)
+                .with_body(r#"{"price": 2557.96}"#)
+                .expect(2)
+                .create_async()
+                .await;
+
+            let fetcher = cached_fetcher(60_000);
+            let url = format!("{}/price", server.url());
+            let with_header = RequestConfig {
+                headers: BTreeMap::from([("X-Api-Key".to_string(), "abc".to_string())]),
+                ..Default::default()
+            };
+
+            fetcher
+                .fetch_json(&url, "eth_usd", "ethereum")
+                .await
+                .unwrap();
+            fetcher
+                .fetch_json_with_request(&url, &with_header, "eth_usd", "base")
+                .await
+                .unwrap();
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_errors_are_not_cached() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/price")
+                .with_status(503)
+                .expect(2)
+                .create_async()
+                .await;
+
+            let fetcher = cached_fetcher(60_000);
+            let url = format!("{}/price", server.url());
+
+            for network in ["ethereum", "base"] {
+                let err = fetcher
+                    .fetch_json(&url, "eth_usd", network)
+                    .await
+                    .unwrap_err();
+                assert!(matches!(
+                    err.downcast_ref::<FetchError>(),
+                    Some(FetchError::Http(503))
+                ));
+            }
+            mock.assert_async().await;
+        }
+    }
+
+    mod retry_tests {
+        use crate::config::models::{FetchRetryConfig, RateLimitConfig};
+        use crate::datafeed::fetcher::{FetchError, Fetcher};
+        use std::collections::BTreeMap;
+        use std::time::{Duration, Instant};
+
+        fn retrying_fetcher(max_retries: u32) -> Fetcher {
+            Fetcher::new().with_retry(&FetchRetryConfig {
+                max_retries,
+                initial_backoff_ms: 10,
+                max_backoff_ms: 2000,
+                timeout_secs: 5,
+            })
+        }
+
+        #[tokio::test]
+        async fn test_server_errors_are_retried() {
+            let mut server = mockito::Server::new_async().await;
+            let failing = server
+                .mock("GET", "/price")
+                .with_status(503)
+                .expect(2)
+                .create_async()
+                .await;
+            let succeeding = server
+                .mock("GET", "/price")
+                .with_status(200)
+                .with_body(r#"{"price": 2557.96}"#)
+                .expect(1)
+                .create_async()
+                .await;
+
+            let url = format!("{}/price", server.url());
+            let json = retrying_fetcher(3)
+                .fetch_json(&url, "eth_usd", "ethereum")
+                .await
+                .unwrap();
+
+            assert_eq!(json["price"], 2557.96);
+            failing.assert_async().await;
+            succeeding.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_gives_up_after_max_retries() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/price")
+                .with_status(500)
+                .expect(3)
+                .create_async()
+                .await;
+
+            let url = format!("{}/price", server.url());
+            let err = retrying_fetcher(2)
+                .fetch_json(&url, "eth_usd", "ethereum")
+                .await
+                .unwrap_err();
+
+            assert!(matches!(
+                err.downcast_ref::<FetchError>(),
+                Some(FetchError::Http(500))
+            ));
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_client_errors_are_not_retried() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/price")
+                .with_status(404)
+                .expect(1)
+                .create_async()
+                .await;
+
+            let url = format!("{}/price", server.url());
+            let result = retrying_fetcher(3)
+                .fetch_json(&url, "eth_usd", "ethereum")
+                .await;
+
+            assert!(result.is_err());
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_retry_after_is_honoured() {
+            let mut server = mockito::Server::new_async().await;
+            let limited = server
+                .mock("GET", "/price")
+                .with_status(429)
+                .with_header("Retry-After", "1")
+                .expect(1)
+                .create_async()
+                .await;
+            let succeeding = server
+                .mock("GET", "/price")
+                .with_status(200)
+                .with_body(r#"{"price": 2557.96}"#)
+                .expect(1)
+                .create_async()
+                .await;
+
+            let url = format!("{}/price", server.url());
+            let start = Instant::now();
+            retrying_fetcher(3)
+                .fetch_json(&url, "eth_usd", "ethereum")
+                .await
+                .unwrap();
+
+            assert!(start.elapsed() >= Duration::from_secs(1));
+            limited.assert_async().await;
+            succeeding.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_retry_after_longer_than_max_backoff_is_not_retried() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/price")
+                .with_status(429)
+                .with_header("Retry-After", "120")
+                .expect(1)
+                .create_async()
+                .await;
+
+            let url = format!("{}/price", server.url());
+            let err = retrying_fetcher(3)
+                .fetch_json(&url, "eth_usd", "ethereum")
+                .await
+                .unwrap_err();
+
+            assert!(matches!(
+                err.downcast_ref::<FetchError>(),
+                Some(FetchError::Http(429))
+            ));
+            mock.assert_async().await;
+        }
+
+        #[tokio::test]
+        async fn test_requests_are_rate_limited_per_host() {
+            let mut server = mockito::Server::new_async().await;
+            let mock = server
+                .mock("GET", "/price")
+                .with_status(200)
+                .with_body(r#"{"price": 2557.96}"#)
+                .expect(2)
+                .create_async()
+                .await;
+
+            let fetcher = Fetcher::new().with_rate_limits(&BTreeMap::from([(
+                "127.0.0.1".to_string(),
+                RateLimitConfig {
+                    requests_per_second: 5.0,
+                    burst: 1,
+                },
+            )]));
+            let url = format!("{}/price", server.url());
+
+            let start = Instant::now();
+            fetcher
+                .fetch_json(&url, "eth_usd", "ethereum")
+                .await
+                .unwrap();
+            fetcher.fetch_json(&url, "eth_usd", "base").await.unwrap();
+
+            assert!(start.elapsed() >= Duration::from_millis(190));
+            mock.assert_async().await;
+        }
+    }
+
     mod integration_tests {
         use super::*;
         use crate::datafeed::json_extractor::JsonExtractor;
@@ -303,6 +816,14 @@ mod tests {
                     gas_config: Default::default(),
                     gas_token: "ethereum".to_string(),
                     gas_token_symbol: "ETH".to_string(),
+                    batching: Default::default(),
+                    confirmations: 1,
+                    rpc_endpoints: Vec::new(),
+                    rpc_failover: Default::default(),
+                    read_quorum: Default::default(),
+                    check_feeds_on_new_heads: false,
+                    chain_id: None,
+                    l2_type: "none".to_string(),
                 }],
                 datafeeds: vec![Datafeed {
                     name: "test-feed".to_string(),
@@ -310,6 +831,9 @@ mod tests {
                     check_frequency: 60,
                     contract_address: "0x1234567890123456789012345678901234567890".to_string(),
                     contract_type: "fluxmon".to_string(),
+                    setter_signature: None,
+                    custom_abi: None,
+                    custom_errors: Vec::new(),
                     read_contract_config: false,
                     minimum_update_frequency: 3600,
                     deviation_threshold_pct: 0.5,
@@ -320,12 +844,26 @@ mod tests {
                     min_value: Some(I256::try_from(1).unwrap()),
                     max_value: Some(I256::try_from(1000000).unwrap()),
                     data_retention_days: 7,
+                    request: Default::default(),
+                    sources: vec![],
+                    aggregation: Default::default(),
+                    min_sources: 1,
+                    transform: None,
+                    outlier_guard: None,
+                    dry_run: false,
+                    gas_config: None,
+                    deployments: vec![],
+                    stream: None,
+                    onchain: None,
                 }],
                 database_cleanup: Default::default(),
                 key_storage: Default::default(),
                 metrics: Default::default(),
                 gas_price_feeds: Default::default(),
                 scheduled_tasks: vec![],
+                fetch_cache: Default::default(),
+                fetch_retry: Default::default(),
+                rate_limits: Default::default(),
             }
         }
 
@@ -469,6 +1007,9 @@ mod tests {
                 check_frequency: 60,
                 contract_address: "0x0".to_string(),
                 contract_type: "flux".to_string(),
+                setter_signature: None,
+                custom_abi: None,
+                custom_errors: Vec::new(),
                 read_contract_config: false,
                 minimum_update_frequency: 60,
                 deviation_threshold_pct: 1.0,
@@ -479,6 +1020,17 @@ mod tests {
                 min_value: Some(I256::try_from(1000).unwrap()),
                 max_value: Some(I256::try_from(1000000).unwrap()),
                 data_retention_days: 7,
+                request: Default::default(),
+                sources: vec![],
+                aggregation: Default::default(),
+                min_sources: 1,
+                transform: None,
+                outlier_guard: None,
+                dry_run: false,
+                gas_config: None,
+                deployments: vec![],
+                stream: None,
+                onchain: None,
             };
 
             // Test value within bounds
@@ -519,6 +1071,9 @@ mod tests {
                 metrics: Default::default(),
                 gas_price_feeds: Default::default(),
                 scheduled_tasks: vec![],
+                fetch_cache: Default::default(),
+                fetch_retry: Default::default(),
+                rate_limits: Default::default(),
             };
 
             // NetworkManager::new is async, so we can't test it in a sync test
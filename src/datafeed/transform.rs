@@ -0,0 +1,360 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use thiserror::Error;
+
+/// Name of the variable that refers to the value extracted from the feed source
+pub const VALUE_VARIABLE: &str = "value";
+
+/// Errors that can occur when parsing or evaluating a transform expression
+#[derive(Debug, Error)]
+pub enum TransformError {
+    #[error("Invalid transform expression '{expression}': {reason}")]
+    Parse { expression: String, reason: String },
+
+    #[error("Transform references feed '{0}' which has no value yet")]
+    MissingFeedValue(String),
+
+    #[error("Transform produced a non-finite result: {0}")]
+    NonFinite(f64),
+}
+
+/// Latest values of all feeds run by a `FeedManager`, used to resolve
+/// feed references in transform expressions
+#[derive(Debug, Default)]
+pub struct FeedValueStore {
+    values: RwLock<HashMap<String, f64>>,
+}
+
+impl FeedValueStore {
+    /// Creates an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the latest value of a feed
+    pub fn set(&self, feed_name: &str, value: f64) {
+        let mut values = self.values.write().unwrap_or_else(|e| e.into_inner());
+        values.insert(feed_name.to_string(), value);
+    }
+
+    /// Returns the latest value of a feed, if one has been recorded
+    pub fn get(&self, feed_name: &str) -> Option<f64> {
+        let values = self.values.read().unwrap_or_else(|e| e.into_inner());
+        values.get(feed_name).copied()
+    }
+}
+
+/// A parsed arithmetic expression applied to an extracted feed value
+///
+/// Supports numbers, `+ - * / ^`, parentheses, unary minus, the variable
+/// `value` and references to other feeds by name, e.g. `value * eth_usd`.
+#[derive(Debug, Clone)]
+pub struct Transform {
+    expression: String,
+    root: Expr,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(f64),
+    Variable(String),
+    Negate(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+}
+
+impl Transform {
+    /// Parses a transform expression
+    pub fn parse(expression: &str) -> Result<Self> {
+        let parse_error = |reason: String| TransformError::Parse {
+            expression: expression.to_string(),
+            reason,
+        };
+
+        let tokens = tokenize(expression).map_err(parse_error)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let root = parser.expression().map_err(parse_error)?;
+
+        if let Some(token) = parser.peek() {
+            return Err(parse_error(format!("unexpected token {token:?}")).into());
+        }
+
+        Ok(Self {
+            expression: expression.to_string(),
+            root,
+        })
+    }
+
+    /// Returns the original expression text
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Returns the names of other feeds referenced by the expression
+    pub fn feed_references(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        collect_variables(&self.root, &mut names);
+        names.retain(|name| name != VALUE_VARIABLE);
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Evaluates the expression for an extracted value, resolving feed references from the store
+    pub fn apply(&self, value: f64, feed_values: &FeedValueStore) -> Result<f64> {
+        let result = evaluate(&self.root, &|name| {
+            if name == VALUE_VARIABLE {
+                Ok(value)
+            } else {
+                feed_values
+                    .get(name)
+                    .ok_or_else(|| TransformError::MissingFeedValue(name.to_string()).into())
+            }
+        })
+        .with_context(|| format!("Failed to apply transform '{}'", self.expression))?;
+
+        if !result.is_finite() {
+            return Err(TransformError::NonFinite(result).into());
+        }
+
+        Ok(result)
+    }
+}
+
+fn collect_variables(expr: &Expr, names: &mut Vec<String>) {
+    match expr {
+        Expr::Number(_) => {}
+        Expr::Variable(name) => names.push(name.clone()),
+        Expr::Negate(inner) => collect_variables(inner, names),
+        Expr::Binary(_, lhs, rhs) => {
+            collect_variables(lhs, names);
+            collect_variables(rhs, names);
+        }
+    }
+}
+
+fn evaluate(expr: &Expr, resolve: &dyn Fn(&str) -> Result<f64>) -> Result<f64> {
+    Ok(match expr {
+        Expr::Number(n) => *n,
+        Expr::Variable(name) => resolve(name)?,
+        Expr::Negate(inner) => -evaluate(inner, resolve)?,
+        Expr::Binary(op, lhs, rhs) => {
+            let lhs = evaluate(lhs, resolve)?;
+            let rhs = evaluate(rhs, resolve)?;
+            match op {
+                Op::Add => lhs + rhs,
+                Op::Sub => lhs - rhs,
+                Op::Mul => lhs * rhs,
+                Op::Div => {
+                    if rhs == 0.0 {
+                        anyhow::bail!("division by zero");
+                    }
+                    lhs / rhs
+                }
+                Op::Pow => lhs.powf(rhs),
+            }
+        }
+    })
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = expression.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '+' | '-' | '*' | '/' | '^' => {
+                tokens.push(Token::Op(c));
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid number '{text}'"))?;
+                tokens.push(Token::Number(number));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            other => return Err(format!("unexpected character '{other}'")),
+        }
+    }
+
+    if tokens.is_empty() {
+        return Err("expression is empty".to_string());
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser with the usual precedence:
+/// `+ -` < `* /` < unary minus < `^` (right associative)
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.term()?;
+        while let Some(Token::Op(c @ ('+' | '-'))) = self.peek() {
+            let op = if *c == '+' { Op::Add } else { Op::Sub };
+            self.pos += 1;
+            let rhs = self.term()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.unary()?;
+        while let Some(Token::Op(c @ ('*' | '/'))) = self.peek() {
+            let op = if *c == '*' { Op::Mul } else { Op::Div };
+            self.pos += 1;
+            let rhs = self.unary()?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if let Some(Token::Op('-')) = self.peek() {
+            self.pos += 1;
+            return Ok(Expr::Negate(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Expr, String> {
+        let base = self.primary()?;
+        if let Some(Token::Op('^')) = self.peek() {
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return Ok(Expr::Binary(Op::Pow, Box::new(base), Box::new(exponent)));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Ident(name)) => Ok(Expr::Variable(name)),
+            Some(Token::LParen) => {
+                let inner = self.expression()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err("missing closing parenthesis".to_string()),
+                }
+            }
+            Some(token) => Err(format!("unexpected token {token:?}")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(expression: &str, value: f64) -> Result<f64> {
+        Transform::parse(expression)?.apply(value, &FeedValueStore::new())
+    }
+
+    #[test]
+    fn test_identity_and_constants() {
+        assert_eq!(apply("value", 42.0).unwrap(), 42.0);
+        assert_eq!(apply("value / 100", 12345.0).unwrap(), 123.45);
+        assert_eq!(apply("1 / value", 4.0).unwrap(), 0.25);
+    }
+
+    #[test]
+    fn test_operator_precedence() {
+        assert_eq!(apply("1 + value * 2", 3.0).unwrap(), 7.0);
+        assert_eq!(apply("(1 + value) * 2", 3.0).unwrap(), 8.0);
+        assert_eq!(apply("2 ^ 3 ^ 2", 0.0).unwrap(), 512.0);
+        assert_eq!(apply("-value ^ 2", 3.0).unwrap(), -9.0);
+        assert_eq!(apply("10 - 4 - 3", 0.0).unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_feed_references() {
+        let store = FeedValueStore::new();
+        store.set("eth_usd", 2500.0);
+
+        let transform = Transform::parse("value * eth_usd").unwrap();
+        assert_eq!(transform.feed_references(), vec!["eth_usd".to_string()]);
+        assert_eq!(transform.apply(0.05, &store).unwrap(), 125.0);
+    }
+
+    #[test]
+    fn test_missing_feed_value() {
+        let transform = Transform::parse("value * eth_usd").unwrap();
+        let err = transform.apply(0.05, &FeedValueStore::new()).unwrap_err();
+        assert!(err
+            .root_cause()
+            .to_string()
+            .contains("Transform references feed 'eth_usd' which has no value yet"));
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        assert!(apply("1 / value", 0.0).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Transform::parse("").is_err());
+        assert!(Transform::parse("value *").is_err());
+        assert!(Transform::parse("(value + 1").is_err());
+        assert!(Transform::parse("value $ 2").is_err());
+        assert!(Transform::parse("value 2").is_err());
+    }
+}
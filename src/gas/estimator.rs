@@ -1,5 +1,6 @@
 use crate::config::models::{GasConfig, Network};
 use alloy::{
+    network::{Network as AlloyNetwork, TransactionBuilder},
     primitives::{
         utils::{format_units, parse_units},
         U256,
@@ -24,6 +25,13 @@ pub struct GasEstimate {
     pub max_priority_fee_per_gas: Option<U256>,
 }
 
+impl GasEstimate {
+    /// Highest price per gas the transaction can pay (gas price or max fee per gas)
+    pub fn max_price_per_gas(&self) -> U256 {
+        self.gas_price.or(self.max_fee_per_gas).unwrap_or_default()
+    }
+}
+
 /// Gas estimator that handles both legacy and EIP-1559 transactions
 pub struct GasEstimator<T: Transport + Clone, P: Provider<T> + Clone> {
     provider: Arc<P>,
@@ -252,4 +260,61 @@ impl<T: Transport + Clone, P: Provider<T> + Clone> GasEstimator<T, P> {
             }),
         }
     }
+
+    /// Raise the fees of a stuck transaction for its replacement with the same nonce
+    ///
+    /// Both fees go up by the larger of `fee_increase_percent` and
+    /// `min_replacement_bump_percent`, so nodes accept the replacement.
+    pub fn bump_for_replacement(&self, previous: &GasEstimate) -> GasEstimate {
+        let fee_bumping = &self.network_config.gas_config.fee_bumping;
+        let bump_percent = fee_bumping
+            .fee_increase_percent
+            .max(fee_bumping.min_replacement_bump_percent);
+        let bump = |fee: U256| super::utils::replacement_fee(fee, bump_percent);
+
+        GasEstimate {
+            gas_limit: previous.gas_limit,
+            gas_price: previous.gas_price.map(bump),
+            max_fee_per_gas: previous.max_fee_per_gas.map(bump),
+            max_priority_fee_per_gas: previous.max_priority_fee_per_gas.map(bump),
+        }
+    }
+}
+
+/// Set the fees estimated by the node on a transaction without explicit fees
+///
+/// A replacement has to raise the fees of the transaction it replaces, so they
+/// must be known instead of being filled in by the provider when sending.
+pub async fn fill_missing_fees<T, N, P>(
+    provider: &P,
+    tx: &mut N::TransactionRequest,
+    legacy: bool,
+) -> Result<()>
+where
+    T: Transport + Clone,
+    N: AlloyNetwork,
+    N::TransactionRequest: TransactionBuilder<N>,
+    P: Provider<T, N>,
+{
+    if legacy {
+        if tx.gas_price().is_none() {
+            tx.set_gas_price(provider.get_gas_price().await?);
+        }
+        return Ok(());
+    }
+
+    if tx.gas_price().is_some()
+        || (tx.max_fee_per_gas().is_some() && tx.max_priority_fee_per_gas().is_some())
+    {
+        return Ok(());
+    }
+
+    let fees = provider.estimate_eip1559_fees(None).await?;
+    let max_fee = tx.max_fee_per_gas().unwrap_or(fees.max_fee_per_gas);
+    tx.set_max_fee_per_gas(max_fee);
+    if tx.max_priority_fee_per_gas().is_none() {
+        tx.set_max_priority_fee_per_gas(fees.max_priority_fee_per_gas.min(max_fee));
+    }
+
+    Ok(())
 }
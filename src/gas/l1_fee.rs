@@ -0,0 +1,225 @@
+//! L1 fees of transactions on rollups
+//!
+//! On OP Stack chains a transaction pays an L1 data fee on top of
+//! `gas_used * effective_gas_price`. Receipts report it as `l1Fee`; for nodes
+//! that don't, it is read from the `GasPriceOracle` predeploy. On Arbitrum the
+//! L1 component is part of the gas used, so it is reported separately but not
+//! added to the cost again.
+
+use alloy::{
+    network::Ethereum,
+    primitives::{address, Address, Bytes, B256, U256},
+    providers::Provider,
+    rpc::types::{BlockId, TransactionRequest},
+    sol,
+    sol_types::SolCall,
+    transports::Transport,
+};
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+/// The `GasPriceOracle` predeploy of OP Stack chains
+pub const GAS_PRICE_ORACLE: Address = address!("420000000000000000000000000000000000000F");
+
+sol! {
+    interface IGasPriceOracle {
+        function getL1Fee(bytes memory data) external view returns (uint256);
+    }
+}
+
+/// The L1 component of a transaction's cost
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct L1Fee {
+    /// L1 fee in wei
+    pub wei: u128,
+    /// Whether `gas_used * effective_gas_price` already pays for it, as on Arbitrum
+    pub in_gas_used: bool,
+}
+
+impl L1Fee {
+    /// The part of the fee paid on top of `gas_used * effective_gas_price`
+    pub fn extra_wei(&self) -> u128 {
+        if self.in_gas_used {
+            0
+        } else {
+            self.wei
+        }
+    }
+
+    /// Splits the fee evenly across the feeds sharing a transaction, like its gas
+    pub fn split(&self, parts: usize) -> Vec<L1Fee> {
+        if parts == 0 {
+            return Vec::new();
+        }
+
+        let share = self.wei / parts as u128;
+        let remainder = (self.wei % parts as u128) as usize;
+        (0..parts)
+            .map(|i| L1Fee {
+                wei: share + u128::from(i < remainder),
+                in_gas_used: self.in_gas_used,
+            })
+            .collect()
+    }
+}
+
+/// Reads the L1 fee from the fields an L2 node adds to receipts
+///
+/// Returns None if the receipt doesn't have them.
+pub fn l1_fee_from_receipt(
+    l2_type: &str,
+    receipt: &Value,
+    effective_gas_price: u128,
+) -> Option<L1Fee> {
+    let quantity = |field: &str| -> Option<u128> {
+        let value = serde_json::from_value::<U256>(receipt.get(field)?.clone()).ok()?;
+        Some(value.saturating_to())
+    };
+
+    match l2_type {
+        "op_stack" => Some(L1Fee {
+            wei: quantity("l1Fee")?,
+            in_gas_used: false,
+        }),
+        "arbitrum" => Some(L1Fee {
+            wei: quantity("gasUsedForL1")?.saturating_mul(effective_gas_price),
+            in_gas_used: true,
+        }),
+        _ => Some(L1Fee::default()),
+    }
+}
+
+/// Returns the L1 fee of a mined transaction, zero for networks that aren't rollups
+///
+/// Failures are logged and count as no L1 fee, so they never fail a submission.
+pub async fn fetch_l1_fee<T, P>(
+    network: &str,
+    l2_type: &str,
+    provider: &P,
+    tx_hash: B256,
+    effective_gas_price: u128,
+) -> L1Fee
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum>,
+{
+    if l2_type == "none" {
+        return L1Fee::default();
+    }
+
+    match read_l1_fee(l2_type, provider, tx_hash, effective_gas_price).await {
+        Ok(fee) => {
+            debug!("L1 fee of 0x{:x} on {}: {} wei", tx_hash, network, fee.wei);
+            fee
+        }
+        Err(e) => {
+            warn!(
+                "Failed to read the L1 fee of 0x{:x} on {}, leaving it out of the cost: {}",
+                tx_hash, network, e
+            );
+            L1Fee::default()
+        }
+    }
+}
+
+async fn read_l1_fee<T, P>(
+    l2_type: &str,
+    provider: &P,
+    tx_hash: B256,
+    effective_gas_price: u128,
+) -> Result<L1Fee>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum>,
+{
+    let receipt: Option<Value> = provider
+        .raw_request("eth_getTransactionReceipt".into(), (tx_hash,))
+        .await?;
+    let receipt = receipt.ok_or_else(|| anyhow!("receipt not found"))?;
+    if let Some(fee) = l1_fee_from_receipt(l2_type, &receipt, effective_gas_price) {
+        return Ok(fee);
+    }
+    if l2_type != "op_stack" {
+        bail!("receipt has no gasUsedForL1");
+    }
+
+    let raw_tx = provider
+        .get_raw_transaction_by_hash(tx_hash)
+        .await?
+        .ok_or_else(|| anyhow!("transaction not found"))?;
+    let block = receipt
+        .get("blockNumber")
+        .and_then(|block| serde_json::from_value::<U256>(block.clone()).ok())
+        .map_or(BlockId::latest(), |block| BlockId::number(block.to()));
+
+    oracle_l1_fee(provider, raw_tx, block).await
+}
+
+/// Asks the `GasPriceOracle` for the L1 fee of a signed transaction
+async fn oracle_l1_fee<T, P>(provider: &P, raw_tx: Bytes, block: BlockId) -> Result<L1Fee>
+where
+    T: Transport + Clone,
+    P: Provider<T, Ethereum>,
+{
+    let call = IGasPriceOracle::getL1FeeCall { data: raw_tx };
+    let tx = TransactionRequest::default()
+        .to(GAS_PRICE_ORACLE)
+        .input(call.abi_encode().into());
+    let result = provider.call(&tx).block(block).await?;
+
+    let decoded = IGasPriceOracle::getL1FeeCall::abi_decode_returns(&result, true)?;
+    Ok(L1Fee {
+        wei: decoded._0.saturating_to(),
+        in_gas_used: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_l1_fee_from_op_stack_receipt() {
+        let receipt = json!({ "gasUsed": "0x5208", "l1Fee": "0x2386f26fc10000" });
+        let fee = l1_fee_from_receipt("op_stack", &receipt, 1_000_000).unwrap();
+
+        assert_eq!(fee.wei, 10_000_000_000_000_000);
+        assert_eq!(fee.extra_wei(), fee.wei);
+        // Older nodes leave the field out, the oracle is asked instead
+        assert_eq!(l1_fee_from_receipt("op_stack", &json!({}), 1_000_000), None);
+    }
+
+    #[test]
+    fn test_l1_fee_from_arbitrum_receipt() {
+        let receipt = json!({ "gasUsed": "0x30d40", "gasUsedForL1": "0x186a0" });
+        let fee = l1_fee_from_receipt("arbitrum", &receipt, 10_000_000).unwrap();
+
+        assert_eq!(fee.wei, 100_000 * 10_000_000);
+        // Already paid for by the gas used
+        assert!(fee.in_gas_used);
+        assert_eq!(fee.extra_wei(), 0);
+    }
+
+    #[test]
+    fn test_l1_fee_without_l2() {
+        let receipt = json!({ "l1Fee": "0x1" });
+        assert_eq!(
+            l1_fee_from_receipt("none", &receipt, 1),
+            Some(L1Fee::default())
+        );
+    }
+
+    #[test]
+    fn test_split_l1_fee() {
+        let fee = L1Fee {
+            wei: 10,
+            in_gas_used: false,
+        };
+        let shares: Vec<u128> = fee.split(3).iter().map(|share| share.wei).collect();
+
+        assert_eq!(shares, vec![4, 3, 3]);
+        assert!(fee.split(0).is_empty());
+    }
+}
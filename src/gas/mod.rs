@@ -1,9 +1,11 @@
 pub mod estimator;
+pub mod l1_fee;
 pub mod transaction_builder;
 pub mod utils;
 
 #[cfg(test)]
 mod tests;
 
-pub use estimator::{GasEstimate, GasEstimator};
+pub use estimator::{fill_missing_fees, GasEstimate, GasEstimator};
+pub use l1_fee::{fetch_l1_fee, L1Fee};
 pub use transaction_builder::GasAwareTransactionBuilder;
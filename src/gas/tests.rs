@@ -21,10 +21,20 @@ mod tests {
                         max_retries: 3,
                         initial_wait_seconds: 30,
                         fee_increase_percent: 10.0,
+                        min_replacement_bump_percent: 12.5,
+                        cancel_after_max_retries: false,
                     },
                 },
                 gas_token: "ethereum".to_string(),
                 gas_token_symbol: "ETH".to_string(),
+                batching: Default::default(),
+                confirmations: 1,
+                rpc_endpoints: Vec::new(),
+                rpc_failover: Default::default(),
+                read_quorum: Default::default(),
+                check_feeds_on_new_heads: false,
+                chain_id: None,
+                l2_type: "none".to_string(),
             }
         }
 
@@ -275,12 +285,15 @@ mod tests {
                 max_retries: 5,
                 initial_wait_seconds: 45,
                 fee_increase_percent: 15.0,
+                min_replacement_bump_percent: 12.5,
+                cancel_after_max_retries: true,
             };
 
             assert!(config.enabled);
             assert_eq!(config.max_retries, 5);
             assert_eq!(config.initial_wait_seconds, 45);
             assert_eq!(config.fee_increase_percent, 15.0);
+            assert!(config.cancel_after_max_retries);
         }
 
         #[test]
@@ -322,6 +335,14 @@ mod tests {
                     gas_config: Default::default(),
                     gas_token: "ethereum".to_string(),
                     gas_token_symbol: "ETH".to_string(),
+                    batching: Default::default(),
+                    confirmations: 1,
+                    rpc_endpoints: Vec::new(),
+                    rpc_failover: Default::default(),
+                    read_quorum: Default::default(),
+                    check_feeds_on_new_heads: false,
+                    chain_id: None,
+                    l2_type: "none".to_string(),
                 },
                 Network {
                     name: "polygon".to_string(),
@@ -330,6 +351,14 @@ mod tests {
                     gas_config: Default::default(),
                     gas_token: "matic".to_string(),
                     gas_token_symbol: "MATIC".to_string(),
+                    batching: Default::default(),
+                    confirmations: 1,
+                    rpc_endpoints: Vec::new(),
+                    rpc_failover: Default::default(),
+                    read_quorum: Default::default(),
+                    check_feeds_on_new_heads: false,
+                    chain_id: None,
+                    l2_type: "none".to_string(),
                 },
             ];
 
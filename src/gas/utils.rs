@@ -116,6 +116,24 @@ pub fn calculate_fee_bump(base_fee: U256, attempt: u32, increase_percent: f64) -
     U256::from(bumped)
 }
 
+/// Calculate the fee of a same-nonce replacement
+///
+/// Rounds up, so the replacement pays at least `bump_percent` more than the
+/// transaction it replaces, as nodes require.
+///
+/// # Arguments
+/// * `previous_fee` - The fee of the transaction being replaced in wei
+/// * `bump_percent` - The minimum percentage increase
+///
+/// # Returns
+/// The replacement fee in wei
+pub fn replacement_fee(previous_fee: U256, bump_percent: f64) -> U256 {
+    // Per hundred thousand, so fractional percentages like 12.5 are exact
+    let factor = ((100.0 + bump_percent) * 1000.0).ceil() as u64;
+    let scaled = previous_fee.saturating_mul(U256::from(factor));
+    scaled.div_ceil(U256::from(100_000))
+}
+
 /// Format wei as a human-readable string with unit
 ///
 /// # Arguments
@@ -213,6 +231,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_replacement_fee() {
+        let fee = U256::from(100_000_000_000u64); // 100 gwei
+        assert_eq!(replacement_fee(fee, 12.5), U256::from(112_500_000_000u64));
+        assert_eq!(replacement_fee(fee, 10.0), U256::from(110_000_000_000u64));
+
+        // Rounded up, so the increase never falls short of the percentage
+        assert_eq!(replacement_fee(U256::from(7), 10.0), U256::from(8));
+        assert_eq!(replacement_fee(U256::ZERO, 10.0), U256::ZERO);
+    }
+
     #[test]
     fn test_format_wei() {
         assert_eq!(
@@ -193,6 +193,9 @@ impl GasPriceManager {
     }
 
     /// Calculate USD cost for a gas transaction
+    ///
+    /// `l1_fee_wei` is the L1 fee a rollup transaction pays on top of its gas,
+    /// zero on other networks.
     pub async fn calculate_usd_cost(
         &self,
         network: &str,
@@ -200,11 +203,12 @@ impl GasPriceManager {
         transaction_hash: &str,
         gas_used: u64,
         gas_price_wei: u128,
+        l1_fee_wei: u128,
     ) -> Option<GasCostUsd> {
         let price = self.get_price(network).await?;
 
         // Convert wei to native token (1 token = 10^18 wei)
-        let gas_cost_native = (gas_used as f64 * gas_price_wei as f64) / 1e18;
+        let gas_cost_native = (gas_used as f64 * gas_price_wei as f64 + l1_fee_wei as f64) / 1e18;
         let total_cost_usd = gas_cost_native * price.price_usd;
 
         Some(GasCostUsd {
@@ -213,6 +217,7 @@ impl GasPriceManager {
             transaction_hash: transaction_hash.to_string(),
             gas_used,
             gas_price_wei,
+            l1_fee_wei,
             gas_token_price_usd: price.price_usd,
             total_cost_usd,
             timestamp: SystemTime::now()
@@ -94,6 +94,8 @@ pub struct GasCostUsd {
     pub transaction_hash: String,
     pub gas_used: u64,
     pub gas_price_wei: u128,
+    /// L1 fee paid on top of the gas on rollups
+    pub l1_fee_wei: u128,
     pub gas_token_price_usd: f64,
     pub total_cost_usd: f64,
     pub timestamp: u64,
@@ -50,7 +50,7 @@ async fn main() -> Result<()> {
     info!("Using configuration file: {:?}", config_path);
 
     // Load and validate configuration
-    let config = match config::load_config(&config_path) {
+    let mut config = match config::load_config(&config_path) {
         Ok(cfg) => {
             info!("Configuration loaded successfully");
             cfg
@@ -61,6 +61,20 @@ async fn main() -> Result<()> {
         }
     };
 
+    // Dry-run mode simulates every datafeed and scheduled task submission
+    if cli.dry_run {
+        warn!("Dry-run mode enabled: transactions will be simulated but never broadcast");
+        for datafeed in &mut config.datafeeds {
+            datafeed.dry_run = true;
+            for deployment in &mut datafeed.deployments {
+                deployment.dry_run = Some(true);
+            }
+        }
+        for task in &mut config.scheduled_tasks {
+            task.dry_run = true;
+        }
+    }
+
     // Initialize metrics configuration
     use crate::metrics::{init_metrics_config, ConfigMetrics};
     init_metrics_config(config.metrics.clone());
@@ -82,7 +96,8 @@ async fn main() -> Result<()> {
     for datafeed in &config.datafeeds {
         info!(
             "Datafeed: {} on network {}",
-            datafeed.name, datafeed.networks
+            datafeed.name,
+            datafeed.network_label()
         );
     }
 
@@ -214,6 +229,7 @@ async fn main() -> Result<()> {
 
     // Now wrap in Arc for sharing across threads
     let network_manager = Arc::new(network_manager);
+    let key_storage: Arc<dyn KeyStorage> = Arc::from(key_storage);
 
     // Initialize database connection (optional - continues if not available)
     info!("Checking for database configuration...");
@@ -327,6 +343,27 @@ async fn main() -> Result<()> {
         None
     };
 
+    // Resume tracking the transactions still pending before the restart.
+    // Nothing is broadcast in dry-run mode, so neither are fee bumps.
+    let tracker = if let (Some(ref pool), false) = (&database_pool, cli.dry_run) {
+        let tracker = Arc::new(network::TransactionTracker::new(
+            &config,
+            Arc::clone(&network_manager),
+            Arc::new(database::PendingTransactionRepository::new(pool.clone())),
+            Arc::new(database::TransactionLogRepository::new(pool.clone())),
+        ));
+
+        match tracker.resume().await {
+            Ok(0) => {}
+            Ok(count) => info!("Resumed tracking {} pending transactions", count),
+            Err(e) => error!("Failed to resume tracking pending transactions: {}", e),
+        }
+
+        Some(tracker)
+    } else {
+        None
+    };
+
     // Initialize and start datafeed monitoring
     let mut feed_manager = if let Some(ref pool) = &database_pool {
         let mut manager = datafeed::FeedManager::new(config.clone(), Arc::clone(&network_manager))
@@ -337,6 +374,16 @@ async fn main() -> Result<()> {
             manager = manager.with_gas_price_manager(Arc::clone(gas_price_manager));
         }
 
+        // Resolve ${secret:NAME} request placeholders from the key storage backend.
+        // The env backend is excluded as it falls back to PRIVATE_KEY for unknown names.
+        if config.key_storage.storage_type != "env" {
+            manager = manager.with_key_storage(Arc::clone(&key_storage));
+        }
+
+        if let Some(ref tracker) = tracker {
+            manager = manager.with_tracker(Arc::clone(tracker));
+        }
+
         manager
     } else {
         let mut manager = datafeed::FeedManager::new(config.clone(), Arc::clone(&network_manager));
@@ -346,6 +393,12 @@ async fn main() -> Result<()> {
             manager = manager.with_gas_price_manager(Arc::clone(gas_price_manager));
         }
 
+        // Resolve ${secret:NAME} request placeholders from the key storage backend.
+        // The env backend is excluded as it falls back to PRIVATE_KEY for unknown names.
+        if config.key_storage.storage_type != "env" {
+            manager = manager.with_key_storage(Arc::clone(&key_storage));
+        }
+
         manager
     };
 
@@ -411,6 +464,10 @@ async fn main() -> Result<()> {
             task_manager = task_manager.with_tx_log_repo(tx_repo);
         }
 
+        if let Some(ref tracker) = tracker {
+            task_manager = task_manager.with_tracker(Arc::clone(tracker));
+        }
+
         task_manager
             .start()
             .await
@@ -439,6 +496,11 @@ async fn main() -> Result<()> {
     if let Some(ref gas_price_manager) = gas_price_manager {
         wallet_monitor = wallet_monitor.with_gas_price_manager(Arc::clone(gas_price_manager));
     }
+    if let Some(ref pool) = database_pool {
+        wallet_monitor = wallet_monitor.with_tx_log_repo(Arc::new(
+            database::TransactionLogRepository::new(pool.clone()),
+        ));
+    }
     tokio::spawn(async move {
         wallet_monitor.start().await;
     });
@@ -506,6 +568,14 @@ mod tests {
                 gas_config: GasConfig::default(),
                 gas_token: "ethereum".to_string(),
                 gas_token_symbol: "ETH".to_string(),
+                batching: Default::default(),
+                confirmations: 1,
+                rpc_endpoints: Vec::new(),
+                rpc_failover: Default::default(),
+                read_quorum: Default::default(),
+                check_feeds_on_new_heads: false,
+                chain_id: None,
+                l2_type: "none".to_string(),
             }],
             datafeeds: vec![],
             database_cleanup: DatabaseCleanupConfig::default(),
@@ -520,6 +590,9 @@ mod tests {
             metrics: MetricsConfig::default(),
             gas_price_feeds: GasPriceFeedConfig::default(),
             scheduled_tasks: vec![],
+            fetch_cache: Default::default(),
+            fetch_retry: Default::default(),
+            rate_limits: Default::default(),
         }
     }
 
@@ -216,7 +216,11 @@ impl ConfigMetrics {
         }
 
         // Set datafeed configs
-        for datafeed in &config.datafeeds {
+        for datafeed in config
+            .datafeeds
+            .iter()
+            .flat_map(crate::config::models::Datafeed::expand_deployments)
+        {
             Self::set_datafeed_config(
                 &datafeed.name,
                 &datafeed.networks,
@@ -247,7 +251,7 @@ impl ConfigMetrics {
 }
 
 /// Sanitize URL to remove credentials
-fn sanitize_url(url: &str) -> String {
+pub(crate) fn sanitize_url(url: &str) -> String {
     if let Ok(parsed) = url::Url::parse(url) {
         let mut sanitized = parsed.clone();
         sanitized.set_username("").ok();
@@ -384,6 +388,14 @@ mod tests {
                 gas_config: Default::default(),
                 gas_token: "ethereum".to_string(),
                 gas_token_symbol: "ETH".to_string(),
+                batching: Default::default(),
+                confirmations: 1,
+                rpc_endpoints: Vec::new(),
+                rpc_failover: Default::default(),
+                read_quorum: Default::default(),
+                check_feeds_on_new_heads: false,
+                chain_id: None,
+                l2_type: "none".to_string(),
             }],
             datafeeds: vec![Datafeed {
                 name: "test-feed".to_string(),
@@ -391,6 +403,9 @@ mod tests {
                 check_frequency: 60,
                 contract_address: "0x1234567890123456789012345678901234567890".to_string(),
                 contract_type: "fluxmon".to_string(),
+                setter_signature: None,
+                custom_abi: None,
+                custom_errors: Vec::new(),
                 read_contract_config: false,
                 minimum_update_frequency: 3600,
                 deviation_threshold_pct: 0.5,
@@ -401,6 +416,17 @@ mod tests {
                 min_value: None,
                 max_value: None,
                 data_retention_days: 7,
+                request: Default::default(),
+                sources: vec![],
+                aggregation: Default::default(),
+                min_sources: 1,
+                transform: None,
+                outlier_guard: None,
+                dry_run: false,
+                gas_config: None,
+                deployments: vec![],
+                stream: None,
+                onchain: None,
             }],
             database_cleanup: Default::default(),
             key_storage: KeyStorageConfig {
@@ -412,6 +438,9 @@ mod tests {
             metrics: MetricsConfig::default(),
             gas_price_feeds: GasPriceFeedConfig::default(),
             scheduled_tasks: vec![],
+            fetch_cache: Default::default(),
+            fetch_retry: Default::default(),
+            rate_limits: Default::default(),
         };
 
         ConfigMetrics::record_startup_info(&config);
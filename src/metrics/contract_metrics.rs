@@ -50,6 +50,13 @@ lazy_static! {
         &["feed_name", "network", "reason"]
     ).expect("Failed to create transaction_revert_count metric");
 
+    /// Mined transactions reorged out before reaching the confirmation depth
+    static ref TRANSACTION_REORG_COUNT: CounterVec = register_counter_vec!(
+        "omikuji_transaction_reorgs_total",
+        "Total number of mined transactions reorged out before they were final",
+        &["feed_name", "network"]
+    ).expect("Failed to create transaction_reorg_count metric");
+
     /// Contract permission errors
     static ref CONTRACT_PERMISSION_ERROR_COUNT: CounterVec = register_counter_vec!(
         "omikuji_contract_permission_errors_total",
@@ -60,7 +67,7 @@ lazy_static! {
     /// Transaction confirmation time
     static ref TRANSACTION_CONFIRMATION_TIME_SECONDS: HistogramVec = register_histogram_vec!(
         "omikuji_transaction_confirmation_time_seconds",
-        "Time from submission to confirmation",
+        "Time from submission until the transaction reached the network's confirmation depth",
         &["feed_name", "network"],
         vec![1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0]
     ).expect("Failed to create transaction_confirmation_time metric");
@@ -86,6 +93,13 @@ lazy_static! {
         &["feed_name", "network"],
         vec![1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0]
     ).expect("Failed to create transaction_mempool_time metric");
+
+    /// Oracle payment that can be withdrawn
+    static ref ORACLE_WITHDRAWABLE_PAYMENT: GaugeVec = register_gauge_vec!(
+        "omikuji_oracle_withdrawable_payment",
+        "Payment the oracle can withdraw from the contract, in the payment token's base units",
+        &["feed_name", "network"]
+    ).expect("Failed to create oracle_withdrawable_payment metric");
 }
 
 /// Contract metrics collector
@@ -222,7 +236,9 @@ impl ContractMetrics {
 
     /// Record a transaction revert
     pub fn record_transaction_revert(feed_name: &str, network: &str, reason: &str) {
-        let reason_category = if reason.contains("gas") {
+        let reason_category = if reason.starts_with("panic:") {
+            "panic"
+        } else if reason.contains("gas") {
             "out_of_gas"
         } else if reason.contains("nonce") {
             "nonce_error"
@@ -244,7 +260,19 @@ impl ContractMetrics {
         );
     }
 
-    /// Record transaction confirmation time
+    /// Record a mined transaction that was reorged out before it was final
+    pub fn record_transaction_reorg(feed_name: &str, network: &str, tx_hash: &str) {
+        TRANSACTION_REORG_COUNT
+            .with_label_values(&[feed_name, network])
+            .inc();
+
+        warn!(
+            "Transaction {} for {}/{} was reorged out before it was final",
+            tx_hash, feed_name, network
+        );
+    }
+
+    /// Record the time from submission until the confirmation depth was reached
     pub fn record_confirmation_time(
         feed_name: &str,
         network: &str,
@@ -302,6 +330,13 @@ impl ContractMetrics {
         }
     }
 
+    /// Update the payment the oracle can withdraw
+    pub fn update_withdrawable_payment(feed_name: &str, network: &str, payment: f64) {
+        ORACLE_WITHDRAWABLE_PAYMENT
+            .with_label_values(&[feed_name, network])
+            .set(payment);
+    }
+
     /// Record mempool time
     pub fn record_mempool_time(feed_name: &str, network: &str, mempool_seconds: f64) {
         TRANSACTION_MEMPOOL_TIME_SECONDS
@@ -424,6 +459,12 @@ mod tests {
         ContractMetrics::record_nonce_gap("optimism", 400, 395);
     }
 
+    #[test]
+    fn test_transaction_reorg() {
+        ContractMetrics::record_transaction_reorg("eth_usd", "polygon", "0xabc");
+        ContractMetrics::record_transaction_reorg("eth_usd", "polygon", "0xdef");
+    }
+
     #[test]
     fn test_transaction_revert_categorization() {
         // Test gas-related revert
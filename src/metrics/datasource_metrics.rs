@@ -69,6 +69,42 @@ lazy_static! {
         "Total number of rate limit responses",
         &["feed_name", "network", "url"]
     ).expect("Failed to create rate_limit_count metric");
+
+    /// Retries of failed data source requests
+    static ref RETRY_COUNT: CounterVec = register_counter_vec!(
+        "omikuji_datasource_retries_total",
+        "Total number of retried data source requests",
+        &["feed_name", "network", "reason"]
+    ).expect("Failed to create retry_count metric");
+
+    /// Time spent waiting for the per-host rate limit
+    static ref THROTTLE_WAIT_SECONDS: HistogramVec = register_histogram_vec!(
+        "omikuji_datasource_throttle_wait_seconds",
+        "Time requests waited for the per-host rate limit",
+        &["feed_name", "network", "host"],
+        vec![0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]
+    ).expect("Failed to create throttle_wait metric");
+
+    /// WebSocket stream connection state (1 = connected, 0 = disconnected)
+    static ref STREAM_CONNECTED: GaugeVec = register_gauge_vec!(
+        "omikuji_datasource_stream_connected",
+        "WebSocket stream connection state (1 = connected, 0 = disconnected)",
+        &["feed_name", "network"]
+    ).expect("Failed to create stream_connected metric");
+
+    /// WebSocket stream messages counter
+    static ref STREAM_MESSAGE_COUNT: CounterVec = register_counter_vec!(
+        "omikuji_datasource_stream_messages_total",
+        "Total number of WebSocket stream messages received",
+        &["feed_name", "network", "result"]
+    ).expect("Failed to create stream_message_count metric");
+
+    /// WebSocket stream reconnects counter
+    static ref STREAM_RECONNECT_COUNT: CounterVec = register_counter_vec!(
+        "omikuji_datasource_stream_reconnects_total",
+        "Total number of WebSocket stream reconnect attempts",
+        &["feed_name", "network"]
+    ).expect("Failed to create stream_reconnect_count metric");
 }
 
 /// Data source metrics collector
@@ -259,4 +295,79 @@ impl DatasourceMetrics {
             operation
         );
     }
+
+    /// Record a retried request
+    ///
+    /// `reason` is "network", "server_error" or "rate_limited"
+    pub fn record_retry(feed_name: &str, network: &str, reason: &str, delay: Duration) {
+        if !is_metric_enabled(MetricCategory::Datasource) {
+            return;
+        }
+
+        RETRY_COUNT
+            .with_label_values(&[feed_name, network, reason])
+            .inc();
+
+        debug!(
+            "Retrying request for {}/{} after {} in {:.3}s",
+            feed_name,
+            network,
+            reason,
+            delay.as_secs_f64()
+        );
+    }
+
+    /// Record time a request waited for the per-host rate limit
+    pub fn record_throttle_wait(feed_name: &str, network: &str, host: &str, wait: Duration) {
+        if !is_metric_enabled(MetricCategory::Datasource) {
+            return;
+        }
+
+        THROTTLE_WAIT_SECONDS
+            .with_label_values(&[feed_name, network, host])
+            .observe(wait.as_secs_f64());
+    }
+
+    /// Record a WebSocket stream connection state change
+    pub fn set_stream_connected(feed_name: &str, network: &str, connected: bool) {
+        if !is_metric_enabled(MetricCategory::Datasource) {
+            return;
+        }
+
+        STREAM_CONNECTED
+            .with_label_values(&[feed_name, network])
+            .set(if connected { 1.0 } else { 0.0 });
+    }
+
+    /// Record a received WebSocket stream message
+    ///
+    /// `has_value` is false for messages without a value (acks, heartbeats)
+    pub fn record_stream_message(feed_name: &str, network: &str, has_value: bool) {
+        if !is_metric_enabled(MetricCategory::Datasource) {
+            return;
+        }
+
+        let result = if has_value { "value" } else { "ignored" };
+        STREAM_MESSAGE_COUNT
+            .with_label_values(&[feed_name, network, result])
+            .inc();
+    }
+
+    /// Record a WebSocket stream reconnect attempt
+    pub fn record_stream_reconnect(feed_name: &str, network: &str, delay: Duration) {
+        if !is_metric_enabled(MetricCategory::Datasource) {
+            return;
+        }
+
+        STREAM_RECONNECT_COUNT
+            .with_label_values(&[feed_name, network])
+            .inc();
+
+        debug!(
+            "Reconnecting stream for {}/{} in {:.1}s",
+            feed_name,
+            network,
+            delay.as_secs_f64()
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use crate::contracts::multicall::split_gas;
+use crate::gas::L1Fee;
 use alloy::{primitives::U256, rpc::types::TransactionReceipt};
 use lazy_static::lazy_static;
 use prometheus::{
@@ -38,6 +40,14 @@ lazy_static! {
         vec![1e14, 5e14, 1e15, 5e15, 1e16, 5e16, 1e17, 5e17, 1e18]
     ).expect("Failed to create transaction_cost_wei metric");
 
+    /// L1 fee of transactions on rollups in wei, part of the transaction cost
+    static ref TRANSACTION_L1_FEE_WEI: HistogramVec = register_histogram_vec!(
+        "omikuji_transaction_l1_fee_wei",
+        "L1 fee of transactions on rollups in wei",
+        &["feed_name", "network"],
+        vec![1e11, 1e12, 1e13, 1e14, 5e14, 1e15, 5e15, 1e16, 1e17]
+    ).expect("Failed to create transaction_l1_fee_wei metric");
+
     /// Number of transactions counter
     static ref TRANSACTION_COUNT: CounterVec = register_counter_vec!(
         "omikuji_transaction_count",
@@ -107,12 +117,60 @@ pub struct GasMetrics;
 
 impl GasMetrics {
     /// Record gas metrics from a transaction receipt
+    ///
+    /// The L1 fee of a rollup transaction is part of its total cost.
     pub fn record_transaction(
         feed_name: &str,
         network: &str,
         receipt: &TransactionReceipt,
         gas_limit: U256,
         tx_type: &str,
+        l1_fee: L1Fee,
+    ) {
+        Self::record_gas(
+            feed_name,
+            network,
+            receipt,
+            receipt.gas_used as u64,
+            gas_limit.to::<u64>(),
+            tx_type,
+            l1_fee,
+        );
+    }
+
+    /// Record gas metrics from the receipt of a transaction shared by several feeds
+    ///
+    /// The gas used, the gas limit and the L1 fee are split evenly across the feeds.
+    pub fn record_shared_transaction(
+        feed_names: &[String],
+        network: &str,
+        receipt: &TransactionReceipt,
+        gas_limit: U256,
+        tx_type: &str,
+        l1_fee: L1Fee,
+    ) {
+        let gas_used = split_gas(receipt.gas_used as u64, feed_names.len());
+        let gas_limit = split_gas(gas_limit.to::<u64>(), feed_names.len());
+        let l1_fees = l1_fee.split(feed_names.len());
+
+        for (((feed_name, gas_used), gas_limit), l1_fee) in
+            feed_names.iter().zip(gas_used).zip(gas_limit).zip(l1_fees)
+        {
+            Self::record_gas(
+                feed_name, network, receipt, gas_used, gas_limit, tx_type, l1_fee,
+            );
+        }
+    }
+
+    /// Record the gas one feed used in a transaction
+    fn record_gas(
+        feed_name: &str,
+        network: &str,
+        receipt: &TransactionReceipt,
+        gas_used: u64,
+        gas_limit: u64,
+        tx_type: &str,
+        l1_fee: L1Fee,
     ) {
         let status = if receipt.status() {
             "success"
@@ -120,16 +178,17 @@ impl GasMetrics {
             "failed"
         };
 
-        // Get gas used and effective gas price
-        let gas_used = receipt.gas_used;
+        // Get effective gas price
         let effective_gas_price = U256::from(receipt.effective_gas_price);
 
         // Calculate metrics
         let gas_used_f64 = gas_used as f64;
-        let gas_limit_f64 = gas_limit.to::<u64>() as f64;
+        let gas_limit_f64 = gas_limit as f64;
         let gas_price_gwei = effective_gas_price.to::<u128>() as f64 / 1e9;
-        let total_cost_wei = U256::from(gas_used).saturating_mul(effective_gas_price);
-        let efficiency_percent = if gas_limit > U256::ZERO {
+        let total_cost_wei = U256::from(gas_used)
+            .saturating_mul(effective_gas_price)
+            .saturating_add(U256::from(l1_fee.extra_wei()));
+        let efficiency_percent = if gas_limit > 0 {
             (gas_used_f64 / gas_limit_f64) * 100.0
         } else {
             0.0
@@ -152,6 +211,12 @@ impl GasMetrics {
             .with_label_values(&[feed_name, network])
             .observe(total_cost_wei.to::<u128>() as f64);
 
+        if l1_fee.wei > 0 {
+            TRANSACTION_L1_FEE_WEI
+                .with_label_values(&[feed_name, network])
+                .observe(l1_fee.wei as f64);
+        }
+
         TRANSACTION_COUNT
             .with_label_values(&[feed_name, network, status, tx_type])
             .inc();
@@ -165,7 +230,7 @@ impl GasMetrics {
             "Transaction gas metrics - Feed: {}, Network: {}, Status: {}, \
             Gas Used: {}, Gas Limit: {}, Efficiency: {:.1}%, \
             Gas Price: {:.2} gwei, Total Cost: {} wei ({:.6} native tokens), \
-            Tx Hash: {:?}",
+            L1 Fee: {} wei, Tx Hash: {:?}",
             feed_name,
             network,
             status,
@@ -175,11 +240,12 @@ impl GasMetrics {
             gas_price_gwei,
             total_cost_wei,
             total_cost_wei.to::<u128>() as f64 / 1e18,
+            l1_fee.wei,
             receipt.transaction_hash
         );
 
         // Warn if efficiency is poor
-        if efficiency_percent < 50.0 && gas_limit > U256::ZERO {
+        if efficiency_percent < 50.0 && gas_limit > 0 {
             warn!(
                 "Low gas efficiency for {} on {}: {:.1}% of limit used. \
                 Consider reducing gas limit.",
@@ -222,15 +288,18 @@ impl GasMetrics {
     }
 
     /// Record gas cost in USD
+    ///
+    /// `l1_fee_wei` is the L1 fee paid on top of the gas, zero outside of rollups.
     pub fn record_usd_cost(
         feed_name: &str,
         network: &str,
         gas_used: u64,
         gas_price_wei: u128,
+        l1_fee_wei: u128,
         gas_token_price_usd: f64,
     ) {
         // Calculate cost in USD
-        let gas_cost_native = (gas_used as f64 * gas_price_wei as f64) / 1e18;
+        let gas_cost_native = (gas_used as f64 * gas_price_wei as f64 + l1_fee_wei as f64) / 1e18;
         let total_cost_usd = gas_cost_native * gas_token_price_usd;
 
         // Update cumulative cost
@@ -240,9 +309,15 @@ impl GasMetrics {
 
         info!(
             "Transaction cost in USD - Feed: {}, Network: {}, \
-            Gas Used: {}, Gas Price: {} wei, Token Price: ${:.2}, \
+            Gas Used: {}, Gas Price: {} wei, L1 Fee: {} wei, Token Price: ${:.2}, \
             Total Cost: ${:.6}",
-            feed_name, network, gas_used, gas_price_wei, gas_token_price_usd, total_cost_usd
+            feed_name,
+            network,
+            gas_used,
+            gas_price_wei,
+            l1_fee_wei,
+            gas_token_price_usd,
+            total_cost_usd
         );
     }
 
@@ -272,10 +347,16 @@ pub struct TransactionDetails {
     pub gas_limit: u64,
     pub gas_used: u64,
     pub gas_price_gwei: f64,
+    /// Gas cost plus the L1 fee paid on top of it
     pub total_cost_wei: u128,
+    /// L1 fee of a rollup transaction, 0 outside of rollups
+    pub l1_fee_wei: u128,
     pub efficiency_percent: f64,
     pub status: String,
     pub tx_type: String,
     pub block_number: u64,
     pub error_message: Option<String>,
+    /// `pending` until the confirmation depth is reached, then `confirmed`
+    /// None for transactions that aren't mined
+    pub confirmation_status: Option<String>,
 }
@@ -22,6 +22,7 @@ pub use datasource_metrics::DatasourceMetrics;
 pub use economic_metrics::EconomicMetrics;
 pub use feed_metrics::FeedMetrics;
 pub use network_metrics::NetworkMetrics;
+pub use performance_metrics::PerformanceMetrics;
 pub use quality_metrics::QualityMetrics;
 pub use recorder::{
     FeedMetricsRecorder, MetricsContext, RetryMetricsRecorder, TimedOperationRecorder,
@@ -84,6 +84,13 @@ lazy_static! {
         "Total number of RPC errors by type",
         &["network", "error_type", "method"]
     ).expect("Failed to create rpc_error_count metric");
+
+    /// Quorum reads whose endpoints returned different values
+    static ref RPC_QUORUM_DISAGREEMENT_COUNT: CounterVec = register_counter_vec!(
+        "omikuji_rpc_quorum_disagreements_total",
+        "Total number of quorum reads whose endpoints disagreed",
+        &["network", "method", "outcome"]
+    ).expect("Failed to create rpc_quorum_disagreement_count metric");
 }
 
 /// Network metrics collector
@@ -239,6 +246,21 @@ impl NetworkMetrics {
         }
     }
 
+    /// Record a quorum read whose endpoints disagreed
+    ///
+    /// The outcome is `outvoted` if enough endpoints still agreed, otherwise `no_quorum`.
+    pub fn record_quorum_disagreement(network: &str, method: &str, quorum_reached: bool) {
+        let outcome = if quorum_reached {
+            "outvoted"
+        } else {
+            "no_quorum"
+        };
+
+        RPC_QUORUM_DISAGREEMENT_COUNT
+            .with_label_values(&[network, method, outcome])
+            .inc();
+    }
+
     /// Get error type from error string
     pub fn classify_rpc_error(error: &str) -> &'static str {
         if error.contains("timeout") {
@@ -21,6 +21,13 @@ lazy_static! {
         &["feed_name", "network", "outlier_type", "action"]
     ).expect("Failed to create outlier_detection_count metric");
 
+    /// Circuit breaker state (1 = open, submissions blocked)
+    static ref CIRCUIT_BREAKER_OPEN: GaugeVec = register_gauge_vec!(
+        "omikuji_circuit_breaker_open",
+        "Whether the outlier circuit breaker is blocking submissions (0/1)",
+        &["feed_name", "network"]
+    ).expect("Failed to create circuit_breaker_open metric");
+
     /// Data consistency score
     static ref DATA_CONSISTENCY_SCORE: GaugeVec = register_gauge_vec!(
         "omikuji_data_consistency_score",
@@ -135,6 +142,13 @@ impl QualityMetrics {
         );
     }
 
+    /// Update the outlier circuit breaker state
+    pub fn set_circuit_breaker_open(feed_name: &str, network: &str, open: bool) {
+        CIRCUIT_BREAKER_OPEN
+            .with_label_values(&[feed_name, network])
+            .set(if open { 1.0 } else { 0.0 });
+    }
+
     /// Update data consistency score
     pub fn update_consistency_score(feed_name: &str, network: &str, score: f64) {
         let clamped_score = score.clamp(0.0, 100.0);
@@ -9,6 +9,7 @@ use alloy::primitives::U256;
 use alloy::rpc::types::TransactionReceipt;
 use anyhow::Result;
 
+use crate::gas::L1Fee;
 use crate::metrics::gas_metrics::GasMetrics;
 use crate::metrics::{ContractMetrics, FeedMetrics};
 
@@ -211,6 +212,7 @@ impl TransactionMetricsRecorder {
         &self,
         receipt: &TransactionReceipt,
         gas_limit: U256,
+        l1_fee: L1Fee,
         submission_time: Option<u64>,
     ) {
         // Record gas metrics
@@ -220,6 +222,7 @@ impl TransactionMetricsRecorder {
             receipt,
             gas_limit,
             &self.tx_type,
+            l1_fee,
         );
 
         // Record confirmation time if submission time is provided
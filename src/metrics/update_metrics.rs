@@ -65,6 +65,27 @@ lazy_static! {
         vec![0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0]
     ).expect("Failed to create update_deviation metric");
 
+    /// Submissions simulated in dry-run mode
+    static ref DRY_RUN_SUBMISSION_COUNT: CounterVec = register_counter_vec!(
+        "omikuji_dry_run_submissions_total",
+        "Total number of submissions simulated in dry-run mode",
+        &["feed_name", "network", "context", "result"]
+    ).expect("Failed to create dry_run_submission_count metric");
+
+    /// Gas limit of the last simulated submission
+    static ref DRY_RUN_GAS_ESTIMATE: GaugeVec = register_gauge_vec!(
+        "omikuji_dry_run_gas_estimate",
+        "Gas limit estimated for the last simulated submission",
+        &["feed_name", "network"]
+    ).expect("Failed to create dry_run_gas_estimate metric");
+
+    /// Estimated USD cost of the last simulated submission
+    static ref DRY_RUN_ESTIMATED_COST_USD: GaugeVec = register_gauge_vec!(
+        "omikuji_dry_run_estimated_cost_usd",
+        "Estimated USD cost of the last simulated submission",
+        &["feed_name", "network"]
+    ).expect("Failed to create dry_run_estimated_cost_usd metric");
+
     /// Update attempts counter
     static ref UPDATE_ATTEMPT_COUNT: CounterVec = register_counter_vec!(
         "omikuji_update_attempts_total",
@@ -102,6 +123,15 @@ pub enum SkipReason {
     TooSoon,
     NoChange,
     BelowThreshold,
+    InsufficientSources,
+    OutlierRejected,
+    StaleSource,
+    CircuitOpen,
+    NotOracle,
+    RoundAlreadySubmitted,
+    RestartDelay,
+    InsufficientFunds,
+    PendingTransaction,
     Error,
 }
 
@@ -112,6 +142,15 @@ impl SkipReason {
             SkipReason::TooSoon => "too_soon",
             SkipReason::NoChange => "no_change",
             SkipReason::BelowThreshold => "below_threshold",
+            SkipReason::InsufficientSources => "insufficient_sources",
+            SkipReason::OutlierRejected => "outlier_rejected",
+            SkipReason::StaleSource => "stale_source",
+            SkipReason::CircuitOpen => "circuit_open",
+            SkipReason::NotOracle => "not_oracle",
+            SkipReason::RoundAlreadySubmitted => "round_already_submitted",
+            SkipReason::RestartDelay => "restart_delay",
+            SkipReason::InsufficientFunds => "insufficient_funds",
+            SkipReason::PendingTransaction => "pending_transaction",
             SkipReason::Error => "error",
         }
     }
@@ -149,6 +188,15 @@ impl UpdateMetrics {
                 "too_soon",
                 "no_change",
                 "below_threshold",
+                "insufficient_sources",
+                "outlier_rejected",
+                "stale_source",
+                "circuit_open",
+                "not_oracle",
+                "round_already_submitted",
+                "restart_delay",
+                "insufficient_funds",
+                "pending_transaction",
                 "error",
             ] {
                 CONSECUTIVE_SKIPPED_UPDATES
@@ -273,4 +321,28 @@ impl UpdateMetrics {
             .with_label_values(&[feed_name, network, result])
             .inc();
     }
+
+    /// Record a submission simulated in dry-run mode
+    pub fn record_dry_run(
+        feed_name: &str,
+        network: &str,
+        context: &str,
+        result: &str,
+        gas_limit: u64,
+        estimated_cost_usd: Option<f64>,
+    ) {
+        DRY_RUN_SUBMISSION_COUNT
+            .with_label_values(&[feed_name, network, context, result])
+            .inc();
+
+        DRY_RUN_GAS_ESTIMATE
+            .with_label_values(&[feed_name, network])
+            .set(gas_limit as f64);
+
+        if let Some(cost) = estimated_cost_usd {
+            DRY_RUN_ESTIMATED_COST_USD
+                .with_label_values(&[feed_name, network])
+                .set(cost);
+        }
+    }
 }
@@ -0,0 +1,175 @@
+//! Waiting for mined transactions to reach the network's confirmation depth
+//!
+//! A receipt only says which block included the transaction. On chains that
+//! reorg, that block can be replaced. Networks with `confirmations` above 1
+//! treat a transaction as final once its block is that many blocks deep and
+//! its receipt still points at it. A transaction that moved to another block
+//! is followed there. One that no block includes anymore was reorged out.
+//!
+//! A missing receipt alone doesn't prove a reorg, a node behind a load
+//! balancer may just not have the block yet. The transaction counts as
+//! reorged out once its block was replaced by another one at the same height,
+//! or its receipt stayed missing for several polls in a row.
+
+use alloy::{
+    eips::BlockNumberOrTag,
+    network::{primitives::HeaderResponse, BlockResponse, Network, ReceiptResponse},
+    providers::Provider,
+    rpc::types::BlockTransactionsKind,
+    transports::Transport,
+};
+use anyhow::{Context, Result};
+use tokio::time::Duration;
+use tracing::{debug, warn};
+
+/// Interval between head and receipt checks while waiting for confirmations
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Upper bound on the wait for every block of the confirmation depth
+const MAX_WAIT_PER_BLOCK: Duration = Duration::from_secs(60);
+
+/// Consecutive polls without a receipt after which a transaction whose block
+/// is still unknown counts as reorged out
+const MISSING_RECEIPT_POLLS: u32 = 3;
+
+/// Outcome of waiting for a mined transaction to be buried
+#[derive(Debug)]
+pub enum Confirmation<R> {
+    /// Buried at the configured depth, with the receipt of the including block
+    Confirmed(R),
+    /// The including block was reorged away and no other block includes it
+    Reorged,
+}
+
+/// Number of blocks from `block` up to and including the `head`
+pub fn depth(head: u64, block: u64) -> u64 {
+    if head < block {
+        0
+    } else {
+        head - block + 1
+    }
+}
+
+/// Waits until the transaction of `receipt` is `confirmations` blocks deep
+///
+/// Returns at once for a depth of 1. Fails if the depth isn't reached within
+/// a minute per block.
+pub async fn wait_for_confirmations<T, N, P>(
+    provider: &P,
+    receipt: N::ReceiptResponse,
+    confirmations: u64,
+) -> Result<Confirmation<N::ReceiptResponse>>
+where
+    T: Transport + Clone,
+    N: Network,
+    P: Provider<T, N>,
+{
+    if confirmations <= 1 {
+        return Ok(Confirmation::Confirmed(receipt));
+    }
+
+    let tx_hash = receipt.transaction_hash();
+    let timeout = MAX_WAIT_PER_BLOCK * confirmations as u32;
+    let wait = async {
+        let mut receipt = receipt;
+        let mut missing_polls = 0;
+        loop {
+            match provider.get_transaction_receipt(tx_hash).await? {
+                None => {
+                    missing_polls += 1;
+                    let canonical = is_canonical(provider, &receipt).await?;
+                    if is_reorged(missing_polls, canonical) {
+                        return Ok(Confirmation::Reorged);
+                    }
+                    debug!(
+                        "Receipt of 0x{:x} is missing ({}/{} polls), checking again",
+                        tx_hash, missing_polls, MISSING_RECEIPT_POLLS
+                    );
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    continue;
+                }
+                Some(current) if current.block_hash() != receipt.block_hash() => {
+                    warn!(
+                        "Transaction 0x{:x} moved from block {:?} to {:?} in a reorg",
+                        tx_hash,
+                        receipt.block_number(),
+                        current.block_number()
+                    );
+                    receipt = current;
+                }
+                Some(_) => {}
+            }
+            missing_polls = 0;
+
+            let head = provider.get_block_number().await?;
+            let block = receipt.block_number().unwrap_or(head);
+            let reached = depth(head, block);
+            if reached >= confirmations {
+                return Ok(Confirmation::Confirmed(receipt));
+            }
+
+            debug!(
+                "Transaction 0x{:x} has {}/{} confirmations",
+                tx_hash, reached, confirmations
+            );
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    };
+
+    tokio::time::timeout(timeout, wait).await.with_context(|| {
+        format!("Timed out waiting for {confirmations} confirmations of 0x{tx_hash:x}")
+    })?
+}
+
+/// Returns whether the block of the receipt is still the canonical one at its
+/// height, or `None` if the node doesn't know that height
+async fn is_canonical<T, N, P>(provider: &P, receipt: &N::ReceiptResponse) -> Result<Option<bool>>
+where
+    T: Transport + Clone,
+    N: Network,
+    P: Provider<T, N>,
+{
+    let (Some(number), Some(hash)) = (receipt.block_number(), receipt.block_hash()) else {
+        return Ok(None);
+    };
+
+    let block = provider
+        .get_block_by_number(
+            BlockNumberOrTag::Number(number),
+            BlockTransactionsKind::Hashes,
+        )
+        .await?;
+    Ok(block.map(|block| block.header().hash() == hash))
+}
+
+/// Decides whether a transaction without a receipt was reorged out
+///
+/// A different block at its height proves it; otherwise the receipt must stay
+/// missing for `MISSING_RECEIPT_POLLS` polls in a row.
+fn is_reorged(missing_polls: u32, canonical: Option<bool>) -> bool {
+    canonical == Some(false) || missing_polls >= MISSING_RECEIPT_POLLS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth() {
+        assert_eq!(depth(100, 100), 1);
+        assert_eq!(depth(105, 100), 6);
+        // The node serving the head is behind the one serving the receipt
+        assert_eq!(depth(99, 100), 0);
+    }
+
+    #[test]
+    fn test_missing_receipt_is_not_a_reorg_at_once() {
+        // The node may not have the block yet
+        assert!(!is_reorged(1, None));
+        assert!(!is_reorged(1, Some(true)));
+        assert!(is_reorged(MISSING_RECEIPT_POLLS, None));
+
+        // Another block at the same height is proof enough
+        assert!(is_reorged(1, Some(false)));
+    }
+}
@@ -0,0 +1,259 @@
+//! New block subscriptions over WebSocket endpoints
+//!
+//! Networks with a `ws://` or `wss://` endpoint subscribe to `newHeads` on the
+//! preferred one. Every head updates the chain head, block time and sync
+//! status metrics and is published to the [`HeadTicker`]s of the network, so
+//! pending transaction tracking and, with `check_feeds_on_new_heads`, feed
+//! checks run on every block instead of on a fixed interval. A dropped
+//! subscription is reconnected and resubscribed with exponential backoff.
+
+use alloy::{
+    providers::{Provider, ProviderBuilder, WsConnect},
+    rpc::types::Header,
+};
+use anyhow::Result;
+use futures::StreamExt;
+use tokio::sync::watch;
+use tokio::time::{Duration, Instant, Interval, MissedTickBehavior};
+use tracing::{debug, info, warn};
+
+use crate::config::models::Network;
+use crate::metrics::config_metrics::sanitize_url;
+use crate::metrics::NetworkMetrics;
+
+/// Age of the latest head up to which the endpoint counts as synced
+const SYNC_TOLERANCE: Duration = Duration::from_secs(120);
+
+/// Delay before the first reconnect, doubled up to [`MAX_RECONNECT_DELAY`]
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Minimum time between two ticks driven by new heads, for chains with fast blocks
+const MIN_HEAD_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The latest block of a network
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Head {
+    pub number: u64,
+    /// Unix time of the block
+    pub timestamp: u64,
+}
+
+/// Returns true for WebSocket URLs
+pub fn is_ws_url(url: &str) -> bool {
+    url.starts_with("ws://") || url.starts_with("wss://")
+}
+
+/// Average block time between two heads, None if `current` doesn't follow `previous`
+pub fn block_time(previous: Head, current: Head) -> Option<f64> {
+    if current.number <= previous.number || current.timestamp < previous.timestamp {
+        return None;
+    }
+
+    let blocks = current.number - previous.number;
+    Some((current.timestamp - previous.timestamp) as f64 / blocks as f64)
+}
+
+/// Subscribes to the new heads of a network if it has a WebSocket endpoint
+///
+/// Returns the receiver of the latest head, None without a WebSocket endpoint.
+pub fn spawn_head_subscription(network: &Network) -> Option<watch::Receiver<Option<Head>>> {
+    let mut endpoints = network.endpoints();
+    endpoints.sort_by_key(|e| e.priority);
+    let url = endpoints.into_iter().find(|e| is_ws_url(&e.url))?.url;
+
+    let (sender, receiver) = watch::channel(None);
+    let network = network.name.clone();
+    tokio::spawn(async move {
+        let mut delay = INITIAL_RECONNECT_DELAY;
+        loop {
+            match follow_heads(&network, &url, &sender).await {
+                // Only reached after receiving heads, so the next failure starts over
+                Ok(()) => delay = INITIAL_RECONNECT_DELAY,
+                Err(e) => warn!(
+                    "newHeads subscription on {} at {} failed: {}",
+                    network,
+                    sanitize_url(&url),
+                    e
+                ),
+            }
+            if sender.is_closed() {
+                return;
+            }
+
+            debug!("Resubscribing to newHeads on {} in {:?}", network, delay);
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    });
+
+    Some(receiver)
+}
+
+/// Follows the heads of one subscription until it ends
+async fn follow_heads(
+    network: &str,
+    url: &str,
+    sender: &watch::Sender<Option<Head>>,
+) -> Result<()> {
+    let provider = ProviderBuilder::new().on_ws(WsConnect::new(url)).await?;
+    let mut heads = provider.subscribe_blocks().await?.into_stream();
+    info!(
+        "Subscribed to newHeads on {} at {}",
+        network,
+        sanitize_url(url)
+    );
+
+    let mut received = false;
+    while let Some(header) = heads.next().await {
+        received = true;
+        record_head(network, &header, sender);
+        if sender.is_closed() {
+            return Ok(());
+        }
+    }
+
+    if received {
+        warn!("newHeads subscription on {} ended", network);
+        Ok(())
+    } else {
+        anyhow::bail!("subscription ended before the first head")
+    }
+}
+
+fn record_head(network: &str, header: &Header, sender: &watch::Sender<Option<Head>>) {
+    let head = Head {
+        number: header.number,
+        timestamp: header.timestamp,
+    };
+
+    NetworkMetrics::update_chain_head(network, head.number);
+    if let Some(block_time) = (*sender.borrow()).and_then(|previous| block_time(previous, head)) {
+        NetworkMetrics::update_block_time(network, block_time);
+    }
+
+    let now = chrono::Utc::now().timestamp().max(0) as u64;
+    let age = now.saturating_sub(head.timestamp);
+    NetworkMetrics::update_sync_status(network, age <= SYNC_TOLERANCE.as_secs());
+
+    sender.send_replace(Some(head));
+}
+
+/// Ticks on every new head of a network, or on a fixed interval without one
+///
+/// The interval remains the fallback when no head arrives in time. Heads that
+/// arrive while the previous tick is handled are coalesced.
+pub struct HeadTicker {
+    heads: Option<watch::Receiver<Option<Head>>>,
+    interval: Interval,
+    last_tick: Option<Instant>,
+}
+
+impl HeadTicker {
+    /// Creates a ticker whose first tick completes at once
+    pub fn new(heads: Option<watch::Receiver<Option<Head>>>, fallback: Duration) -> Self {
+        let mut interval = tokio::time::interval(fallback);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        Self {
+            heads,
+            interval,
+            last_tick: None,
+        }
+    }
+
+    /// Returns true if ticks follow new heads
+    pub fn follows_heads(&self) -> bool {
+        self.heads.is_some()
+    }
+
+    /// Waits for the next head or the fallback interval
+    pub async fn tick(&mut self) {
+        let Some(heads) = self.heads.as_mut() else {
+            self.interval.tick().await;
+            return;
+        };
+
+        if let Some(last_tick) = self.last_tick {
+            tokio::time::sleep_until(last_tick + MIN_HEAD_TICK_INTERVAL).await;
+        }
+
+        tokio::select! {
+            changed = heads.changed() => {
+                if changed.is_err() {
+                    // The subscription stopped, fall back to the interval
+                    self.heads = None;
+                }
+            }
+            _ = self.interval.tick() => {}
+        }
+
+        self.interval.reset();
+        self.last_tick = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_ws_url() {
+        assert!(is_ws_url("wss://eth.example.com"));
+        assert!(is_ws_url("ws://localhost:8546"));
+        assert!(!is_ws_url("https://eth.example.com"));
+    }
+
+    #[test]
+    fn test_block_time() {
+        let previous = Head {
+            number: 100,
+            timestamp: 1_000,
+        };
+        let next = Head {
+            number: 102,
+            timestamp: 1_024,
+        };
+
+        assert_eq!(block_time(previous, next), Some(12.0));
+        // A reorged or repeated head says nothing about the block time
+        assert_eq!(block_time(next, previous), None);
+        assert_eq!(block_time(previous, previous), None);
+    }
+
+    #[tokio::test]
+    async fn test_head_ticker() {
+        let (sender, receiver) = watch::channel(None);
+        let mut ticker = HeadTicker::new(Some(receiver), Duration::from_secs(60));
+        assert!(ticker.follows_heads());
+
+        // The first tick completes at once
+        let start = Instant::now();
+        ticker.tick().await;
+        assert!(start.elapsed() < MIN_HEAD_TICK_INTERVAL);
+
+        sender.send_replace(Some(Head {
+            number: 1,
+            timestamp: 1,
+        }));
+        ticker.tick().await;
+        assert!(start.elapsed() < Duration::from_secs(60));
+
+        // Without a subscription the interval takes over
+        drop(sender);
+        ticker.tick().await;
+        assert!(!ticker.follows_heads());
+    }
+
+    #[tokio::test]
+    async fn test_head_ticker_without_heads() {
+        let mut ticker = HeadTicker::new(None, Duration::from_millis(50));
+        assert!(!ticker.follows_heads());
+
+        let start = Instant::now();
+        ticker.tick().await;
+        ticker.tick().await;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+}
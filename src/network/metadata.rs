@@ -0,0 +1,185 @@
+//! What a network's RPC endpoints report about the chain they serve
+//!
+//! On startup the chain ID of every endpoint is checked against the network's
+//! `chain_id`, or against each other without one, so a mis-pasted URL can't
+//! make Omikuji read from or submit to the wrong chain. Endpoints that don't
+//! answer are left out of routing until a health probe reads the verified
+//! chain ID from them. The client version and whether the latest block has a
+//! base fee are detected for diagnostics.
+
+use alloy::{
+    eips::BlockNumberOrTag, providers::Provider, rpc::types::BlockTransactionsKind,
+    transports::Transport,
+};
+use tracing::{info, warn};
+
+use super::NetworkError;
+use crate::config::models::Network;
+
+/// What the endpoints of a network report about the chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkMetadata {
+    pub chain_id: u64,
+    /// `web3_clientVersion` of the preferred endpoint, if it answers it
+    pub client_version: Option<String>,
+    /// Whether the latest block has a base fee, i.e. the chain supports EIP-1559
+    pub eip1559: bool,
+}
+
+/// Checks that every endpoint that answered serves the same chain, and the
+/// configured one if there is one
+///
+/// Takes the label and chain ID of every endpoint, None for those that didn't
+/// answer. Returns the verified chain ID.
+pub fn verify_chain_ids(
+    network: &str,
+    configured: Option<u64>,
+    chain_ids: &[(String, Option<u64>)],
+) -> Result<u64, NetworkError> {
+    let mut answered = chain_ids
+        .iter()
+        .filter_map(|(label, id)| id.map(|id| (label, id)));
+    let (first_label, first_id) = answered.next().ok_or_else(|| {
+        NetworkError::ConnectionFailed(format!("no endpoint of {network} returned its chain ID"))
+    })?;
+
+    let expected = configured.unwrap_or(first_id);
+    for (label, actual) in std::iter::once((first_label, first_id)).chain(answered) {
+        if actual != expected {
+            return Err(NetworkError::ChainIdMismatch {
+                network: network.to_string(),
+                endpoint: label.clone(),
+                expected,
+                actual,
+            });
+        }
+    }
+
+    for (label, id) in chain_ids {
+        if id.is_none() {
+            warn!(
+                "RPC endpoint {} of {} didn't return its chain ID, it is not used until it does",
+                label, network
+            );
+        }
+    }
+
+    Ok(expected)
+}
+
+/// Detects the client version and EIP-1559 support of a network
+///
+/// Warns if the network is configured for EIP-1559 transactions on a chain
+/// without a base fee.
+pub async fn detect<T, P>(network: &Network, chain_id: u64, provider: &P) -> NetworkMetadata
+where
+    T: Transport + Clone,
+    P: Provider<T>,
+{
+    let client_version = match provider.get_client_version().await {
+        Ok(version) => Some(version),
+        Err(e) => {
+            warn!(
+                "Failed to get the client version of {}: {}",
+                network.name, e
+            );
+            None
+        }
+    };
+
+    let eip1559 = match provider
+        .get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Hashes)
+        .await
+    {
+        Ok(Some(block)) => block.header.base_fee_per_gas.is_some(),
+        Ok(None) | Err(_) => {
+            warn!(
+                "Failed to read the latest block of {}, assuming EIP-1559 support",
+                network.name
+            );
+            true
+        }
+    };
+
+    info!(
+        "Network {} is chain {} served by {}, EIP-1559 {}",
+        network.name,
+        chain_id,
+        client_version.as_deref().unwrap_or("an unknown client"),
+        if eip1559 {
+            "supported"
+        } else {
+            "not supported"
+        }
+    );
+    if !eip1559 && network.transaction_type == "eip1559" {
+        warn!(
+            "Network {} uses transaction_type eip1559, but its blocks have no base fee. \
+             Set transaction_type to legacy if transactions fail",
+            network.name
+        );
+    }
+
+    NetworkMetadata {
+        chain_id,
+        client_version,
+        eip1559,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoints(ids: &[Option<u64>]) -> Vec<(String, Option<u64>)> {
+        ids.iter()
+            .enumerate()
+            .map(|(i, id)| (format!("https://rpc{i}.example.com/"), *id))
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_chain_ids() {
+        assert_eq!(
+            verify_chain_ids("ethereum", Some(1), &endpoints(&[Some(1), None])).unwrap(),
+            1
+        );
+        // Without a configured chain ID the endpoints must agree
+        assert_eq!(
+            verify_chain_ids("base", None, &endpoints(&[Some(8453), Some(8453)])).unwrap(),
+            8453
+        );
+    }
+
+    #[test]
+    fn test_verify_chain_ids_mismatch() {
+        let error = verify_chain_ids("ethereum", Some(1), &endpoints(&[Some(1), Some(11155111)]))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            NetworkError::ChainIdMismatch {
+                expected: 1,
+                actual: 11155111,
+                ..
+            }
+        ));
+
+        let error = verify_chain_ids("base", None, &endpoints(&[Some(8453), Some(1)])).unwrap_err();
+        assert!(matches!(
+            error,
+            NetworkError::ChainIdMismatch {
+                expected: 8453,
+                actual: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_verify_chain_ids_without_answers() {
+        assert!(matches!(
+            verify_chain_ids("ethereum", Some(1), &endpoints(&[None])),
+            Err(NetworkError::ConnectionFailed(_))
+        ));
+    }
+}
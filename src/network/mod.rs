@@ -1,5 +1,19 @@
+mod confirmations;
+mod failover;
+mod heads;
+mod metadata;
+mod nonce;
 mod provider;
+mod quorum;
 #[cfg(test)]
 mod tests;
+mod tracker;
 
+pub use confirmations::{depth, wait_for_confirmations, Confirmation};
+pub use failover::{rank, EndpointHealth, FailoverTransport};
+pub use heads::{block_time, is_ws_url, Head, HeadTicker};
+pub use metadata::{verify_chain_ids, NetworkMetadata};
+pub use nonce::NonceManager;
 pub use provider::*;
+pub use quorum::{quorum_block, tally};
+pub use tracker::{cancellation, TrackedOwner, TransactionTracker};
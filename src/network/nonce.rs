@@ -0,0 +1,273 @@
+//! Nonce management shared by every sender of a signer
+//!
+//! Feed monitors, batches and scheduled tasks sending from the same address on
+//! the same network take their nonces from one [`NonceManager`] instead of
+//! each asking the node, so concurrent sends don't collide. Nonces are handed
+//! out locally and resynced from `eth_getTransactionCount(pending)` after a
+//! failed send. Nonces that were handed out but never used below a nonce that
+//! is still in use are gaps; they are filled with zero-value self-transfers so
+//! the later transactions aren't stuck.
+
+use crate::metrics::ContractMetrics;
+use alloy::{
+    network::{Network, TransactionBuilder},
+    primitives::{Address, U256},
+    providers::Provider,
+    transports::Transport,
+};
+use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Nonce state of one signer on one network
+#[derive(Debug, Default)]
+struct SignerNonces {
+    /// Next nonce to hand out, None until synced with the node
+    next: Option<u64>,
+    /// Nonces handed out whose transactions are not confirmed or released yet
+    outstanding: BTreeSet<u64>,
+    /// Nonces released after a failed send since the last resync
+    released: usize,
+}
+
+impl SignerNonces {
+    /// Resyncs with the node's pending transaction count
+    /// Returns the gaps to fill, nonces no one will send below one in use
+    fn resync(&mut self, chain_pending: u64) -> Vec<u64> {
+        let gaps = find_gaps(chain_pending, &self.outstanding);
+        let after_outstanding = self.outstanding.last().map_or(0, |nonce| nonce + 1);
+        self.next = Some(chain_pending.max(after_outstanding));
+        self.released = 0;
+        gaps
+    }
+}
+
+/// Nonces from `chain_pending` up to the highest outstanding one that are not outstanding
+fn find_gaps(chain_pending: u64, outstanding: &BTreeSet<u64>) -> Vec<u64> {
+    let Some(&highest) = outstanding.last() else {
+        return Vec::new();
+    };
+
+    (chain_pending..highest)
+        .filter(|nonce| !outstanding.contains(nonce))
+        .collect()
+}
+
+/// Nonce state per (network, address)
+type Signers = HashMap<(String, Address), Arc<Mutex<SignerNonces>>>;
+
+/// Hands out nonces per (network, address)
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    signers: std::sync::Mutex<Signers>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn signer(&self, network: &str, address: Address) -> Arc<Mutex<SignerNonces>> {
+        let mut signers = self.signers.lock().expect("nonce manager lock poisoned");
+        Arc::clone(signers.entry((network.to_string(), address)).or_default())
+    }
+
+    /// Returns the next nonce of `address` on `network`
+    ///
+    /// The first call, and the first after a release, resyncs with the node and
+    /// fills any gaps before handing out the nonce. The nonce must be passed to
+    /// [`confirm`](Self::confirm) or [`release`](Self::release) once its
+    /// transaction is mined or has failed.
+    pub async fn next_nonce<T, N, P>(
+        &self,
+        network: &str,
+        address: Address,
+        provider: &P,
+    ) -> Result<u64>
+    where
+        T: Transport + Clone,
+        N: Network,
+        N::TransactionRequest: TransactionBuilder<N>,
+        P: Provider<T, N>,
+    {
+        let signer = self.signer(network, address);
+        let mut state = signer.lock().await;
+
+        let nonce = match state.next {
+            Some(nonce) => nonce,
+            None => {
+                let chain_pending = provider
+                    .get_transaction_count(address)
+                    .pending()
+                    .await
+                    .with_context(|| {
+                        format!("Failed to get the nonce of {address} on {network}")
+                    })?;
+                let gaps = state.resync(chain_pending);
+                debug!(
+                    "Synced nonce of {} on {}: node pending {}, next {:?}",
+                    address, network, chain_pending, state.next
+                );
+
+                if let Some(&first) = gaps.first() {
+                    let blocked = state
+                        .outstanding
+                        .range(first..)
+                        .next()
+                        .copied()
+                        .unwrap_or(first);
+                    ContractMetrics::record_nonce_gap(network, first, blocked);
+                    Self::update_queue_metrics(network, address, &state, gaps.len());
+                    Self::fill_gaps(network, address, provider, &gaps).await;
+                }
+
+                state.next.unwrap_or(chain_pending)
+            }
+        };
+
+        state.next = Some(nonce + 1);
+        state.outstanding.insert(nonce);
+        Self::update_queue_metrics(network, address, &state, 0);
+
+        Ok(nonce)
+    }
+
+    /// Marks the transaction sent with `nonce` as mined
+    pub async fn confirm(&self, network: &str, address: Address, nonce: u64) {
+        let signer = self.signer(network, address);
+        let mut state = signer.lock().await;
+
+        state.outstanding.remove(&nonce);
+        Self::update_queue_metrics(network, address, &state, 0);
+    }
+
+    /// Returns a nonce whose transaction failed or was never sent
+    ///
+    /// The next nonce is then resynced from the node, which also reuses this
+    /// one if the node never saw its transaction.
+    pub async fn release(&self, network: &str, address: Address, nonce: u64) {
+        let signer = self.signer(network, address);
+        let mut state = signer.lock().await;
+
+        state.outstanding.remove(&nonce);
+        state.next = None;
+        state.released += 1;
+        Self::update_queue_metrics(network, address, &state, 0);
+
+        debug!(
+            "Released nonce {} of {} on {}, resyncing on the next send",
+            nonce, address, network
+        );
+    }
+
+    /// Sends a zero-value self-transfer for every gap
+    async fn fill_gaps<T, N, P>(network: &str, address: Address, provider: &P, gaps: &[u64])
+    where
+        T: Transport + Clone,
+        N: Network,
+        N::TransactionRequest: TransactionBuilder<N>,
+        P: Provider<T, N>,
+    {
+        for &nonce in gaps {
+            let tx = N::TransactionRequest::default()
+                .with_from(address)
+                .with_to(address)
+                .with_value(U256::ZERO)
+                .with_nonce(nonce);
+
+            match provider.send_transaction(tx).await {
+                Ok(pending) => info!(
+                    "Filled nonce gap {} of {} on {} with 0x{:x}",
+                    nonce,
+                    address,
+                    network,
+                    pending.tx_hash()
+                ),
+                Err(e) => warn!(
+                    "Failed to fill nonce gap {} of {} on {}: {}",
+                    nonce, address, network, e
+                ),
+            }
+        }
+    }
+
+    fn update_queue_metrics(network: &str, address: Address, state: &SignerNonces, filling: usize) {
+        ContractMetrics::update_transaction_queue(
+            &address.to_string(),
+            network,
+            state.outstanding.len(),
+            filling,
+            state.released,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_gaps() {
+        let outstanding = BTreeSet::from([5, 7]);
+        assert_eq!(find_gaps(5, &outstanding), vec![6]);
+        assert_eq!(find_gaps(3, &outstanding), vec![3, 4, 6]);
+        assert!(find_gaps(8, &outstanding).is_empty());
+        assert!(find_gaps(3, &BTreeSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_resync() {
+        // Nothing in flight, continue from the node
+        let mut state = SignerNonces::default();
+        assert!(state.resync(10).is_empty());
+        assert_eq!(state.next, Some(10));
+
+        // Nonce 11 failed while 12 is still being sent
+        let mut state = SignerNonces {
+            next: None,
+            outstanding: BTreeSet::from([12]),
+            released: 1,
+        };
+        assert_eq!(state.resync(11), vec![11]);
+        assert_eq!(state.next, Some(13));
+        assert_eq!(state.released, 0);
+
+        // The node knows more transactions than were sent through the manager
+        let mut state = SignerNonces {
+            next: None,
+            outstanding: BTreeSet::from([4]),
+            released: 0,
+        };
+        assert!(state.resync(9).is_empty());
+        assert_eq!(state.next, Some(9));
+    }
+
+    #[tokio::test]
+    async fn test_confirm_and_release() {
+        let manager = NonceManager::new();
+        let address = Address::repeat_byte(1);
+
+        {
+            let signer = manager.signer("ethereum", address);
+            let mut state = signer.lock().await;
+            state.resync(3);
+            state.outstanding.extend([3, 4]);
+            state.next = Some(5);
+        }
+
+        manager.confirm("ethereum", address, 3).await;
+        manager.release("ethereum", address, 4).await;
+
+        let signer = manager.signer("ethereum", address);
+        let state = signer.lock().await;
+        assert!(state.outstanding.is_empty());
+        assert_eq!(state.next, None);
+        assert_eq!(state.released, 1);
+
+        // Other networks are tracked separately
+        let other = manager.signer("base", address);
+        assert!(other.lock().await.next.is_none());
+    }
+}
@@ -3,18 +3,24 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use alloy::{
-    primitives::Address,
-    providers::{Provider, ProviderBuilder, RootProvider},
+    network::EthereumWallet,
+    primitives::{Address, B256},
+    providers::{Provider, RootProvider},
+    rpc::client::RpcClient,
     signers::local::PrivateKeySigner,
-    transports::http::{Client, Http},
+    transports::utils::guess_local_url,
 };
 use anyhow::{Context, Result};
 use secrecy::ExposeSecret;
 use thiserror::Error;
+use tokio::sync::watch;
 use tracing::{error, info};
-use url::Url;
 
+use super::heads::{spawn_head_subscription, Head};
+use super::metadata::{detect, verify_chain_ids, NetworkMetadata};
+use super::{FailoverTransport, NonceManager};
 use crate::config::models::Network;
+use crate::gas::{fetch_l1_fee, L1Fee};
 use crate::metrics::NetworkMetrics;
 use crate::wallet::key_storage::KeyStorage;
 
@@ -30,16 +36,39 @@ pub enum NetworkError {
 
     #[error("RPC connection failed: {0}")]
     ConnectionFailed(String),
+
+    #[error("RPC endpoint {endpoint} of {network} serves chain {actual}, expected {expected}")]
+    ChainIdMismatch {
+        network: String,
+        endpoint: String,
+        expected: u64,
+        actual: u64,
+    },
 }
 
 /// Type alias for the alloy provider we will use
-pub type EthProvider = RootProvider<Http<Client>>;
+pub type EthProvider = RootProvider<FailoverTransport>;
 
 /// Manages the connections to different EVM networks
 pub struct NetworkManager {
     /// Map of network name to provider
     providers: HashMap<String, Arc<EthProvider>>,
 
+    /// Transports failing over across the RPC endpoints of each network
+    transports: HashMap<String, FailoverTransport>,
+
+    /// Providers checking reads against several endpoints, for networks with `read_quorum`
+    quorum_providers: HashMap<String, Arc<EthProvider>>,
+
+    /// Latest heads of the networks subscribed to over WebSocket
+    heads: HashMap<String, watch::Receiver<Option<Head>>>,
+
+    /// Networks whose datafeeds are checked on every new head
+    feed_checks_on_heads: HashMap<String, bool>,
+
+    /// Chain ID, client and EIP-1559 support detected on startup
+    metadata: HashMap<String, NetworkMetadata>,
+
     /// Private keys for each network (stored securely)
     private_keys: HashMap<String, String>,
 
@@ -48,32 +77,82 @@ pub struct NetworkManager {
 
     /// Wallet addresses for each network
     wallet_addresses: HashMap<String, Address>,
+
+    /// Confirmation depth at which transactions are final, per network
+    confirmations: HashMap<String, u64>,
+
+    /// Rollup type of each network, for the L1 fee of its transactions
+    l2_types: HashMap<String, String>,
+
+    /// Nonces of the wallets, shared by everything sending transactions
+    nonce_manager: Arc<NonceManager>,
 }
 
 impl NetworkManager {
     /// Create a new network manager from a list of network configurations
     pub async fn new(networks: &[Network]) -> Result<Self> {
         let mut providers = HashMap::new();
+        let mut transports = HashMap::new();
+        let mut quorum_providers = HashMap::new();
+        let mut heads = HashMap::new();
+        let mut feed_checks_on_heads = HashMap::new();
+        let mut metadata = HashMap::new();
         let private_keys = HashMap::new();
         let mut rpc_urls = HashMap::new();
         let wallet_addresses = HashMap::new();
+        let mut confirmations = HashMap::new();
+        let mut l2_types = HashMap::new();
 
         for network in networks {
-            let provider = Self::create_provider(&network.rpc_url)
-                .await
-                .with_context(|| {
-                    format!("Failed to create provider for network {}", network.name)
-                })?;
+            let transport = Self::connect(network).await.with_context(|| {
+                format!("Failed to create provider for network {}", network.name)
+            })?;
+            let chain_id = verify_chain_ids(
+                &network.name,
+                network.chain_id,
+                &transport.chain_ids().await,
+            )?;
+            transport.set_chain_id(chain_id);
+
+            let is_local = guess_local_url(&network.rpc_url);
+            let client = RpcClient::new(transport.clone(), is_local);
+            let provider = RootProvider::new(client);
+            metadata.insert(
+                network.name.clone(),
+                detect(network, chain_id, &provider).await,
+            );
+
+            if network.read_quorum.enabled {
+                let quorum = transport.with_read_quorum(network.read_quorum.clone());
+                let client = RpcClient::new(quorum, is_local);
+                quorum_providers.insert(network.name.clone(), Arc::new(RootProvider::new(client)));
+            }
 
             providers.insert(network.name.clone(), Arc::new(provider));
+            transports.insert(network.name.clone(), transport);
+
+            if let Some(receiver) = spawn_head_subscription(network) {
+                heads.insert(network.name.clone(), receiver);
+            }
+            feed_checks_on_heads.insert(network.name.clone(), network.check_feeds_on_new_heads);
             rpc_urls.insert(network.name.clone(), network.rpc_url.clone());
+            confirmations.insert(network.name.clone(), network.confirmations);
+            l2_types.insert(network.name.clone(), network.l2_type.clone());
         }
 
         Ok(Self {
             providers,
+            transports,
+            quorum_providers,
+            heads,
+            feed_checks_on_heads,
+            metadata,
             private_keys,
             rpc_urls,
             wallet_addresses,
+            confirmations,
+            l2_types,
+            nonce_manager: Arc::new(NonceManager::new()),
         })
     }
 
@@ -236,6 +315,53 @@ impl NetworkManager {
             .ok_or_else(|| NetworkError::NetworkNotFound(network_name.to_string()).into())
     }
 
+    /// Get a provider for reading critical contract state on a network
+    ///
+    /// On networks with `read_quorum` enabled, reads of the latest state are only
+    /// accepted if enough endpoints agree. Elsewhere this is the regular provider.
+    pub fn get_quorum_provider(&self, network_name: &str) -> Result<Arc<EthProvider>> {
+        match self.quorum_providers.get(network_name) {
+            Some(provider) => Ok(Arc::clone(provider)),
+            None => self.get_provider(network_name),
+        }
+    }
+
+    /// Get what the endpoints of a network reported about its chain on startup
+    pub fn get_metadata(&self, network_name: &str) -> Result<&NetworkMetadata> {
+        self.metadata
+            .get(network_name)
+            .ok_or_else(|| NetworkError::NetworkNotFound(network_name.to_string()).into())
+    }
+
+    /// Subscribe to the new heads of a network
+    ///
+    /// Returns None for networks without a WebSocket endpoint.
+    pub fn subscribe_heads(&self, network_name: &str) -> Option<watch::Receiver<Option<Head>>> {
+        self.heads.get(network_name).cloned()
+    }
+
+    /// Subscribe to the new heads a network's datafeeds are checked on
+    ///
+    /// Returns None unless the network has `check_feeds_on_new_heads` and a
+    /// WebSocket endpoint.
+    pub fn subscribe_feed_check_heads(
+        &self,
+        network_name: &str,
+    ) -> Option<watch::Receiver<Option<Head>>> {
+        match self.feed_checks_on_heads.get(network_name) {
+            Some(true) => self.subscribe_heads(network_name),
+            _ => None,
+        }
+    }
+
+    /// Get the transport failing over across the RPC endpoints of a network
+    pub fn get_transport(&self, network_name: &str) -> Result<FailoverTransport> {
+        self.transports
+            .get(network_name)
+            .cloned()
+            .ok_or_else(|| NetworkError::NetworkNotFound(network_name.to_string()).into())
+    }
+
     /// Get the private key for a network
     pub fn get_private_key(&self, network_name: &str) -> Result<String> {
         self.private_keys.get(network_name).cloned().ok_or_else(|| {
@@ -246,6 +372,16 @@ impl NetworkManager {
         })
     }
 
+    /// Get a wallet signing with the network's private key
+    pub fn get_wallet(&self, network_name: &str) -> Result<EthereumWallet> {
+        let signer = self
+            .get_private_key(network_name)?
+            .parse::<PrivateKeySigner>()
+            .with_context(|| "Failed to parse private key as signer")?;
+
+        Ok(EthereumWallet::from(signer))
+    }
+
     /// Get the RPC URL for a network
     pub fn get_rpc_url(&self, network_name: &str) -> Result<&str> {
         self.rpc_urls
@@ -254,6 +390,43 @@ impl NetworkManager {
             .ok_or_else(|| NetworkError::NetworkNotFound(network_name.to_string()).into())
     }
 
+    /// Get the confirmation depth at which transactions on a network are final
+    pub fn get_confirmations(&self, network_name: &str) -> u64 {
+        self.confirmations.get(network_name).copied().unwrap_or(1)
+    }
+
+    /// Get the rollup type of a network for L1 fees, "none" if unknown
+    pub fn get_l2_type(&self, network_name: &str) -> &str {
+        self.l2_types
+            .get(network_name)
+            .map_or("none", |l2_type| l2_type.as_str())
+    }
+
+    /// Get the L1 fee of a mined transaction, zero on networks that aren't rollups
+    pub async fn get_l1_fee(
+        &self,
+        network_name: &str,
+        tx_hash: B256,
+        effective_gas_price: u128,
+    ) -> L1Fee {
+        let l2_type = self.get_l2_type(network_name);
+        if l2_type == "none" {
+            return L1Fee::default();
+        }
+        let Ok(provider) = self.get_provider(network_name) else {
+            return L1Fee::default();
+        };
+
+        fetch_l1_fee(
+            network_name,
+            l2_type,
+            provider.as_ref(),
+            tx_hash,
+            effective_gas_price,
+        )
+        .await
+    }
+
     /// Get a signer for a given network
     #[allow(dead_code)]
     pub fn get_signer(&self, network_name: &str) -> Result<Arc<EthProvider>> {
@@ -269,6 +442,11 @@ impl NetworkManager {
         }
     }
 
+    /// Get the nonce manager shared by all transaction senders
+    pub fn nonce_manager(&self) -> &Arc<NonceManager> {
+        &self.nonce_manager
+    }
+
     /// Get all configured network names
     pub fn get_network_names(&self) -> Vec<String> {
         self.providers.keys().cloned().collect()
@@ -287,37 +465,34 @@ impl NetworkManager {
             })
     }
 
-    /// Create a provider from an RPC URL
-    async fn create_provider(rpc_url: &str) -> Result<EthProvider> {
-        let url =
-            Url::parse(rpc_url).with_context(|| format!("Failed to parse RPC URL: {rpc_url}"))?;
-
-        let provider = ProviderBuilder::new().on_http(url);
-
-        // Test connection by getting the current block number
+    /// Create the transport of a network and check that an endpoint answers
+    async fn connect(network: &Network) -> Result<FailoverTransport> {
+        let transport = FailoverTransport::new(network).await?;
         let start = Instant::now();
-        match provider.get_block_number().await {
-            Ok(block_number) => {
-                let _duration = start.elapsed();
-
-                // Update endpoint health metric
-                NetworkMetrics::update_endpoint_health("unknown", rpc_url, true);
-
-                info!(
-                    "Connected to RPC at {}, current block: {}",
-                    rpc_url, block_number
-                );
-                Ok(provider)
-            }
-            Err(err) => {
-                let _duration = start.elapsed();
+        let reachable = transport.probe().await;
+
+        if reachable == 0 {
+            error!(
+                "Failed to connect to any RPC endpoint of network {}",
+                network.name
+            );
+            return Err(NetworkError::ConnectionFailed(format!(
+                "none of the {} RPC endpoints of {} answered",
+                network.endpoints().len(),
+                network.name
+            ))
+            .into());
+        }
 
-                // Update endpoint health metric
-                NetworkMetrics::update_endpoint_health("unknown", rpc_url, false);
+        info!(
+            "Connected to {}/{} RPC endpoints of {} in {:?}",
+            reachable,
+            network.endpoints().len(),
+            network.name,
+            start.elapsed()
+        );
+        transport.spawn_health_checks();
 
-                error!("Failed to connect to RPC at {}: {}", rpc_url, err);
-                Err(NetworkError::ConnectionFailed(err.to_string()).into())
-            }
-        }
+        Ok(transport)
     }
 }
@@ -0,0 +1,247 @@
+//! Quorum reads across the RPC endpoints of a network
+//!
+//! A lagging or misbehaving endpoint can return a stale contract state. With
+//! `read_quorum` enabled, `eth_call`s of the latest state are sent to the best
+//! `endpoints` endpoints, pinned to the lowest head among them so they all
+//! answer for the same block. Endpoints trailing the highest head by more than
+//! `rpc_failover.max_block_lag` don't take part. The read is accepted if at
+//! least `min_agreeing` endpoints return the same value; differing answers are
+//! logged and counted in `omikuji_rpc_quorum_disagreements_total`.
+
+use alloy::{
+    rpc::json_rpc::{Request, RequestPacket, ResponsePacket, SerializedRequest},
+    transports::{TransportError, TransportErrorKind, TransportResult},
+};
+use futures::future::join_all;
+use serde_json::Value;
+use tracing::warn;
+
+use super::failover::Endpoints;
+use crate::config::models::ReadQuorumConfig;
+use crate::metrics::NetworkMetrics;
+
+/// Returns true for `eth_call`s of the latest state
+pub(super) fn is_quorum_read(request: &RequestPacket) -> bool {
+    let RequestPacket::Single(request) = request else {
+        return false;
+    };
+    if request.method() != "eth_call" {
+        return false;
+    }
+
+    match call_params(request) {
+        Some(params) => params.get(1).map_or(true, |block| block == "latest"),
+        None => false,
+    }
+}
+
+fn call_params(request: &SerializedRequest) -> Option<Vec<Value>> {
+    serde_json::from_str(request.params()?.get()).ok()
+}
+
+/// Rewrites an `eth_call` of the latest state to read at `block`
+fn pin_to_block(request: &SerializedRequest, block: u64) -> TransportResult<SerializedRequest> {
+    let mut params = call_params(request)
+        .ok_or_else(|| TransportErrorKind::custom_str("eth_call without parameters"))?;
+    params.truncate(1);
+    params.push(Value::String(format!("0x{block:x}")));
+
+    Request::new(request.method().to_string(), request.id().clone(), params)
+        .serialize()
+        .map_err(TransportError::ser_err)
+}
+
+/// Finds the most common answer, ignoring missing ones
+///
+/// Returns the index of its first occurrence and the number of answers equal to it.
+pub fn tally<T: PartialEq>(answers: &[Option<T>]) -> Option<(usize, usize)> {
+    let mut best: Option<(usize, usize)> = None;
+    for (index, answer) in answers.iter().enumerate() {
+        let Some(answer) = answer else {
+            continue;
+        };
+        let count = answers
+            .iter()
+            .filter(|other| other.as_ref() == Some(answer))
+            .count();
+        if best.map_or(true, |(_, best_count)| count > best_count) {
+            best = Some((index, count));
+        }
+    }
+    best
+}
+
+/// Returns the block the endpoints are read at and the endpoints reading it
+///
+/// Endpoints without a head or trailing the highest head by more than
+/// `max_block_lag` are left out.
+pub fn quorum_block(
+    heads: &[(usize, Option<u64>)],
+    max_block_lag: u64,
+) -> Option<(u64, Vec<usize>)> {
+    let highest = heads.iter().filter_map(|(_, head)| *head).max()?;
+    let participants: Vec<(usize, u64)> = heads
+        .iter()
+        .filter_map(|&(index, head)| head.map(|head| (index, head)))
+        .filter(|(_, head)| highest - head <= max_block_lag)
+        .collect();
+    let block = participants.iter().map(|(_, head)| *head).min()?;
+
+    Some((
+        block,
+        participants.into_iter().map(|(index, _)| index).collect(),
+    ))
+}
+
+impl Endpoints {
+    /// Sends a read of the latest state to several endpoints and returns the
+    /// answer enough of them agree on
+    pub(super) async fn quorum_request(
+        &self,
+        request: RequestPacket,
+        quorum: &ReadQuorumConfig,
+    ) -> TransportResult<ResponsePacket> {
+        let RequestPacket::Single(ref single) = request else {
+            return self.request(request).await;
+        };
+
+        let ranked = self.ranked();
+        let asked = &ranked[..quorum.endpoints.min(ranked.len())];
+        let heads = join_all(
+            asked
+                .iter()
+                .map(|&index| async move { (index, self.endpoints[index].head().await.ok()) }),
+        )
+        .await;
+        let (block, participants) = quorum_block(&heads, self.config.max_block_lag)
+            .ok_or_else(|| TransportErrorKind::custom_str("No endpoint answered for the quorum"))?;
+
+        let pinned = RequestPacket::Single(pin_to_block(single, block)?);
+        let responses = join_all(
+            participants
+                .iter()
+                .map(|&index| self.send(index, pinned.clone())),
+        )
+        .await;
+
+        let answers: Vec<Option<String>> = responses
+            .iter()
+            .map(|response| match response {
+                Ok(ResponsePacket::Single(r)) => {
+                    r.payload.as_success().map(|p| p.get().to_string())
+                }
+                _ => None,
+            })
+            .collect();
+        let agreed = tally(&answers);
+        let quorum_reached = agreed.is_some_and(|(_, count)| count >= quorum.min_agreeing);
+
+        let mut distinct: Vec<&String> = Vec::new();
+        for answer in answers.iter().flatten() {
+            if !distinct.contains(&answer) {
+                distinct.push(answer);
+            }
+        }
+        if distinct.len() > 1 {
+            let details: Vec<String> = participants
+                .iter()
+                .zip(&answers)
+                .map(|(&index, answer)| {
+                    let label = &self.endpoints[index].label;
+                    format!("{label}: {}", answer.as_deref().unwrap_or("no answer"))
+                })
+                .collect();
+            warn!(
+                "RPC endpoints on {} disagree on eth_call at block {}: {}",
+                self.network,
+                block,
+                details.join(", ")
+            );
+            NetworkMetrics::record_quorum_disagreement(&self.network, "eth_call", quorum_reached);
+        }
+
+        match agreed {
+            Some((index, _)) if quorum_reached => {
+                return responses
+                    .into_iter()
+                    .nth(index)
+                    .expect("tally returns an index of the answers");
+            }
+            // Without any value, an error the nodes answered with, e.g. a
+            // revert, is returned as is
+            None => {
+                if let Some(response) = responses.into_iter().find_map(Result::ok) {
+                    return Ok(response);
+                }
+            }
+            Some(_) => {}
+        }
+
+        Err(TransportErrorKind::custom_str(&format!(
+            "Read quorum of {}/{} not reached on {} at block {}",
+            quorum.min_agreeing,
+            participants.len(),
+            self.network,
+            block
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(block: Option<&str>) -> RequestPacket {
+        let tx = serde_json::json!({ "to": "0x0000000000000000000000000000000000000001" });
+        let params = match block {
+            Some(block) => vec![tx, Value::String(block.to_string())],
+            None => vec![tx],
+        };
+        let request = Request::new("eth_call", 1u64.into(), params);
+        RequestPacket::Single(request.serialize().unwrap())
+    }
+
+    #[test]
+    fn test_is_quorum_read() {
+        assert!(is_quorum_read(&call(Some("latest"))));
+        assert!(is_quorum_read(&call(None)));
+        // Simulations read the pending block
+        assert!(!is_quorum_read(&call(Some("pending"))));
+        assert!(!is_quorum_read(&call(Some("0x10"))));
+
+        let block_number = Request::new("eth_blockNumber", 1u64.into(), ());
+        assert!(!is_quorum_read(&RequestPacket::Single(
+            block_number.serialize().unwrap()
+        )));
+    }
+
+    #[test]
+    fn test_pin_to_block() {
+        let RequestPacket::Single(request) = call(Some("latest")) else {
+            unreachable!()
+        };
+        let pinned = pin_to_block(&request, 255).unwrap();
+
+        assert_eq!(pinned.method(), "eth_call");
+        assert_eq!(pinned.id(), request.id());
+        assert_eq!(call_params(&pinned).unwrap()[1], "0xff");
+    }
+
+    #[test]
+    fn test_tally() {
+        assert_eq!(tally(&[Some("a"), Some("b"), Some("a")]), Some((0, 2)));
+        assert_eq!(
+            tally(&[None, Some("b"), Some("a"), Some("b")]),
+            Some((1, 2))
+        );
+        assert_eq!(tally::<&str>(&[None, None]), None);
+    }
+
+    #[test]
+    fn test_quorum_block() {
+        // Endpoint 2 trails by more than the allowed lag, endpoint 3 didn't answer
+        let heads = [(0, Some(100)), (1, Some(98)), (2, Some(80)), (3, None)];
+        assert_eq!(quorum_block(&heads, 5), Some((98, vec![0, 1])));
+        assert_eq!(quorum_block(&[(0, None)], 5), None);
+    }
+}
@@ -13,6 +13,14 @@ mod tests {
             gas_config: Default::default(),
             gas_token: "ethereum".to_string(),
             gas_token_symbol: "ETH".to_string(),
+            batching: Default::default(),
+            confirmations: 1,
+            rpc_endpoints: Vec::new(),
+            rpc_failover: Default::default(),
+            read_quorum: Default::default(),
+            check_feeds_on_new_heads: false,
+            chain_id: None,
+            l2_type: "none".to_string(),
         }
     }
 
@@ -130,6 +138,17 @@ mod tests {
 
         let error = NetworkError::ConnectionFailed("timeout".to_string());
         assert_eq!(error.to_string(), "RPC connection failed: timeout");
+
+        let error = NetworkError::ChainIdMismatch {
+            network: "ethereum".to_string(),
+            endpoint: "https://rpc.example.com/".to_string(),
+            expected: 1,
+            actual: 11155111,
+        };
+        assert_eq!(
+            error.to_string(),
+            "RPC endpoint https://rpc.example.com/ of ethereum serves chain 11155111, expected 1"
+        );
     }
 
     // Mock provider tests would require more complex setup with mock HTTP servers
@@ -0,0 +1,865 @@
+//! Tracking of broadcast transactions across restarts
+//!
+//! Transactions sent with a managed nonce are signed locally and stored in the
+//! `pending_transactions` table before they are broadcast, and deleted once
+//! their nonce is mined at the network's confirmation depth, so a transaction
+//! reorged out before that is still tracked. Transactions a sender stopped waiting for, and those
+//! still stored at startup, are watched until they resolve: rebroadcast if the
+//! node dropped them, replaced with higher fees if they are stuck and, once
+//! out of retries, optionally cancelled. Their `transaction_log` rows are
+//! written as `pending` when sent, linked to the first transaction of their
+//! nonce, and finalized on resolution.
+
+use super::{depth, HeadTicker, NetworkManager};
+use crate::config::models::{Network as NetworkConfig, OmikujiConfig};
+use crate::constants::gas::TRANSFER_GAS_LIMIT;
+use crate::contracts::multicall::split_gas;
+use crate::database::pending_transaction_repository::{NewPendingTransaction, PendingTransaction};
+use crate::database::{PendingTransactionRepository, TransactionLogRepository};
+use crate::gas::{fill_missing_fees, GasEstimate, GasEstimator, L1Fee};
+use crate::metrics::gas_metrics::TransactionDetails;
+use alloy::{
+    consensus::TxEnvelope,
+    eips::eip2718::{Decodable2718, Encodable2718},
+    network::{EthereumWallet, Network, NetworkWallet, ReceiptResponse, TransactionBuilder},
+    primitives::{utils::format_units, Address, B256, U256},
+    providers::{PendingTransactionBuilder, Provider},
+    rpc::types::TransactionRequest,
+    transports::Transport,
+};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use tokio::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// How often watched nonces are checked on networks without new head subscriptions
+const WATCH_INTERVAL: Duration = Duration::from_secs(15);
+
+/// What a tracked transaction was sent for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedOwner {
+    context_type: String,
+    names: Vec<String>,
+}
+
+impl TrackedOwner {
+    /// A datafeed submission, or a batch shared by several datafeeds
+    pub fn datafeeds(names: Vec<String>) -> Self {
+        Self {
+            context_type: "datafeed".to_string(),
+            names,
+        }
+    }
+
+    /// A scheduled task execution
+    pub fn task(name: &str) -> Self {
+        Self {
+            context_type: "scheduled_task".to_string(),
+            names: vec![name.to_string()],
+        }
+    }
+}
+
+/// The mined transaction of a nonce
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Resolution {
+    tx_hash: B256,
+    status: bool,
+    gas_used: u64,
+    effective_gas_price: u128,
+    block_number: u64,
+}
+
+impl Resolution {
+    fn from_receipt(receipt: &impl ReceiptResponse) -> Self {
+        Self {
+            tx_hash: receipt.transaction_hash(),
+            status: receipt.status(),
+            gas_used: receipt.gas_used() as u64,
+            effective_gas_price: receipt.effective_gas_price(),
+            block_number: receipt.block_number().unwrap_or(0),
+        }
+    }
+}
+
+/// Persists broadcast transactions and watches them until they resolve
+pub struct TransactionTracker {
+    network_manager: Arc<NetworkManager>,
+    networks: HashMap<String, NetworkConfig>,
+    repo: Arc<PendingTransactionRepository>,
+    tx_log_repo: Arc<TransactionLogRepository>,
+}
+
+impl TransactionTracker {
+    pub fn new(
+        config: &OmikujiConfig,
+        network_manager: Arc<NetworkManager>,
+        repo: Arc<PendingTransactionRepository>,
+        tx_log_repo: Arc<TransactionLogRepository>,
+    ) -> Self {
+        Self {
+            network_manager,
+            networks: config
+                .networks
+                .iter()
+                .map(|network| (network.name.clone(), network.clone()))
+                .collect(),
+            repo,
+            tx_log_repo,
+        }
+    }
+
+    /// Signs the transaction, stores it and broadcasts it
+    ///
+    /// The transaction must have its sender and nonce set; a missing chain ID,
+    /// gas limit or fees are filled from the node. `attempt` counts the
+    /// transactions sent with the nonce, including this one.
+    pub async fn broadcast<T, N, P>(
+        &self,
+        provider: &P,
+        network: &str,
+        mut tx: N::TransactionRequest,
+        owner: &TrackedOwner,
+        attempt: u32,
+    ) -> Result<PendingTransactionBuilder<T, N>>
+    where
+        T: Transport + Clone,
+        N: Network,
+        N::TransactionRequest: TransactionBuilder<N>,
+        P: Provider<T, N>,
+        EthereumWallet: NetworkWallet<N>,
+    {
+        let sender = tx.from().context("Tracked transactions need a sender")?;
+        let nonce = tx.nonce().context("Tracked transactions need a nonce")?;
+        Self::fill(provider, &mut tx).await?;
+        let cancellation =
+            tx.to() == Some(sender) && tx.input().map_or(true, |input| input.is_empty());
+        let wei = |fee: Option<u128>| fee.map(|fee| fee.to_string());
+
+        let pending = NewPendingTransaction {
+            tx_hash: String::new(),
+            network_name: network.to_string(),
+            sender: sender.to_string(),
+            nonce,
+            raw_tx: Vec::new(),
+            context_type: owner.context_type.clone(),
+            names: owner.names.clone(),
+            tx_type: if tx.gas_price().is_some() {
+                "legacy"
+            } else {
+                "eip1559"
+            }
+            .to_string(),
+            gas_limit: tx.gas_limit().unwrap_or_default(),
+            gas_price_wei: wei(tx.gas_price()),
+            max_fee_per_gas_wei: wei(tx.max_fee_per_gas()),
+            max_priority_fee_per_gas_wei: wei(tx.max_priority_fee_per_gas()),
+            attempt,
+            cancellation,
+        };
+
+        let wallet = self.network_manager.get_wallet(network)?;
+        let envelope = tx
+            .build(&wallet)
+            .await
+            .context("Failed to sign transaction")?;
+        let pending = NewPendingTransaction {
+            tx_hash: format!("0x{:x}", envelope.trie_hash()),
+            raw_tx: envelope.encoded_2718(),
+            ..pending
+        };
+
+        // Stored first, so a restart during the send still finds it
+        self.repo.save(&pending).await?;
+
+        match provider.send_raw_transaction(&pending.raw_tx).await {
+            Ok(sent) => {
+                self.log_pending(&pending).await;
+                Ok(sent)
+            }
+            Err(e) => {
+                if let Err(e) = self.repo.delete_by_hash(&pending.tx_hash).await {
+                    error!(
+                        "Failed to delete unsent transaction {}: {}",
+                        pending.tx_hash, e
+                    );
+                }
+                Err(e).context("Failed to broadcast transaction")
+            }
+        }
+    }
+
+    /// Writes the `pending` transaction log rows of a broadcast transaction
+    /// A replacement is linked to the first transaction sent with its nonce
+    async fn log_pending(&self, pending: &NewPendingTransaction) {
+        for details in pending_details(pending) {
+            if let Err(e) = self.tx_log_repo.save_transaction(details).await {
+                error!(
+                    "Failed to log pending transaction {}: {}",
+                    pending.tx_hash, e
+                );
+            }
+        }
+
+        if pending.attempt <= 1 {
+            return;
+        }
+        let original = match self
+            .repo
+            .get_by_nonce(&pending.network_name, &pending.sender, pending.nonce)
+            .await
+        {
+            Ok(transactions) => transactions
+                .into_iter()
+                .find(|tx| tx.tx_hash != pending.tx_hash),
+            Err(e) => {
+                error!("Failed to find the original of {}: {}", pending.tx_hash, e);
+                return;
+            }
+        };
+        if let Some(original) = original {
+            if let Err(e) = self
+                .tx_log_repo
+                .link_replacement(&pending.tx_hash, &original.tx_hash)
+                .await
+            {
+                error!(
+                    "Failed to link {} to {}: {}",
+                    pending.tx_hash, original.tx_hash, e
+                );
+            }
+        }
+    }
+
+    /// Fills the chain ID, gas limit and fees the transaction is missing
+    async fn fill<T, N, P>(provider: &P, tx: &mut N::TransactionRequest) -> Result<()>
+    where
+        T: Transport + Clone,
+        N: Network,
+        N::TransactionRequest: TransactionBuilder<N>,
+        P: Provider<T, N>,
+    {
+        if tx.chain_id().is_none() {
+            tx.set_chain_id(provider.get_chain_id().await?);
+        }
+        if tx.gas_limit().is_none() {
+            let gas_limit = provider
+                .estimate_gas(tx)
+                .await
+                .context("Failed to estimate gas")?;
+            tx.set_gas_limit(gas_limit);
+        }
+
+        fill_missing_fees(provider, tx, false)
+            .await
+            .context("Failed to estimate fees")
+    }
+
+    /// Resolves a nonce whose transaction the sender saw mined
+    ///
+    /// The other transactions sent with the nonce were replaced.
+    pub async fn resolve(
+        &self,
+        network: &str,
+        sender: Address,
+        nonce: u64,
+        receipt: &impl ReceiptResponse,
+    ) {
+        if let Err(e) = self
+            .finalize(network, sender, nonce, Resolution::from_receipt(receipt))
+            .await
+        {
+            error!(
+                "Failed to resolve nonce {} of {} on {}: {}",
+                nonce, sender, network, e
+            );
+        }
+    }
+
+    /// Returns true while the owner has a nonce on the network that isn't resolved
+    ///
+    /// A submission made meanwhile would land next to the one still pending.
+    pub async fn has_unresolved(&self, network: &str, owner: &TrackedOwner) -> Result<bool> {
+        self.repo
+            .has_pending_for(network, &owner.context_type, &owner.names)
+            .await
+    }
+
+    /// Keeps watching a nonce the sender stopped waiting for
+    pub fn watch(self: &Arc<Self>, network: &str, sender: Address, nonce: u64) {
+        let tracker = Arc::clone(self);
+        let network = network.to_string();
+
+        tokio::spawn(async move {
+            info!(
+                "Watching nonce {} of {} on {} until it resolves",
+                nonce, sender, network
+            );
+            tracker.watch_nonce(&network, sender, nonce).await;
+        });
+    }
+
+    /// Resumes watching the transactions stored before a restart
+    /// Returns the number of nonces watched
+    pub async fn resume(self: &Arc<Self>) -> Result<usize> {
+        let nonces: BTreeSet<(String, String, i64)> = self
+            .repo
+            .get_all()
+            .await?
+            .into_iter()
+            .map(|tx| (tx.network_name, tx.sender, tx.nonce))
+            .collect();
+
+        for (network, sender, nonce) in &nonces {
+            match sender.parse::<Address>() {
+                Ok(sender) => self.watch(network, sender, *nonce as u64),
+                Err(e) => error!("Invalid sender {} of a pending transaction: {}", sender, e),
+            }
+        }
+
+        Ok(nonces.len())
+    }
+
+    async fn watch_nonce(&self, network: &str, sender: Address, nonce: u64) {
+        let heads = self.network_manager.subscribe_heads(network);
+        let mut ticker = HeadTicker::new(heads, WATCH_INTERVAL);
+
+        loop {
+            ticker.tick().await;
+            match self.check_nonce(network, sender, nonce).await {
+                Ok(true) => return,
+                Ok(false) => {}
+                Err(e) => warn!(
+                    "Failed to check nonce {} of {} on {}: {}",
+                    nonce, sender, network, e
+                ),
+            }
+        }
+    }
+
+    /// Checks the transactions of a nonce once
+    /// Returns true once the nonce is resolved
+    async fn check_nonce(&self, network: &str, sender: Address, nonce: u64) -> Result<bool> {
+        let transactions = self
+            .repo
+            .get_by_nonce(network, &sender.to_string(), nonce)
+            .await?;
+        let Some(latest) = transactions.last() else {
+            // Resolved by its sender in the meantime
+            return Ok(true);
+        };
+        let provider = self.network_manager.get_provider(network)?;
+        let config = self
+            .networks
+            .get(network)
+            .with_context(|| format!("Network {network} is not configured"))?;
+
+        if let Some(resolution) = self.find_mined(&transactions).await? {
+            // Not final until buried, a reorg may still remove it
+            let head = provider.get_block_number().await?;
+            if depth(head, resolution.block_number) < config.confirmations {
+                debug!(
+                    "Nonce {} of {} on {} was mined in 0x{:x}, waiting for {} confirmations",
+                    nonce, sender, network, resolution.tx_hash, config.confirmations
+                );
+                return Ok(false);
+            }
+
+            info!(
+                "Nonce {} of {} on {} was mined in 0x{:x}",
+                nonce, sender, network, resolution.tx_hash
+            );
+            self.finalize(network, sender, nonce, resolution).await?;
+            return Ok(true);
+        }
+
+        // The nonce was used by a transaction that isn't tracked
+        let mined_nonce = provider.get_transaction_count(sender).latest().await?;
+        if mined_nonce > nonce {
+            // One of ours may have been mined since the receipts were checked,
+            // it is finalized once buried
+            if self.find_mined(&transactions).await?.is_some() {
+                return Ok(false);
+            }
+            self.drop_nonce(network, sender, nonce, &transactions)
+                .await?;
+            return Ok(true);
+        }
+
+        let fee_bumping = &config.gas_config.fee_bumping;
+        let waited = Utc::now().signed_duration_since(latest.created_at);
+
+        let stuck = fee_bumping.enabled
+            && !latest.cancellation
+            && waited.num_seconds() >= fee_bumping.initial_wait_seconds as i64;
+
+        if stuck && latest.attempt as u32 <= fee_bumping.max_retries as u32 {
+            self.replace(config, sender, latest, false).await?;
+        } else if stuck && fee_bumping.cancel_after_max_retries {
+            self.replace(config, sender, latest, true).await?;
+        } else if provider
+            .get_transaction_by_hash(latest.tx_hash.parse()?)
+            .await?
+            .is_none()
+        {
+            info!(
+                "Rebroadcasting {} of {} on {}, no longer known to the node",
+                latest.tx_hash, sender, network
+            );
+            if let Err(e) = provider.send_raw_transaction(&latest.raw_tx).await {
+                warn!("Failed to rebroadcast {}: {}", latest.tx_hash, e);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Returns the resolution of the first transaction of the nonce that was mined
+    async fn find_mined(&self, transactions: &[PendingTransaction]) -> Result<Option<Resolution>> {
+        for tx in transactions {
+            let provider = self.network_manager.get_provider(&tx.network_name)?;
+            if let Some(receipt) = provider
+                .get_transaction_receipt(tx.tx_hash.parse()?)
+                .await?
+            {
+                return Ok(Some(Resolution::from_receipt(&receipt)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Replaces the latest transaction of a nonce with one paying higher fees,
+    /// or with a zero-value self-transfer if `cancel` is set
+    async fn replace(
+        &self,
+        config: &NetworkConfig,
+        sender: Address,
+        latest: &PendingTransaction,
+        cancel: bool,
+    ) -> Result<()> {
+        let provider = self.network_manager.get_provider(&config.name)?;
+        let estimator = GasEstimator::new(Arc::clone(&provider), config.clone());
+        let fees = estimator.bump_for_replacement(&gas_estimate(latest)?);
+
+        let tx = if cancel {
+            cancellation(sender, latest.nonce as u64, &fees)
+        } else {
+            let envelope = TxEnvelope::decode_2718(&mut latest.raw_tx.as_slice())
+                .context("Failed to decode pending transaction")?;
+            let mut tx: TransactionRequest = envelope.into();
+            tx.set_from(sender);
+            with_fees(tx, &fees)
+        };
+
+        let owner = TrackedOwner {
+            context_type: latest.context_type.clone(),
+            names: latest.names.clone(),
+        };
+        let sent = self
+            .broadcast(
+                provider.as_ref(),
+                &config.name,
+                tx,
+                &owner,
+                latest.attempt as u32 + 1,
+            )
+            .await?;
+
+        info!(
+            "{} stuck {} of {} on {} with 0x{:x} (attempt {})",
+            if cancel { "Cancelled" } else { "Replaced" },
+            latest.tx_hash,
+            sender,
+            config.name,
+            sent.tx_hash(),
+            latest.attempt + 1
+        );
+
+        Ok(())
+    }
+
+    /// Finalizes the transaction log of a mined nonce and stops tracking it
+    async fn finalize(
+        &self,
+        network: &str,
+        sender: Address,
+        nonce: u64,
+        resolution: Resolution,
+    ) -> Result<()> {
+        let transactions = self
+            .repo
+            .get_by_nonce(network, &sender.to_string(), nonce)
+            .await?;
+        let mined_hash = format!("0x{:x}", resolution.tx_hash);
+        let cancelled = transactions
+            .iter()
+            .any(|tx| tx.tx_hash == mined_hash && tx.cancellation);
+        let (status, reason) = if cancelled {
+            ("cancelled", format!("Cancelled by {mined_hash}"))
+        } else {
+            ("replaced", format!("Replaced by {mined_hash}"))
+        };
+
+        let l1_fee = self
+            .network_manager
+            .get_l1_fee(network, resolution.tx_hash, resolution.effective_gas_price)
+            .await;
+        for tx in &transactions {
+            if tx.tx_hash == mined_hash {
+                for details in mined_details(tx, &resolution, l1_fee) {
+                    self.tx_log_repo.save_transaction(details).await?;
+                }
+            } else {
+                self.tx_log_repo
+                    .resolve_pending(&tx.tx_hash, status, Some(&reason))
+                    .await?;
+            }
+        }
+
+        self.repo
+            .delete_by_nonce(network, &sender.to_string(), nonce)
+            .await?;
+        Ok(())
+    }
+
+    /// Stops tracking a nonce that was used by a transaction sent elsewhere
+    async fn drop_nonce(
+        &self,
+        network: &str,
+        sender: Address,
+        nonce: u64,
+        transactions: &[PendingTransaction],
+    ) -> Result<()> {
+        warn!(
+            "Nonce {} of {} on {} was used by another transaction, dropping {} tracked transactions",
+            nonce,
+            sender,
+            network,
+            transactions.len()
+        );
+
+        let reason = format!("Nonce {nonce} was used by another transaction");
+        for tx in transactions {
+            self.tx_log_repo
+                .resolve_pending(&tx.tx_hash, "dropped", Some(&reason))
+                .await?;
+        }
+
+        self.repo
+            .delete_by_nonce(network, &sender.to_string(), nonce)
+            .await?;
+        debug!("Dropped nonce {} of {} on {}", nonce, sender, network);
+        Ok(())
+    }
+}
+
+/// A zero-value self-transfer cancelling the transactions sent with `nonce`
+pub fn cancellation(sender: Address, nonce: u64, fees: &GasEstimate) -> TransactionRequest {
+    let tx = TransactionRequest::default()
+        .from(sender)
+        .to(sender)
+        .value(U256::ZERO)
+        .nonce(nonce)
+        .gas_limit(TRANSFER_GAS_LIMIT);
+
+    with_fees(tx, fees)
+}
+
+/// Sets the fees of an estimate on a transaction
+fn with_fees(mut tx: TransactionRequest, fees: &GasEstimate) -> TransactionRequest {
+    if let Some(gas_price) = fees.gas_price {
+        tx.set_gas_price(gas_price.to::<u128>());
+    }
+    if let Some(max_fee) = fees.max_fee_per_gas {
+        tx.set_max_fee_per_gas(max_fee.to::<u128>());
+    }
+    if let Some(priority_fee) = fees.max_priority_fee_per_gas {
+        tx.set_max_priority_fee_per_gas(priority_fee.to::<u128>());
+    }
+    tx
+}
+
+/// The gas settings a stored transaction was sent with
+fn gas_estimate(tx: &PendingTransaction) -> Result<GasEstimate> {
+    let parse = |fee: &Option<String>| -> Result<Option<U256>> {
+        fee.as_deref()
+            .map(|fee| fee.parse::<U256>().context("Invalid stored fee"))
+            .transpose()
+    };
+
+    Ok(GasEstimate {
+        gas_limit: U256::from(tx.gas_limit),
+        gas_price: parse(&tx.gas_price_wei)?,
+        max_fee_per_gas: parse(&tx.max_fee_per_gas_wei)?,
+        max_priority_fee_per_gas: parse(&tx.max_priority_fee_per_gas_wei)?,
+    })
+}
+
+/// Fee per gas in gwei for the transaction log
+fn fee_gwei(fee: Option<&str>) -> f64 {
+    fee.and_then(|fee| fee.parse::<U256>().ok())
+        .and_then(|fee| format_units(fee, "gwei").ok())
+        .and_then(|fee| fee.parse().ok())
+        .unwrap_or(0.0)
+}
+
+/// The `pending` transaction log rows of a broadcast transaction, one per owner
+fn pending_details(tx: &NewPendingTransaction) -> Vec<TransactionDetails> {
+    let gas_limits = split_gas(tx.gas_limit, tx.names.len());
+    let gas_price_gwei = fee_gwei(
+        tx.gas_price_wei
+            .as_deref()
+            .or(tx.max_fee_per_gas_wei.as_deref()),
+    );
+
+    tx.names
+        .iter()
+        .zip(gas_limits)
+        .map(|(name, gas_limit)| TransactionDetails {
+            feed_name: name.clone(),
+            network: tx.network_name.clone(),
+            tx_hash: tx.tx_hash.clone(),
+            gas_limit,
+            gas_used: 0,
+            gas_price_gwei,
+            total_cost_wei: 0,
+            l1_fee_wei: 0,
+            efficiency_percent: 0.0,
+            status: "pending".to_string(),
+            tx_type: tx.tx_type.clone(),
+            block_number: 0,
+            error_message: None,
+            confirmation_status: None,
+        })
+        .collect()
+}
+
+/// The final transaction log rows of a mined transaction, one per owner
+fn mined_details(
+    tx: &PendingTransaction,
+    resolution: &Resolution,
+    l1_fee: L1Fee,
+) -> Vec<TransactionDetails> {
+    let gas_used = split_gas(resolution.gas_used, tx.names.len());
+    let gas_limits = split_gas(tx.gas_limit as u64, tx.names.len());
+    let l1_fees = l1_fee.split(tx.names.len());
+    let gas_price_gwei = fee_gwei(Some(&resolution.effective_gas_price.to_string()));
+
+    tx.names
+        .iter()
+        .zip(gas_used.into_iter().zip(gas_limits))
+        .zip(l1_fees)
+        .map(|((name, (gas_used, gas_limit)), fee)| TransactionDetails {
+            feed_name: name.clone(),
+            network: tx.network_name.clone(),
+            tx_hash: tx.tx_hash.clone(),
+            gas_limit,
+            gas_used,
+            gas_price_gwei,
+            total_cost_wei: gas_used as u128 * resolution.effective_gas_price + fee.extra_wei(),
+            l1_fee_wei: fee.wei,
+            efficiency_percent: if gas_limit > 0 {
+                gas_used as f64 / gas_limit as f64 * 100.0
+            } else {
+                0.0
+            },
+            status: match (resolution.status, tx.cancellation) {
+                (true, true) => "cancellation",
+                (true, false) => "success",
+                (false, _) => "failed",
+            }
+            .to_string(),
+            tx_type: tx.tx_type.clone(),
+            block_number: resolution.block_number,
+            error_message: None,
+            confirmation_status: Some("confirmed".to_string()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stored_transaction(names: &[&str]) -> PendingTransaction {
+        PendingTransaction {
+            id: 1,
+            tx_hash: "0xabc".to_string(),
+            network_name: "ethereum".to_string(),
+            sender: Address::repeat_byte(1).to_string(),
+            nonce: 7,
+            raw_tx: Vec::new(),
+            context_type: "datafeed".to_string(),
+            names: names.iter().map(|name| name.to_string()).collect(),
+            tx_type: "eip1559".to_string(),
+            gas_limit: 300_001,
+            gas_price_wei: None,
+            max_fee_per_gas_wei: Some("30000000000".to_string()),
+            max_priority_fee_per_gas_wei: Some("2000000000".to_string()),
+            attempt: 2,
+            cancellation: false,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_gas_estimate_from_stored_transaction() {
+        let estimate = gas_estimate(&stored_transaction(&["eth_usd"])).unwrap();
+        assert_eq!(estimate.gas_limit, U256::from(300_001));
+        assert_eq!(estimate.gas_price, None);
+        assert_eq!(
+            estimate.max_fee_per_gas,
+            Some(U256::from(30_000_000_000u64))
+        );
+        assert_eq!(
+            estimate.max_priority_fee_per_gas,
+            Some(U256::from(2_000_000_000u64))
+        );
+
+        let mut invalid = stored_transaction(&["eth_usd"]);
+        invalid.gas_price_wei = Some("not a fee".to_string());
+        assert!(gas_estimate(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_pending_details() {
+        let tx = NewPendingTransaction {
+            tx_hash: "0xabc".to_string(),
+            network_name: "base".to_string(),
+            sender: Address::repeat_byte(1).to_string(),
+            nonce: 3,
+            raw_tx: Vec::new(),
+            context_type: "datafeed".to_string(),
+            names: vec!["eth_usd".to_string(), "btc_usd".to_string()],
+            tx_type: "legacy".to_string(),
+            gas_limit: 100_001,
+            gas_price_wei: Some("1500000000".to_string()),
+            max_fee_per_gas_wei: None,
+            max_priority_fee_per_gas_wei: None,
+            attempt: 1,
+            cancellation: false,
+        };
+
+        let details = pending_details(&tx);
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].feed_name, "eth_usd");
+        assert_eq!(details[0].gas_limit, 50_001);
+        assert_eq!(details[1].gas_limit, 50_000);
+        assert!(details.iter().all(|d| d.status == "pending"));
+        assert!(details.iter().all(|d| d.gas_price_gwei == 1.5));
+    }
+
+    #[test]
+    fn test_mined_details() {
+        let tx = stored_transaction(&["eth_usd", "btc_usd"]);
+        let resolution = Resolution {
+            tx_hash: B256::ZERO,
+            status: true,
+            gas_used: 150_001,
+            effective_gas_price: 20_000_000_000,
+            block_number: 42,
+        };
+
+        let details = mined_details(&tx, &resolution, L1Fee::default());
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].gas_used, 75_001);
+        assert_eq!(details[1].gas_used, 75_000);
+        assert_eq!(details[1].total_cost_wei, 75_000 * 20_000_000_000);
+        assert_eq!(details[0].gas_price_gwei, 20.0);
+        assert_eq!(details[0].block_number, 42);
+        assert!(details.iter().all(|d| d.status == "success"));
+
+        let failed = Resolution {
+            status: false,
+            ..resolution
+        };
+        assert_eq!(
+            mined_details(&tx, &failed, L1Fee::default())[0].status,
+            "failed"
+        );
+
+        let cancel = PendingTransaction {
+            cancellation: true,
+            ..stored_transaction(&["eth_usd"])
+        };
+        assert_eq!(
+            mined_details(&cancel, &resolution, L1Fee::default())[0].status,
+            "cancellation"
+        );
+        assert_eq!(
+            mined_details(&cancel, &failed, L1Fee::default())[0].status,
+            "failed"
+        );
+    }
+
+    #[test]
+    fn test_mined_details_with_l1_fee() {
+        let tx = stored_transaction(&["eth_usd", "btc_usd"]);
+        let resolution = Resolution {
+            tx_hash: B256::ZERO,
+            status: true,
+            gas_used: 100_000,
+            effective_gas_price: 1_000_000,
+            block_number: 42,
+        };
+
+        // The OP Stack L1 data fee comes on top of the gas
+        let l1_fee = L1Fee {
+            wei: 3_000_000_001,
+            in_gas_used: false,
+        };
+        let details = mined_details(&tx, &resolution, l1_fee);
+        assert_eq!(details[0].l1_fee_wei, 1_500_000_001);
+        assert_eq!(
+            details[0].total_cost_wei,
+            50_000 * 1_000_000 + 1_500_000_001
+        );
+        assert_eq!(
+            details[1].total_cost_wei,
+            50_000 * 1_000_000 + 1_500_000_000
+        );
+
+        // Arbitrum's L1 component is part of the gas used
+        let l1_fee = L1Fee {
+            wei: 20_000_000_000,
+            in_gas_used: true,
+        };
+        let details = mined_details(&tx, &resolution, l1_fee);
+        assert_eq!(details[0].l1_fee_wei, 10_000_000_000);
+        assert_eq!(details[0].total_cost_wei, 50_000 * 1_000_000);
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let sender = Address::repeat_byte(1);
+        let fees = GasEstimate {
+            gas_limit: U256::from(300_000),
+            gas_price: None,
+            max_fee_per_gas: Some(U256::from(33_000_000_000u64)),
+            max_priority_fee_per_gas: Some(U256::from(2_200_000_000u64)),
+        };
+
+        let tx = cancellation(sender, 9, &fees);
+        assert_eq!(tx.from, Some(sender));
+        assert_eq!(tx.to, Some(sender.into()));
+        assert_eq!(tx.value, Some(U256::ZERO));
+        assert_eq!(tx.nonce, Some(9));
+        assert_eq!(tx.gas, Some(TRANSFER_GAS_LIMIT));
+        assert_eq!(tx.max_fee_per_gas, Some(33_000_000_000));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(2_200_000_000));
+        assert_eq!(tx.gas_price, None);
+    }
+
+    #[test]
+    fn test_tracked_owner() {
+        let owner = TrackedOwner::task("cleanup");
+        assert_eq!(owner.context_type, "scheduled_task");
+        assert_eq!(owner.names, vec!["cleanup".to_string()]);
+
+        let owner = TrackedOwner::datafeeds(vec!["eth_usd".to_string()]);
+        assert_eq!(owner.context_type, "datafeed");
+    }
+}
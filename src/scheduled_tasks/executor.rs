@@ -1,11 +1,15 @@
+use crate::contracts::{preflight, RevertDecoder};
+use crate::network::{
+    wait_for_confirmations, Confirmation, NonceManager, TrackedOwner, TransactionTracker,
+};
 use crate::scheduled_tasks::models::{GasConfig, Parameter, TargetFunction};
-use crate::utils::TransactionLogger;
+use crate::utils::{DryRunSubmission, TransactionContext, TransactionLogger};
 use alloy::{
     dyn_abi::{DynSolValue, JsonAbiExt},
     json_abi::{Function, JsonAbi, Param, StateMutability},
-    network::{Network, ReceiptResponse, TransactionBuilder},
+    network::{EthereumWallet, Network, NetworkWallet, ReceiptResponse, TransactionBuilder},
     primitives::{Address, U256},
-    providers::Provider,
+    providers::{PendingTransactionBuilder, Provider},
     transports::Transport,
 };
 use anyhow::{anyhow, Context, Result};
@@ -19,6 +23,10 @@ where
     P: Provider<T, N>,
 {
     provider: Arc<P>,
+    nonce_manager: Option<Arc<NonceManager>>,
+    tracker: Option<Arc<TransactionTracker>>,
+    /// Confirmation depth at which transactions are final
+    confirmations: u64,
     _phantom_t: std::marker::PhantomData<T>,
     _phantom_n: std::marker::PhantomData<N>,
 }
@@ -32,23 +40,223 @@ where
     pub fn new(provider: Arc<P>) -> Self {
         Self {
             provider,
+            nonce_manager: None,
+            tracker: None,
+            confirmations: 1,
             _phantom_t: std::marker::PhantomData,
             _phantom_n: std::marker::PhantomData,
         }
     }
 
+    /// Takes the nonces from the manager shared by all senders of the wallet
+    pub fn with_nonce_manager(mut self, nonce_manager: Arc<NonceManager>) -> Self {
+        self.nonce_manager = Some(nonce_manager);
+        self
+    }
+
+    /// Persists the transactions sent with a managed nonce until they resolve
+    pub fn with_tracker(mut self, tracker: Arc<TransactionTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Waits for transactions to be buried this many blocks deep
+    pub fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
     pub async fn execute_function(
         &self,
         task_name: &str,
-        _network: &str,
+        network: &str,
         target_function: &TargetFunction,
         gas_config: Option<&GasConfig>,
+        from_address: Option<Address>,
     ) -> Result<N::ReceiptResponse>
     where
         N::TransactionRequest: Default + TransactionBuilder<N>,
         N::ReceiptResponse: ReceiptResponse,
+        EthereumWallet: NetworkWallet<N>,
     {
         TransactionLogger::log_execution_start("scheduled_task", task_name);
+        let mut tx = Self::build_transaction(target_function, gas_config)?;
+        if let Some(from) = from_address {
+            tx.set_from(from);
+        }
+
+        // Skip the send if the call would revert at the pending block
+        let decoder = RevertDecoder::new(&target_function.custom_errors)?;
+        if let Some(revert) = preflight(self.provider.as_ref(), &tx, &decoder).await? {
+            TransactionLogger::log_failure("scheduled_task", task_name, &revert.to_string());
+            return Err(revert.into());
+        }
+
+        let nonce = match (&self.nonce_manager, from_address) {
+            (Some(manager), Some(from)) => {
+                let nonce = manager
+                    .next_nonce(network, from, self.provider.as_ref())
+                    .await?;
+                tx.set_nonce(nonce);
+                Some((manager, from, nonce))
+            }
+            _ => None,
+        };
+
+        // Send transaction
+        debug!("Sending transaction...");
+        let sent = match (&self.tracker, nonce) {
+            (Some(tracker), Some(_)) => {
+                tracker
+                    .broadcast(
+                        self.provider.as_ref(),
+                        network,
+                        tx,
+                        &TrackedOwner::task(task_name),
+                        1,
+                    )
+                    .await
+            }
+            _ => self
+                .provider
+                .send_transaction(tx)
+                .await
+                .context("Failed to send transaction"),
+        };
+        let pending_tx = match sent {
+            Ok(pending_tx) => pending_tx,
+            Err(e) => {
+                TransactionLogger::log_failure("scheduled_task", task_name, &e.to_string());
+                if let Some((manager, from, nonce)) = nonce {
+                    manager.release(network, from, nonce).await;
+                }
+                return Err(e);
+            }
+        };
+
+        let tx_hash = *pending_tx.tx_hash();
+        debug!("Submitted transaction: 0x{:x}", tx_hash);
+
+        // Wait for confirmation, final once buried at the confirmation depth
+        debug!("Waiting for transaction confirmation...");
+        let receipt = match self.wait_for_receipt(pending_tx).await {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                error!("Failed to get transaction receipt: {:?}", e);
+                if let Some((manager, from, nonce)) = nonce {
+                    manager.release(network, from, nonce).await;
+                    if let Some(tracker) = &self.tracker {
+                        tracker.watch(network, from, nonce);
+                    }
+                }
+                return Err(e).context("Failed to get a final transaction receipt");
+            }
+        };
+        if let Some((manager, from, nonce)) = nonce {
+            manager.confirm(network, from, nonce).await;
+            if let Some(tracker) = &self.tracker {
+                tracker.resolve(network, from, nonce, &receipt).await;
+            }
+        }
+
+        if receipt.status() {
+            // Transaction succeeded - return receipt for standardized handling
+            Ok(receipt)
+        } else {
+            error!("Transaction failed: 0x{:x}", tx_hash);
+            error!("Receipt: {:?}", receipt);
+            Err(anyhow!("Transaction failed: 0x{:x}", tx_hash))
+        }
+    }
+
+    /// Waits for the receipt of a sent transaction and for its confirmations
+    async fn wait_for_receipt(
+        &self,
+        pending_tx: PendingTransactionBuilder<T, N>,
+    ) -> Result<N::ReceiptResponse> {
+        let tx_hash = *pending_tx.tx_hash();
+        let receipt = pending_tx.get_receipt().await?;
+
+        match wait_for_confirmations(self.provider.as_ref(), receipt, self.confirmations).await? {
+            Confirmation::Confirmed(receipt) => Ok(receipt),
+            Confirmation::Reorged => Err(anyhow!(
+                "Transaction 0x{:x} was reorged out before {} confirmations",
+                tx_hash,
+                self.confirmations
+            )),
+        }
+    }
+
+    /// Builds, estimates and simulates the function call with `eth_call` without broadcasting it
+    pub async fn simulate_function(
+        &self,
+        task_name: &str,
+        network: &str,
+        target_function: &TargetFunction,
+        gas_config: Option<&GasConfig>,
+        from_address: Option<Address>,
+    ) -> Result<DryRunSubmission>
+    where
+        N::TransactionRequest: Default + TransactionBuilder<N>,
+    {
+        let mut tx = Self::build_transaction(target_function, gas_config)?;
+        if let Some(from) = from_address {
+            tx.set_from(from);
+        }
+
+        let mut simulation_error = None;
+
+        let gas_limit = match gas_config.and_then(|cfg| cfg.gas_limit) {
+            Some(gas_limit) => gas_limit,
+            None => match self.provider.estimate_gas(&tx).await {
+                Ok(estimated) => estimated,
+                Err(e) => {
+                    simulation_error = Some(e.to_string());
+                    crate::constants::gas::DEFAULT_GAS_LIMIT
+                }
+            },
+        };
+        tx.set_gas_limit(gas_limit);
+
+        let gas_price_wei = match gas_config.and_then(|cfg| cfg.max_gas_price_gwei) {
+            Some(max_gas_price) => max_gas_price as u128 * 1_000_000_000,
+            None => self
+                .provider
+                .get_gas_price()
+                .await
+                .context("Failed to get gas price")?,
+        };
+
+        if simulation_error.is_none() {
+            let decoder = RevertDecoder::new(&target_function.custom_errors)?;
+            simulation_error = preflight(self.provider.as_ref(), &tx, &decoder)
+                .await?
+                .map(|revert| revert.reason);
+        }
+
+        Ok(DryRunSubmission {
+            context: TransactionContext::ScheduledTask {
+                task_name: task_name.to_string(),
+            },
+            network: network.to_string(),
+            contract_address: tx.to().unwrap_or_default(),
+            round_id: None,
+            value: None,
+            scaled_value: None,
+            gas_limit,
+            gas_price_wei,
+            simulation_error,
+        })
+    }
+
+    /// Encodes the target function call and applies the gas configuration
+    fn build_transaction(
+        target_function: &TargetFunction,
+        gas_config: Option<&GasConfig>,
+    ) -> Result<N::TransactionRequest>
+    where
+        N::TransactionRequest: Default + TransactionBuilder<N>,
+    {
         debug!("Target function details: {:?}", target_function);
         debug!("Gas config: {:?}", gas_config);
 
@@ -158,40 +366,7 @@ where
             debug!("No gas configuration provided, using defaults");
         }
 
-        // Send transaction
-        debug!("Sending transaction...");
-        let pending_tx = self
-            .provider
-            .send_transaction(tx)
-            .await
-            .map_err(|e| {
-                TransactionLogger::log_failure("scheduled_task", task_name, &e.to_string());
-                e
-            })
-            .context("Failed to send transaction")?;
-
-        let tx_hash = *pending_tx.tx_hash();
-        debug!("Submitted transaction: 0x{:x}", tx_hash);
-
-        // Wait for confirmation
-        debug!("Waiting for transaction confirmation...");
-        let receipt = pending_tx
-            .get_receipt()
-            .await
-            .map_err(|e| {
-                error!("Failed to get transaction receipt: {:?}", e);
-                e
-            })
-            .context("Failed to get transaction receipt")?;
-
-        if receipt.status() {
-            // Transaction succeeded - return receipt for standardized handling
-            Ok(receipt)
-        } else {
-            error!("Transaction failed: 0x{:x}", tx_hash);
-            error!("Receipt: {:?}", receipt);
-            Err(anyhow!("Transaction failed: 0x{:x}", tx_hash))
-        }
+        Ok(tx)
     }
 
     fn parse_function_signature(signature: &str) -> Result<(String, Vec<String>)> {
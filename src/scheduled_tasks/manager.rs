@@ -1,10 +1,11 @@
+use crate::contracts::{record_skipped_revert, WouldRevert};
 use crate::database::TransactionLogRepository;
 use crate::gas_price::GasPriceManager;
-use crate::network::NetworkManager as NetworkProviders;
+use crate::network::{NetworkManager as NetworkProviders, TransactionTracker};
 use crate::scheduled_tasks::{
     condition_checker::ConditionChecker, executor::FunctionExecutor, models::ScheduledTask,
 };
-use crate::utils::{TransactionContext, TransactionHandler, TransactionLogger};
+use crate::utils::{DryRunHandler, TransactionContext, TransactionHandler, TransactionLogger};
 use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -20,6 +21,7 @@ pub struct ScheduledTaskManager {
     handles: Arc<RwLock<Vec<JoinHandle<()>>>>,
     gas_price_manager: Option<Arc<GasPriceManager>>,
     tx_log_repo: Option<Arc<TransactionLogRepository>>,
+    tracker: Option<Arc<TransactionTracker>>,
 }
 
 impl ScheduledTaskManager {
@@ -41,6 +43,7 @@ impl ScheduledTaskManager {
             handles: Arc::new(RwLock::new(Vec::new())),
             gas_price_manager: None,
             tx_log_repo: None,
+            tracker: None,
         })
     }
 
@@ -56,6 +59,12 @@ impl ScheduledTaskManager {
         self
     }
 
+    /// Sets the tracker persisting sent transactions until they resolve
+    pub fn with_tracker(mut self, tracker: Arc<TransactionTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
     pub async fn start(&self) -> Result<()> {
         let tasks = self.tasks.read().await;
 
@@ -73,6 +82,7 @@ impl ScheduledTaskManager {
         let network_providers = self.network_providers.clone();
         let gas_price_manager = self.gas_price_manager.clone();
         let tx_log_repo = self.tx_log_repo.clone();
+        let tracker = self.tracker.clone();
         let task_clone = task.clone();
 
         let job = Job::new_async(task.schedule.as_str(), move |_uuid, _l| {
@@ -81,11 +91,12 @@ impl ScheduledTaskManager {
             let providers = network_providers.clone();
             let gas_mgr = gas_price_manager.clone();
             let tx_repo = tx_log_repo.clone();
+            let tracker = tracker.clone();
 
             Box::pin(async move {
                 debug!("Executing scheduled task: {}", name);
 
-                if let Err(e) = execute_task(task, providers, gas_mgr, tx_repo).await {
+                if let Err(e) = execute_task(task, providers, gas_mgr, tx_repo, tracker).await {
                     error!("Failed to execute scheduled task '{}': {}", name, e);
                 }
             })
@@ -149,6 +160,7 @@ async fn execute_task(
     network_providers: Arc<NetworkProviders>,
     gas_price_manager: Option<Arc<GasPriceManager>>,
     tx_log_repo: Option<Arc<TransactionLogRepository>>,
+    tracker: Option<Arc<TransactionTracker>>,
 ) -> Result<()> {
     TransactionLogger::log_execution_start("scheduled_task", &task.name);
     debug!("Task configuration: {:?}", task);
@@ -194,15 +206,65 @@ async fn execute_task(
     debug!("Target function: {:?}", task.target_function);
     debug!("Gas config: {:?}", task.gas_config);
 
-    let executor = FunctionExecutor::new(provider.clone());
-    let receipt = executor
+    let mut executor = FunctionExecutor::new(provider.clone())
+        .with_nonce_manager(Arc::clone(network_providers.nonce_manager()))
+        .with_confirmations(network_providers.get_confirmations(&task.network));
+    if let Some(tracker) = tracker {
+        executor = executor.with_tracker(tracker);
+    }
+
+    if task.dry_run {
+        let submission = executor
+            .simulate_function(
+                &task.name,
+                &task.network,
+                &task.target_function,
+                task.gas_config.as_ref(),
+                network_providers.get_wallet_address(&task.network).ok(),
+            )
+            .await
+            .context("Failed to simulate target function")?;
+
+        DryRunHandler::new(submission)
+            .with_gas_price_manager(gas_price_manager.as_ref())
+            .with_tx_log_repo(tx_log_repo.as_ref())
+            .process()
+            .await;
+
+        return Ok(());
+    }
+
+    // Default to eip1559 transaction type
+    // TODO: Get from network config when available
+    let tx_type = "eip1559".to_string();
+
+    let result = executor
         .execute_function(
             &task.name,
             &task.network,
             &task.target_function,
             task.gas_config.as_ref(),
+            network_providers.get_wallet_address(&task.network).ok(),
         )
-        .await
+        .await;
+
+    // The send was skipped, record why
+    let skipped = result
+        .as_ref()
+        .err()
+        .and_then(|e| e.downcast_ref::<WouldRevert>());
+    if let Some(revert) = skipped {
+        record_skipped_revert(
+            &task.name,
+            &task.network,
+            &tx_type,
+            tx_log_repo.as_ref(),
+            &revert.reason,
+        )
+        .await;
+    }
+
+    let receipt = result
         .map_err(|e| {
             error!(
                 "Target function execution failed for task '{}': {:?}",
@@ -224,13 +286,18 @@ async fn execute_task(
         .and_then(|cfg| cfg.gas_limit)
         .unwrap_or(crate::constants::gas::DEFAULT_GAS_LIMIT);
 
-    // Default to eip1559 transaction type
-    // TODO: Get from network config when available
-    let tx_type = "eip1559".to_string();
+    let l1_fee = network_providers
+        .get_l1_fee(
+            &task.network,
+            receipt.transaction_hash,
+            receipt.effective_gas_price,
+        )
+        .await;
 
     // Convert the receipt to the standard alloy TransactionReceipt type
     // The receipt from executor is already the correct type
     TransactionHandler::new(receipt, context, task.network.clone())
+        .with_l1_fee(l1_fee)
         .with_gas_price_manager(gas_price_manager.as_ref())
         .with_tx_log_repo(tx_log_repo.as_ref())
         .with_gas_limit(gas_limit)
@@ -255,9 +322,11 @@ mod tests {
             target_function: TargetFunction {
                 contract_address: "0x1234567890123456789012345678901234567890".to_string(),
                 function: "execute()".to_string(),
+                custom_errors: vec![],
                 parameters: vec![],
             },
             gas_config: None,
+            dry_run: false,
         }
     }
 
@@ -274,6 +343,7 @@ mod tests {
             target_function: TargetFunction {
                 contract_address: "0x1234567890123456789012345678901234567890".to_string(),
                 function: "performTask()".to_string(),
+                custom_errors: vec![],
                 parameters: vec![],
             },
             gas_config: Some(GasConfig {
@@ -281,6 +351,7 @@ mod tests {
                 max_gas_price_gwei: Some(50),
                 priority_fee_gwei: Some(2),
             }),
+            dry_run: false,
         }
     }
 
@@ -349,6 +420,7 @@ mod tests {
             target_function: TargetFunction {
                 contract_address: "0xABCDEF1234567890123456789012345678901234".to_string(),
                 function: "updatePrices(address[],uint256[])".to_string(),
+                custom_errors: vec![],
                 parameters: vec![
                     Parameter {
                         param_type: "address[]".to_string(),
@@ -364,6 +436,7 @@ mod tests {
                 ],
             },
             gas_config: None,
+            dry_run: false,
         };
 
         assert_eq!(task.target_function.parameters.len(), 2);
@@ -384,9 +457,11 @@ mod tests {
             target_function: TargetFunction {
                 contract_address: "0x9876543210987654321098765432109876543210".to_string(),
                 function: "process()".to_string(),
+                custom_errors: vec![],
                 parameters: vec![],
             },
             gas_config: None,
+            dry_run: false,
         };
 
         match task.check_condition {
@@ -1,3 +1,4 @@
+use crate::contracts::RevertDecoder;
 use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -10,6 +11,9 @@ pub struct ScheduledTask {
     pub check_condition: Option<CheckCondition>,
     pub target_function: TargetFunction,
     pub gas_config: Option<GasConfig>,
+    /// Simulate the transaction instead of broadcasting it
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +36,9 @@ pub struct TargetFunction {
     pub contract_address: String,
     pub function: String,
     pub parameters: Vec<Parameter>,
+    /// Custom error signatures used to decode reverts, e.g. "NotReady(uint256)"
+    #[serde(default)]
+    pub custom_errors: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +64,8 @@ impl ScheduledTask {
         // Validate addresses
         self.validate_address(&self.target_function.contract_address)?;
 
+        RevertDecoder::new(&self.target_function.custom_errors).map_err(|e| format!("{e:#}"))?;
+
         if let Some(condition) = &self.check_condition {
             match condition {
                 CheckCondition::Property {
@@ -100,9 +109,11 @@ mod tests {
             target_function: TargetFunction {
                 contract_address: "0x1234567890123456789012345678901234567890".to_string(),
                 function: "execute()".to_string(),
+                custom_errors: vec![],
                 parameters: vec![],
             },
             gas_config: None,
+            dry_run: false,
         };
 
         match task.validate() {
@@ -121,9 +132,11 @@ mod tests {
             target_function: TargetFunction {
                 contract_address: "0x1234567890123456789012345678901234567890".to_string(),
                 function: "execute()".to_string(),
+                custom_errors: vec![],
                 parameters: vec![],
             },
             gas_config: None,
+            dry_run: false,
         };
 
         assert!(task.validate().is_err());
@@ -175,6 +188,7 @@ mod tests {
         let func1 = TargetFunction {
             contract_address: "0xABCDEF1234567890123456789012345678901234".to_string(),
             function: "pause()".to_string(),
+            custom_errors: vec![],
             parameters: vec![],
         };
         assert_eq!(func1.parameters.len(), 0);
@@ -183,6 +197,7 @@ mod tests {
         let func2 = TargetFunction {
             contract_address: "0xABCDEF1234567890123456789012345678901234".to_string(),
             function: "setThreshold(uint256)".to_string(),
+            custom_errors: vec![],
             parameters: vec![Parameter {
                 param_type: "uint256".to_string(),
                 value: json!("1000"),
@@ -194,6 +209,7 @@ mod tests {
         let func3 = TargetFunction {
             contract_address: "0xABCDEF1234567890123456789012345678901234".to_string(),
             function: "transferBatch(address[],uint256[])".to_string(),
+            custom_errors: vec![],
             parameters: vec![
                 Parameter {
                     param_type: "address[]".to_string(),
@@ -263,6 +279,7 @@ mod tests {
             target_function: TargetFunction {
                 contract_address: "0x1234567890123456789012345678901234567890".to_string(),
                 function: "ping()".to_string(),
+                custom_errors: vec![],
                 parameters: vec![],
             },
             gas_config: Some(GasConfig {
@@ -270,6 +287,7 @@ mod tests {
                 max_gas_price_gwei: Some(30),
                 priority_fee_gwei: Some(2),
             }),
+            dry_run: false,
         };
 
         // Serialize
@@ -305,9 +323,11 @@ mod tests {
                 target_function: TargetFunction {
                     contract_address: "0x1234567890123456789012345678901234567890".to_string(),
                     function: "test()".to_string(),
+                    custom_errors: vec![],
                     parameters: vec![],
                 },
                 gas_config: None,
+                dry_run: false,
             };
 
             assert!(
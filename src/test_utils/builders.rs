@@ -62,6 +62,14 @@ impl NetworkBuilder {
             gas_config: self.gas_config,
             gas_token: self.gas_token,
             gas_token_symbol: self.gas_token_symbol,
+            batching: Default::default(),
+            confirmations: 1,
+            rpc_endpoints: Vec::new(),
+            rpc_failover: Default::default(),
+            read_quorum: Default::default(),
+            check_feeds_on_new_heads: false,
+            chain_id: None,
+            l2_type: "none".to_string(),
         }
     }
 
@@ -156,6 +164,9 @@ impl ConfigBuilder {
             metrics: self.metrics,
             gas_price_feeds: self.gas_price_feeds,
             scheduled_tasks: self.scheduled_tasks,
+            fetch_cache: Default::default(),
+            fetch_retry: Default::default(),
+            rate_limits: Default::default(),
         }
     }
 
@@ -16,6 +16,14 @@ mod examples {
             gas_config: Default::default(),
             gas_token: "ethereum".to_string(),
             gas_token_symbol: "ETH".to_string(),
+            batching: Default::default(),
+            confirmations: 1,
+            rpc_endpoints: Vec::new(),
+            rpc_failover: Default::default(),
+            read_quorum: Default::default(),
+            check_feeds_on_new_heads: false,
+            chain_id: None,
+            l2_type: "none".to_string(),
         };
 
         // NEW WAY (with utilities) - clean and expressive
@@ -215,11 +215,13 @@ impl TransactionDetailsFactory {
             gas_used,
             gas_price_gwei,
             total_cost_wei,
+            l1_fee_wei: 0,
             efficiency_percent: efficiency,
             tx_type: "eip1559".to_string(),
             status: "success".to_string(),
             block_number: 18_000_000,
             error_message: None,
+            confirmation_status: Some("confirmed".to_string()),
         }
     }
 
@@ -238,11 +240,13 @@ impl TransactionDetailsFactory {
             gas_used: 0, // No gas used for failed transactions
             gas_price_gwei: 30.0,
             total_cost_wei: 0,
+            l1_fee_wei: 0,
             efficiency_percent: 0.0,
             tx_type: "eip1559".to_string(),
             status: "failed".to_string(),
             block_number: 18_000_000,
             error_message: Some(error_message.to_string()),
+            confirmation_status: Some("confirmed".to_string()),
         }
     }
 
@@ -0,0 +1,132 @@
+use crate::database::transaction_repository::TransactionLogRepository;
+use crate::gas_price::GasPriceManager;
+use crate::metrics::update_metrics::UpdateMetrics;
+use crate::utils::TransactionContext;
+use alloy::primitives::Address;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+/// A transaction that was built, estimated and simulated but never broadcast
+#[derive(Debug, Clone)]
+pub struct DryRunSubmission {
+    pub context: TransactionContext,
+    pub network: String,
+    pub contract_address: Address,
+    /// Round the value would have been submitted to (datafeeds only)
+    pub round_id: Option<u64>,
+    /// Value before scaling (datafeeds only)
+    pub value: Option<f64>,
+    /// Value as it would have been submitted (datafeeds only)
+    pub scaled_value: Option<i128>,
+    pub gas_limit: u64,
+    /// Gas price used for the estimate (the max fee for EIP-1559 transactions)
+    pub gas_price_wei: u128,
+    /// Revert reason if the simulated call failed
+    pub simulation_error: Option<String>,
+}
+
+impl DryRunSubmission {
+    /// Upper bound of the transaction cost in wei
+    pub fn estimated_cost_wei(&self) -> u128 {
+        self.gas_limit as u128 * self.gas_price_wei
+    }
+
+    /// Returns "success" or "reverted"
+    pub fn status(&self) -> &'static str {
+        if self.simulation_error.is_none() {
+            "success"
+        } else {
+            "reverted"
+        }
+    }
+}
+
+/// Logs a simulated submission, records its metrics and stores it in the database
+pub struct DryRunHandler<'a> {
+    submission: DryRunSubmission,
+    gas_price_manager: Option<&'a Arc<GasPriceManager>>,
+    tx_log_repo: Option<&'a Arc<TransactionLogRepository>>,
+}
+
+impl<'a> DryRunHandler<'a> {
+    pub fn new(submission: DryRunSubmission) -> Self {
+        Self {
+            submission,
+            gas_price_manager: None,
+            tx_log_repo: None,
+        }
+    }
+
+    pub fn with_gas_price_manager(mut self, manager: Option<&'a Arc<GasPriceManager>>) -> Self {
+        self.gas_price_manager = manager;
+        self
+    }
+
+    pub fn with_tx_log_repo(mut self, repo: Option<&'a Arc<TransactionLogRepository>>) -> Self {
+        self.tx_log_repo = repo;
+        self
+    }
+
+    pub async fn process(self) {
+        let submission = &self.submission;
+        let name = submission.context.name();
+
+        // The L1 fee of rollups is only known once the transaction is mined
+        let estimated_cost_usd = match self.gas_price_manager {
+            Some(manager) => manager
+                .calculate_usd_cost(
+                    &submission.network,
+                    name,
+                    "dry-run",
+                    submission.gas_limit,
+                    submission.gas_price_wei,
+                    0,
+                )
+                .await
+                .map(|cost| cost.total_cost_usd),
+            None => None,
+        };
+
+        match &submission.simulation_error {
+            None => info!(
+                "[dry-run] Would submit {} {} to {} on {}: round={:?}, value={:?}, scaled={:?}, \
+                 gas_limit={}, gas_price={} wei, estimated cost={} wei (${})",
+                submission.context.context_type(),
+                name,
+                submission.contract_address,
+                submission.network,
+                submission.round_id,
+                submission.value,
+                submission.scaled_value,
+                submission.gas_limit,
+                submission.gas_price_wei,
+                submission.estimated_cost_wei(),
+                estimated_cost_usd
+                    .map(|usd| format!("{usd:.6}"))
+                    .unwrap_or_else(|| "n/a".to_string()),
+            ),
+            Some(reason) => warn!(
+                "[dry-run] Simulated {} {} on {} would revert: {}",
+                submission.context.context_type(),
+                name,
+                submission.network,
+                reason
+            ),
+        }
+
+        UpdateMetrics::record_dry_run(
+            name,
+            &submission.network,
+            submission.context.context_type(),
+            submission.status(),
+            submission.gas_limit,
+            estimated_cost_usd,
+        );
+
+        if let Some(repo) = self.tx_log_repo {
+            if let Err(e) = repo.save_dry_run(submission, estimated_cost_usd).await {
+                error!("Failed to log dry-run submission for {}: {}", name, e);
+            }
+        }
+    }
+}
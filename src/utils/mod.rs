@@ -1,6 +1,8 @@
+pub mod dry_run;
 pub mod transaction_handler;
 pub mod tx_logger;
 
+pub use dry_run::{DryRunHandler, DryRunSubmission};
 pub use transaction_handler::{TransactionContext, TransactionHandler};
 pub use tx_logger::TransactionLogger;
 
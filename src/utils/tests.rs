@@ -92,3 +92,49 @@ mod transaction_handler_tests {
     // actual TransactionReceipt instances from real transactions.
     // These are tested in the integration tests.
 }
+
+#[cfg(test)]
+mod dry_run_tests {
+    use super::super::dry_run::{DryRunHandler, DryRunSubmission};
+    use super::super::transaction_handler::TransactionContext;
+    use alloy::primitives::Address;
+
+    fn submission(simulation_error: Option<String>) -> DryRunSubmission {
+        DryRunSubmission {
+            context: TransactionContext::Datafeed {
+                feed_name: "eth_usd".to_string(),
+            },
+            network: "ethereum".to_string(),
+            contract_address: Address::ZERO,
+            round_id: Some(42),
+            value: Some(2500.5),
+            scaled_value: Some(250_050_000_000),
+            gas_limit: 150_000,
+            gas_price_wei: 20_000_000_000,
+            simulation_error,
+        }
+    }
+
+    #[test]
+    fn test_estimated_cost() {
+        assert_eq!(submission(None).estimated_cost_wei(), 3_000_000_000_000_000);
+    }
+
+    #[test]
+    fn test_status() {
+        assert_eq!(submission(None).status(), "success");
+        assert_eq!(
+            submission(Some(
+                "execution reverted: cannot report on previous rounds".into()
+            ))
+            .status(),
+            "reverted"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_without_database() {
+        // Logs and records metrics only, there is nothing to broadcast or store
+        DryRunHandler::new(submission(None)).process().await;
+    }
+}
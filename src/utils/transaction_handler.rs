@@ -1,4 +1,5 @@
 use crate::database::transaction_repository::TransactionLogRepository;
+use crate::gas::L1Fee;
 use crate::gas_price::GasPriceManager;
 use crate::metrics::gas_metrics::GasMetrics;
 use crate::metrics::update_metrics::UpdateMetrics;
@@ -38,6 +39,8 @@ pub struct TransactionHandler<'a> {
     gas_price_manager: Option<&'a Arc<GasPriceManager>>,
     tx_log_repo: Option<&'a Arc<TransactionLogRepository>>,
     gas_limit: Option<u64>,
+    gas_used: Option<u64>,
+    l1_fee: L1Fee,
     transaction_type: Option<String>,
 }
 
@@ -50,6 +53,8 @@ impl<'a> TransactionHandler<'a> {
             gas_price_manager: None,
             tx_log_repo: None,
             gas_limit: None,
+            gas_used: None,
+            l1_fee: L1Fee::default(),
             transaction_type: None,
         }
     }
@@ -69,6 +74,18 @@ impl<'a> TransactionHandler<'a> {
         self
     }
 
+    /// Sets the gas attributed to this context when the transaction is shared, e.g. by a batch
+    pub fn with_gas_used(mut self, gas_used: u64) -> Self {
+        self.gas_used = Some(gas_used);
+        self
+    }
+
+    /// Sets the L1 fee of a rollup transaction, or the share attributed to this context
+    pub fn with_l1_fee(mut self, l1_fee: L1Fee) -> Self {
+        self.l1_fee = l1_fee;
+        self
+    }
+
     pub fn with_transaction_type(mut self, tx_type: String) -> Self {
         self.transaction_type = Some(tx_type);
         self
@@ -76,7 +93,7 @@ impl<'a> TransactionHandler<'a> {
 
     pub async fn process(self) -> Result<()> {
         let tx_hash = self.receipt.transaction_hash;
-        let gas_used = self.receipt.gas_used;
+        let gas_used = self.gas_used.unwrap_or(self.receipt.gas_used as u64);
         let effective_gas_price = self.receipt.effective_gas_price;
 
         // Log successful transaction
@@ -126,8 +143,9 @@ impl<'a> TransactionHandler<'a> {
                     &self.network,
                     self.context.name(),
                     &tx_hash_str,
-                    gas_used as u64,
+                    gas_used,
                     effective_gas_price,
+                    self.l1_fee.extra_wei(),
                 )
                 .await
             {
@@ -135,16 +153,19 @@ impl<'a> TransactionHandler<'a> {
                 GasMetrics::record_usd_cost(
                     self.context.name(),
                     &self.network,
-                    gas_used as u64,
+                    gas_used,
                     effective_gas_price,
+                    self.l1_fee.extra_wei(),
                     gas_cost_usd.gas_token_price_usd,
                 );
 
                 info!(
-                    "Transaction cost: ${:.6} USD (gas: {}, price: {} wei, token: ${:.2})",
+                    "Transaction cost: ${:.6} USD (gas: {}, price: {} wei, L1 fee: {} wei, \
+                     token: ${:.2})",
                     gas_cost_usd.total_cost_usd,
                     gas_used,
                     effective_gas_price,
+                    self.l1_fee.wei,
                     gas_cost_usd.gas_token_price_usd
                 );
             }
@@ -159,6 +180,7 @@ impl<'a> TransactionHandler<'a> {
                 &self.receipt,
                 alloy::primitives::U256::from(gas_limit),
                 tx_type,
+                self.l1_fee,
             );
         }
 
@@ -1,3 +1,4 @@
+use crate::database::TransactionLogRepository;
 use crate::gas_price::GasPriceManager;
 use crate::metrics::{EconomicMetrics, FeedMetrics};
 use crate::network::NetworkManager;
@@ -10,8 +11,11 @@ use tracing::{debug, error, info, warn};
 pub struct WalletBalanceMonitor {
     network_manager: Arc<NetworkManager>,
     gas_price_manager: Option<Arc<GasPriceManager>>,
+    /// Transaction log the daily spending is read from, L1 fees of rollups included
+    tx_log_repo: Option<Arc<TransactionLogRepository>>,
     update_interval_seconds: u64,
     /// Track daily spending for runway calculation (network -> daily spend in USD)
+    /// Used for networks without a transaction log
     daily_spending_estimates: HashMap<String, f64>,
 }
 
@@ -21,6 +25,7 @@ impl WalletBalanceMonitor {
         Self {
             network_manager,
             gas_price_manager: None,
+            tx_log_repo: None,
             update_interval_seconds: 60, // Default to 1 minute
             daily_spending_estimates: HashMap::new(),
         }
@@ -32,6 +37,12 @@ impl WalletBalanceMonitor {
         self
     }
 
+    /// Set the transaction log repository to read the daily spending from
+    pub fn with_tx_log_repo(mut self, tx_log_repo: Arc<TransactionLogRepository>) -> Self {
+        self.tx_log_repo = Some(tx_log_repo);
+        self
+    }
+
     /// Start monitoring wallet balances
     pub async fn start(self) {
         let mut interval = interval(Duration::from_secs(self.update_interval_seconds));
@@ -113,7 +124,14 @@ impl WalletBalanceMonitor {
                 );
 
                 // Update runway if we have spending data
-                if let Some(&daily_spend) = self.daily_spending_estimates.get(network_name) {
+                let daily_spend = match self
+                    .logged_daily_spend(network_name, native_token_price)
+                    .await
+                {
+                    Some(daily_spend) => Some(daily_spend),
+                    None => self.daily_spending_estimates.get(network_name).copied(),
+                };
+                if let Some(daily_spend) = daily_spend {
                     let balance_usd = balance_native * native_token_price;
                     EconomicMetrics::update_runway_days(
                         network_name,
@@ -146,6 +164,23 @@ impl WalletBalanceMonitor {
             }
         }
     }
+
+    /// Spending of the last day in USD from the transaction log
+    ///
+    /// The logged costs include the L1 fees of rollup transactions.
+    async fn logged_daily_spend(&self, network_name: &str, native_token_price: f64) -> Option<f64> {
+        let repo = self.tx_log_repo.as_ref()?;
+        match repo.get_network_cost_since(network_name, 24).await {
+            Ok(cost_wei) => Some(cost_wei as f64 / 1e18 * native_token_price),
+            Err(e) => {
+                warn!(
+                    "Failed to read the daily spending on {} from the transaction log: {}",
+                    network_name, e
+                );
+                None
+            }
+        }
+    }
 }
 
 #[cfg(test)]
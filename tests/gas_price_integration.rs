@@ -34,6 +34,7 @@ async fn test_gas_price_manager_creation() {
             "0x123",
             gas_used,
             gas_price,
+            0,
         )
         .await;
 
@@ -75,6 +76,7 @@ async fn test_contract_updater_with_gas_price_manager() {
             "0xabc123",
             21000,
             30_000_000_000,
+            0,
         )
         .await;
 
@@ -136,6 +138,7 @@ async fn test_usd_cost_metrics_recording() {
         "ethereum-mainnet",
         gas_used,
         gas_price,
+        0,
         token_price_usd,
     );
 
@@ -156,6 +159,17 @@ async fn test_usd_cost_metrics_recording() {
         "ethereum-mainnet",
         100000,          // More complex transaction
         150_000_000_000, // 150 gwei (high gas)
+        0,
+        token_price_usd,
+    );
+
+    // An OP Stack transaction paying an L1 data fee on top of its gas
+    GasMetrics::record_usd_cost(
+        "base-feed",
+        "base-mainnet",
+        100000,
+        10_000_000,
+        20_000_000_000_000, // 0.00002 ETH L1 fee
         token_price_usd,
     );
 
@@ -174,6 +188,14 @@ fn create_test_config() -> OmikujiConfig {
             gas_config: GasConfig::default(),
             gas_token: "ethereum".to_string(),
             gas_token_symbol: "ETH".to_string(),
+            batching: Default::default(),
+            confirmations: 1,
+            rpc_endpoints: Vec::new(),
+            rpc_failover: Default::default(),
+            read_quorum: Default::default(),
+            check_feeds_on_new_heads: false,
+            chain_id: None,
+            l2_type: "none".to_string(),
         }],
         datafeeds: vec![Datafeed {
             name: "test-feed".to_string(),
@@ -181,6 +203,9 @@ fn create_test_config() -> OmikujiConfig {
             check_frequency: 60,
             contract_address: "0x0000000000000000000000000000000000000000".to_string(),
             contract_type: "fluxmon".to_string(),
+            setter_signature: None,
+            custom_abi: None,
+            custom_errors: Vec::new(),
             read_contract_config: false,
             decimals: Some(8),
             min_value: None,
@@ -191,6 +216,17 @@ fn create_test_config() -> OmikujiConfig {
             feed_json_path: "price".to_string(),
             feed_json_path_timestamp: None,
             data_retention_days: 7,
+            request: Default::default(),
+            sources: vec![],
+            aggregation: Default::default(),
+            min_sources: 1,
+            transform: None,
+            outlier_guard: None,
+            dry_run: false,
+            gas_config: None,
+            deployments: vec![],
+            stream: None,
+            onchain: None,
         }],
         database_cleanup: DatabaseCleanupConfig {
             enabled: false,
@@ -217,5 +253,8 @@ fn create_test_config() -> OmikujiConfig {
             },
         },
         scheduled_tasks: vec![],
+        fetch_cache: Default::default(),
+        fetch_retry: Default::default(),
+        rate_limits: Default::default(),
     }
 }
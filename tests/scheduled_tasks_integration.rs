@@ -21,6 +21,7 @@ mod integration_tests {
             target_function: TargetFunction {
                 contract_address: "0x5FbDB2315678afecb367f032d93F642f64180aa3".to_string(),
                 function: "distributeRewards(address[])".to_string(),
+                custom_errors: vec![],
                 parameters: vec![Parameter {
                     param_type: "address[]".to_string(),
                     value: json!([
@@ -34,6 +35,7 @@ mod integration_tests {
                 max_gas_price_gwei: Some(50),
                 priority_fee_gwei: Some(2),
             }),
+            dry_run: false,
         }
     }
 
@@ -108,6 +110,7 @@ mod integration_tests {
             target_function: TargetFunction {
                 contract_address: "0x9876543210987654321098765432109876543210".to_string(),
                 function: "performMaintenance(uint256,address[],bool)".to_string(),
+                custom_errors: vec![],
                 parameters: vec![
                     Parameter {
                         param_type: "uint256".to_string(),
@@ -128,6 +131,7 @@ mod integration_tests {
                 ],
             },
             gas_config: None,
+            dry_run: false,
         };
 
         assert_eq!(task.target_function.parameters.len(), 3);
@@ -151,9 +155,11 @@ mod integration_tests {
             target_function: TargetFunction {
                 contract_address: "0xABCDEF1234567890123456789012345678901234".to_string(),
                 function: "processQueue()".to_string(),
+                custom_errors: vec![],
                 parameters: vec![],
             },
             gas_config: None,
+            dry_run: false,
         };
 
         match property_task.check_condition {
@@ -181,9 +187,11 @@ mod integration_tests {
             target_function: TargetFunction {
                 contract_address: "0xFEDCBA9876543210FEDCBA9876543210FEDCBA98".to_string(),
                 function: "claimRewards()".to_string(),
+                custom_errors: vec![],
                 parameters: vec![],
             },
             gas_config: None,
+            dry_run: false,
         };
 
         match function_task.check_condition {